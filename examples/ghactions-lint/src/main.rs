@@ -0,0 +1,43 @@
+//! Validate Action files
+//!
+//! ```bash
+//! cargo run --example ghactions-lint -- action.yml examples/*/action.yml
+//! ```
+use ghactions::{ActionYML, Severity};
+
+fn main() {
+    let paths: Vec<String> = std::env::args().skip(1).collect();
+    if paths.is_empty() {
+        eprintln!("Usage: ghactions-lint <paths...>");
+        std::process::exit(2);
+    }
+    // Annotate the files when running in GitHub Actions
+    let annotate = std::env::var("GITHUB_ACTIONS").is_ok_and(|value| value == "true");
+
+    let mut errors = 0;
+    for path in paths.iter() {
+        for issue in ActionYML::validate_file(path) {
+            if issue.severity == Severity::Error {
+                errors += 1;
+            }
+            let location = match issue.line {
+                Some(line) => format!("{}:{}", path, line),
+                None => path.clone(),
+            };
+            println!("{}: {}", location, issue);
+
+            if annotate {
+                let line = issue
+                    .line
+                    .map(|line| format!(",line={}", line))
+                    .unwrap_or_default();
+                println!("::{} file={}{}::{}", issue.severity, path, line, issue);
+            }
+        }
+    }
+
+    if errors > 0 {
+        eprintln!("{} error(s) found", errors);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,20 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ghactions_core::env_files::{get_output_path, reset_paths_for_testing};
+
+fn bench_output_path(c: &mut Criterion) {
+    std::env::set_var("GITHUB_OUTPUT", "/tmp/ghactions-bench-output.env");
+
+    c.bench_function("get_output_path (cached)", |b| {
+        reset_paths_for_testing();
+        b.iter(|| black_box(get_output_path()))
+    });
+    c.bench_function("get_output_path (uncached)", |b| {
+        b.iter(|| {
+            reset_paths_for_testing();
+            black_box(get_output_path())
+        })
+    });
+}
+
+criterion_group!(benches, bench_output_path);
+criterion_main!(benches);
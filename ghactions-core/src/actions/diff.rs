@@ -0,0 +1,656 @@
+//! # Diff
+//!
+//! Compare two Action YAML files and report the differences between them.
+//!
+//! The differences can be rendered as text ([render_text]) for error messages or as
+//! a Markdown table ([render_markdown]) for the job summary.
+use std::fmt::{Display, Formatter};
+
+use super::models::{ActionRunStep, ActionYML};
+use crate::ActionsError;
+
+/// Schema Mismatch between the Action YAML file the code expects and the file on disk
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaMismatch {
+    /// The Action file could not be loaded
+    LoadError(String),
+    /// Input is present in the file but not in the code
+    InputAdded(String),
+    /// Input is expected by the code but missing from the file
+    InputRemoved(String),
+    /// Input `required` flag differs (name, expected, found)
+    InputRequiredChanged(String, Option<bool>, Option<bool>),
+    /// Input `default` value differs (name, expected, found)
+    InputDefaultChanged(String, Option<String>, Option<String>),
+    /// Input `description` differs (name, expected, found)
+    InputDescriptionChanged(String, Option<String>, Option<String>),
+    /// Output is present in the file but not in the code
+    OutputAdded(String),
+    /// Output is expected by the code but missing from the file
+    OutputRemoved(String),
+    /// Output `description` differs (name, expected, found)
+    OutputDescriptionChanged(String, Option<String>, Option<String>),
+    /// Output `value` differs (name, expected, found)
+    OutputValueChanged(String, Option<String>, Option<String>),
+    /// Part of the `runs` section differs (field, expected, found)
+    RunsChanged(String, Option<String>, Option<String>),
+    /// The schema matches but the file content differs from the generated content
+    /// (first line which differs, e.g. formatting or comments)
+    ContentChanged(usize),
+}
+
+impl Display for SchemaMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaMismatch::LoadError(err) => write!(f, "failed to load action file: {}", err),
+            SchemaMismatch::InputAdded(name) => write!(f, "input `{}` is not expected", name),
+            SchemaMismatch::InputRemoved(name) => write!(f, "input `{}` is missing", name),
+            SchemaMismatch::InputRequiredChanged(name, expected, found) => write!(
+                f,
+                "input `{}` required changed (expected: {:?}, found: {:?})",
+                name, expected, found
+            ),
+            SchemaMismatch::InputDefaultChanged(name, expected, found) => write!(
+                f,
+                "input `{}` default changed (expected: {:?}, found: {:?})",
+                name, expected, found
+            ),
+            SchemaMismatch::InputDescriptionChanged(name, expected, found) => write!(
+                f,
+                "input `{}` description changed (expected: {:?}, found: {:?})",
+                name, expected, found
+            ),
+            SchemaMismatch::OutputAdded(name) => write!(f, "output `{}` is not expected", name),
+            SchemaMismatch::OutputRemoved(name) => write!(f, "output `{}` is missing", name),
+            SchemaMismatch::OutputDescriptionChanged(name, expected, found) => write!(
+                f,
+                "output `{}` description changed (expected: {:?}, found: {:?})",
+                name, expected, found
+            ),
+            SchemaMismatch::OutputValueChanged(name, expected, found) => write!(
+                f,
+                "output `{}` value changed (expected: {:?}, found: {:?})",
+                name, expected, found
+            ),
+            SchemaMismatch::RunsChanged(field, expected, found) => write!(
+                f,
+                "runs `{}` changed (expected: {:?}, found: {:?})",
+                field, expected, found
+            ),
+            SchemaMismatch::ContentChanged(line) => write!(
+                f,
+                "content differs from the generated file (starting at line {})",
+                line
+            ),
+        }
+    }
+}
+
+impl SchemaMismatch {
+    /// Split the mismatch into a (section, name, change, expected, found) table row
+    fn row(
+        &self,
+    ) -> (
+        &'static str,
+        &str,
+        &'static str,
+        Option<String>,
+        Option<String>,
+    ) {
+        let flag = |value: &Option<bool>| value.map(|value| value.to_string());
+        match self {
+            SchemaMismatch::LoadError(err) => ("file", "", "load error", None, Some(err.clone())),
+            SchemaMismatch::InputAdded(name) => ("input", name, "not expected", None, None),
+            SchemaMismatch::InputRemoved(name) => ("input", name, "missing", None, None),
+            SchemaMismatch::InputRequiredChanged(name, expected, found) => {
+                ("input", name, "required", flag(expected), flag(found))
+            }
+            SchemaMismatch::InputDefaultChanged(name, expected, found) => {
+                ("input", name, "default", expected.clone(), found.clone())
+            }
+            SchemaMismatch::InputDescriptionChanged(name, expected, found) => (
+                "input",
+                name,
+                "description",
+                expected.clone(),
+                found.clone(),
+            ),
+            SchemaMismatch::OutputAdded(name) => ("output", name, "not expected", None, None),
+            SchemaMismatch::OutputRemoved(name) => ("output", name, "missing", None, None),
+            SchemaMismatch::OutputDescriptionChanged(name, expected, found) => (
+                "output",
+                name,
+                "description",
+                expected.clone(),
+                found.clone(),
+            ),
+            SchemaMismatch::OutputValueChanged(name, expected, found) => {
+                ("output", name, "value", expected.clone(), found.clone())
+            }
+            SchemaMismatch::RunsChanged(field, expected, found) => {
+                ("runs", field, "changed", expected.clone(), found.clone())
+            }
+            SchemaMismatch::ContentChanged(line) => (
+                "file",
+                "",
+                "content changed",
+                None,
+                Some(format!("line {}", line)),
+            ),
+        }
+    }
+}
+
+/// Render mismatches as a plain text list (used for error messages)
+pub fn render_text(mismatches: &[SchemaMismatch]) -> String {
+    mismatches
+        .iter()
+        .map(|mismatch| format!(" - {}", mismatch))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Render mismatches as a Markdown table (e.g. for the job summary)
+pub fn render_markdown(mismatches: &[SchemaMismatch]) -> String {
+    // Values are shown as inline code, pipes and new lines would break the table
+    let cell = |value: Option<String>| match value {
+        Some(value) => format!("`{}`", value.replace('|', "\\|").replace('\n', "\\n")),
+        None => "-".to_string(),
+    };
+
+    let mut markdown = String::from("### Action file mismatch\n\n");
+    markdown.push_str("| Section | Name | Change | Expected | Found |\n");
+    markdown.push_str("| --- | --- | --- | --- | --- |\n");
+    for mismatch in mismatches {
+        let (section, name, change, expected, found) = mismatch.row();
+        let name = match name.is_empty() {
+            true => "-".to_string(),
+            false => format!("`{}`", name),
+        };
+        markdown.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            section,
+            name,
+            change,
+            cell(expected),
+            cell(found)
+        ));
+    }
+    markdown
+}
+
+impl ActionYML {
+    /// Compare the inputs and outputs of this (expected) Action with another (actual) Action
+    ///
+    /// Only the changes which break the Action are reported (see [ActionYML::diff]).
+    pub fn schema_diff(&self, actual: &ActionYML) -> Vec<SchemaMismatch> {
+        self.compare(actual, false)
+    }
+
+    /// Compare this (expected) Action with another (actual) Action
+    ///
+    /// Includes description, output value and `runs` changes. The `runs` section of user
+    /// authored composite Actions is not compared.
+    pub fn diff(&self, actual: &ActionYML) -> Vec<SchemaMismatch> {
+        self.compare(actual, true)
+    }
+
+    fn compare(&self, actual: &ActionYML, full: bool) -> Vec<SchemaMismatch> {
+        let mut mismatches = Vec::new();
+
+        for (name, input) in self.inputs.iter() {
+            match actual.inputs.get(name) {
+                Some(other) => {
+                    if input.required.unwrap_or(false) != other.required.unwrap_or(false) {
+                        mismatches.push(SchemaMismatch::InputRequiredChanged(
+                            name.clone(),
+                            input.required,
+                            other.required,
+                        ));
+                    }
+                    if input.default != other.default {
+                        mismatches.push(SchemaMismatch::InputDefaultChanged(
+                            name.clone(),
+                            input.default.clone(),
+                            other.default.clone(),
+                        ));
+                    }
+                    if full && input.description != other.description {
+                        mismatches.push(SchemaMismatch::InputDescriptionChanged(
+                            name.clone(),
+                            input.description.clone(),
+                            other.description.clone(),
+                        ));
+                    }
+                }
+                None => mismatches.push(SchemaMismatch::InputRemoved(name.clone())),
+            }
+        }
+        for name in actual.inputs.keys() {
+            if !self.inputs.contains_key(name) {
+                mismatches.push(SchemaMismatch::InputAdded(name.clone()));
+            }
+        }
+
+        for (name, output) in self.outputs.iter() {
+            match actual.outputs.get(name) {
+                Some(other) if full => {
+                    if output.description != other.description {
+                        mismatches.push(SchemaMismatch::OutputDescriptionChanged(
+                            name.clone(),
+                            output.description.clone(),
+                            other.description.clone(),
+                        ));
+                    }
+                    if output.value != other.value {
+                        mismatches.push(SchemaMismatch::OutputValueChanged(
+                            name.clone(),
+                            output.value.clone(),
+                            other.value.clone(),
+                        ));
+                    }
+                }
+                Some(_) => {}
+                None => mismatches.push(SchemaMismatch::OutputRemoved(name.clone())),
+            }
+        }
+        for name in actual.outputs.keys() {
+            if !self.outputs.contains_key(name) {
+                mismatches.push(SchemaMismatch::OutputAdded(name.clone()));
+            }
+        }
+
+        if full && !self.composite {
+            self.compare_runs(actual, &mut mismatches);
+        }
+
+        mismatches
+    }
+
+    fn compare_runs(&self, actual: &ActionYML, mismatches: &mut Vec<SchemaMismatch>) {
+        if self.runs.using != actual.runs.using {
+            mismatches.push(SchemaMismatch::RunsChanged(
+                "using".to_string(),
+                Some(compact(&self.runs.using).trim_matches('"').to_string()),
+                Some(compact(&actual.runs.using).trim_matches('"').to_string()),
+            ));
+        }
+        if self.runs.image != actual.runs.image {
+            mismatches.push(SchemaMismatch::RunsChanged(
+                "image".to_string(),
+                self.runs
+                    .image
+                    .as_ref()
+                    .map(|image| image.display().to_string()),
+                actual
+                    .runs
+                    .image
+                    .as_ref()
+                    .map(|image| image.display().to_string()),
+            ));
+        }
+        if self.runs.main != actual.runs.main {
+            mismatches.push(SchemaMismatch::RunsChanged(
+                "main".to_string(),
+                self.runs.main.clone(),
+                actual.runs.main.clone(),
+            ));
+        }
+        if self.runs.args != actual.runs.args {
+            mismatches.push(SchemaMismatch::RunsChanged(
+                "args".to_string(),
+                self.runs.args.as_ref().map(|args| args.join(" ")),
+                actual.runs.args.as_ref().map(|args| args.join(" ")),
+            ));
+        }
+
+        if self.runs.env != actual.runs.env {
+            mismatches.push(SchemaMismatch::RunsChanged(
+                "env".to_string(),
+                self.runs.env.as_ref().map(compact),
+                actual.runs.env.as_ref().map(compact),
+            ));
+        }
+
+        let expected = self.runs.steps.as_deref().unwrap_or_default();
+        let found = actual.runs.steps.as_deref().unwrap_or_default();
+        for index in 0..expected.len().max(found.len()) {
+            let (expected, found) = (expected.get(index), found.get(index));
+            if expected != found {
+                mismatches.push(SchemaMismatch::RunsChanged(
+                    step_label(index, expected.or(found)),
+                    expected.map(compact),
+                    found.map(compact),
+                ));
+            }
+        }
+    }
+
+    /// Verify the Action file at `path` against the expected Action YAML content
+    pub fn verify_file(
+        expected: &str,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), Vec<SchemaMismatch>> {
+        let expected = ActionYML::from_yaml(expected)
+            .map_err(|err| vec![SchemaMismatch::LoadError(err.to_string())])?;
+        let actual = ActionYML::load_action(path.as_ref().display().to_string())
+            .map_err(|err| vec![SchemaMismatch::LoadError(err.to_string())])?;
+
+        let mismatches = expected.schema_diff(&actual);
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatches)
+        }
+    }
+
+    /// Check the Action file on disk is up to date with this (expected) Action
+    ///
+    /// When running in GitHub Actions the differences are also added to the job summary.
+    pub fn check(&self) -> Result<(), ActionsError> {
+        let path = match self.path {
+            Some(ref path) => path,
+            None => return Err(ActionsError::NotImplemented),
+        };
+        let mut mismatches = match ActionYML::load_action(path.display().to_string()) {
+            Ok(actual) => self.diff(&actual),
+            Err(err) => vec![SchemaMismatch::LoadError(err.to_string())],
+        };
+        // Writing would still change the file (e.g. formatting or comments)
+        if mismatches.is_empty() {
+            let expected = self.render()?;
+            let found = std::fs::read_to_string(path).unwrap_or_default();
+            if let Some(line) = first_changed_line(&expected, &found) {
+                mismatches.push(SchemaMismatch::ContentChanged(line));
+            }
+        }
+        if mismatches.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(summary) = crate::env_files::get_step_summary_path() {
+            let markdown = render_markdown(&mismatches);
+            // The summary is best effort, the mismatches are still returned
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(summary)
+                .and_then(|mut file| std::io::Write::write_all(&mut file, markdown.as_bytes()))
+                .ok();
+        }
+        Err(ActionsError::ActionFileMismatch(mismatches))
+    }
+}
+
+/// Get the first (1-based) line which differs between two files
+fn first_changed_line(expected: &str, found: &str) -> Option<usize> {
+    if expected == found {
+        return None;
+    }
+    let mut expected_lines = expected.lines();
+    let mut found_lines = found.lines();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), found_lines.next()) {
+            // Only the line endings differ
+            (None, None) => return Some(line),
+            (expected, found) if expected == found => line += 1,
+            _ => return Some(line),
+        }
+    }
+}
+
+/// Label a step using its ID or name (e.g. ``steps[1] (`cargo-run`)``)
+fn step_label(index: usize, step: Option<&ActionRunStep>) -> String {
+    match step.and_then(|step| step.id.as_ref().or(step.name.as_ref())) {
+        Some(label) => format!("steps[{}] ({})", index, label),
+        None => format!("steps[{}]", index),
+    }
+}
+
+/// Serialize a value on a single line so it fits into error messages and tables
+fn compact<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::models::ActionOutput;
+    use crate::ActionInput;
+    use std::path::PathBuf;
+
+    fn action() -> ActionYML {
+        let mut action = ActionYML::default();
+        action.inputs.insert(
+            "token".to_string(),
+            ActionInput {
+                required: Some(true),
+                ..Default::default()
+            },
+        );
+        action.inputs.insert(
+            "mode".to_string(),
+            ActionInput {
+                default: Some("fast".to_string()),
+                ..Default::default()
+            },
+        );
+        action
+    }
+
+    #[test]
+    fn test_schema_diff_matching() {
+        assert!(action().schema_diff(&action()).is_empty());
+    }
+
+    #[test]
+    fn test_schema_diff_changes() {
+        let expected = action();
+        let mut actual = action();
+        actual.inputs.shift_remove("token");
+        actual.inputs.get_mut("mode").unwrap().default = Some("slow".to_string());
+        actual
+            .inputs
+            .insert("extra".to_string(), ActionInput::default());
+
+        let diff = expected.schema_diff(&actual);
+        assert_eq!(
+            diff,
+            vec![
+                SchemaMismatch::InputRemoved("token".to_string()),
+                SchemaMismatch::InputDefaultChanged(
+                    "mode".to_string(),
+                    Some("fast".to_string()),
+                    Some("slow".to_string())
+                ),
+                SchemaMismatch::InputAdded("extra".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_descriptions_and_outputs() {
+        let mut expected = action();
+        expected.outputs.insert(
+            "version".to_string(),
+            ActionOutput {
+                description: Some("Version".to_string()),
+                value: Some("${{ steps.cargo-run.outputs.version }}".to_string()),
+                ..Default::default()
+            },
+        );
+        expected
+            .outputs
+            .insert("removed".to_string(), ActionOutput::default());
+
+        let mut actual = action();
+        actual.inputs.get_mut("token").unwrap().description = Some("Token".to_string());
+        actual.outputs.insert(
+            "version".to_string(),
+            ActionOutput {
+                description: Some("Old Version".to_string()),
+                value: Some("${{ steps.tool.outputs.version }}".to_string()),
+                ..Default::default()
+            },
+        );
+        actual
+            .outputs
+            .insert("extra".to_string(), ActionOutput::default());
+
+        // Only breaking changes are part of the schema diff
+        assert_eq!(
+            expected.schema_diff(&actual),
+            vec![
+                SchemaMismatch::OutputRemoved("removed".to_string()),
+                SchemaMismatch::OutputAdded("extra".to_string()),
+            ]
+        );
+        assert_eq!(
+            expected.diff(&actual),
+            vec![
+                SchemaMismatch::InputDescriptionChanged(
+                    "token".to_string(),
+                    None,
+                    Some("Token".to_string())
+                ),
+                SchemaMismatch::OutputDescriptionChanged(
+                    "version".to_string(),
+                    Some("Version".to_string()),
+                    Some("Old Version".to_string())
+                ),
+                SchemaMismatch::OutputValueChanged(
+                    "version".to_string(),
+                    Some("${{ steps.cargo-run.outputs.version }}".to_string()),
+                    Some("${{ steps.tool.outputs.version }}".to_string())
+                ),
+                SchemaMismatch::OutputRemoved("removed".to_string()),
+                SchemaMismatch::OutputAdded("extra".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_runs() {
+        let expected = action();
+        let mut actual = action();
+        let steps = actual.runs.steps.as_mut().unwrap();
+        steps[1].run = Some("set -e\nother".to_string());
+        steps.push(ActionRunStep {
+            name: Some("Extra".to_string()),
+            ..Default::default()
+        });
+
+        let diff = expected.diff(&actual);
+        assert_eq!(diff.len(), 2);
+        assert!(matches!(
+            &diff[0],
+            SchemaMismatch::RunsChanged(field, Some(_), Some(found))
+                if field == "steps[1] (cargo-run)" && found.contains("other")
+        ));
+        assert_eq!(
+            diff[1],
+            SchemaMismatch::RunsChanged(
+                "steps[2] (Extra)".to_string(),
+                None,
+                Some(r#"{"name":"Extra"}"#.to_string())
+            )
+        );
+
+        let mut container = action();
+        container.set_container_image(PathBuf::from("./Dockerfile"));
+        assert_eq!(
+            expected.diff(&container)[0],
+            SchemaMismatch::RunsChanged(
+                "using".to_string(),
+                Some("composite".to_string()),
+                Some("docker".to_string())
+            )
+        );
+
+        // User authored composite steps are not compared
+        let mut composite = action();
+        composite.set_composite();
+        assert!(composite.diff(&actual).is_empty());
+    }
+
+    #[test]
+    fn test_render_diff() {
+        let mismatches = vec![
+            SchemaMismatch::InputRemoved("token".to_string()),
+            SchemaMismatch::InputRequiredChanged("mode".to_string(), Some(true), None),
+            SchemaMismatch::InputDefaultChanged(
+                "mode".to_string(),
+                Some("a|b".to_string()),
+                Some("c".to_string()),
+            ),
+            SchemaMismatch::RunsChanged(
+                "steps[0]".to_string(),
+                None,
+                Some("line\nbreak".to_string()),
+            ),
+        ];
+
+        assert_eq!(
+            render_text(&mismatches),
+            r#" - input `token` is missing
+ - input `mode` required changed (expected: Some(true), found: None)
+ - input `mode` default changed (expected: Some("a|b"), found: Some("c"))
+ - runs `steps[0]` changed (expected: None, found: Some("line\nbreak"))"#
+        );
+        assert_eq!(
+            render_markdown(&mismatches),
+            r#"### Action file mismatch
+
+| Section | Name | Change | Expected | Found |
+| --- | --- | --- | --- | --- |
+| input | `token` | missing | - | - |
+| input | `mode` | required | `true` | - |
+| input | `mode` | default | `a\|b` | `c` |
+| runs | `steps[0]` | changed | - | `line\nbreak` |
+"#
+        );
+    }
+
+    #[test]
+    fn test_first_changed_line() {
+        assert_eq!(first_changed_line("a\nb\n", "a\nb\n"), None);
+        assert_eq!(first_changed_line("a\nb\n", "a\nc\n"), Some(2));
+        assert_eq!(first_changed_line("a\nb\n", "a\n"), Some(2));
+        assert_eq!(first_changed_line("a\nb\n", "a\nb"), Some(3));
+    }
+
+    #[test]
+    fn test_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("action.yml");
+        let mut expected = action();
+        expected.path = Some(path.clone());
+
+        assert!(matches!(
+            expected.check(),
+            Err(ActionsError::ActionFileMismatch(mismatches))
+                if matches!(mismatches.as_slice(), [SchemaMismatch::LoadError(_)])
+        ));
+
+        // Same schema but the file would still be rewritten (missing header)
+        std::fs::write(&path, expected.to_yaml().unwrap()).unwrap();
+        assert_eq!(
+            expected.check(),
+            Err(ActionsError::ActionFileMismatch(vec![
+                SchemaMismatch::ContentChanged(1)
+            ]))
+        );
+
+        std::fs::write(&path, expected.render().unwrap()).unwrap();
+        assert_eq!(expected.check(), Ok(()));
+
+        expected.inputs.shift_remove("mode");
+        assert_eq!(
+            expected.check(),
+            Err(ActionsError::ActionFileMismatch(vec![
+                SchemaMismatch::InputAdded("mode".to_string())
+            ]))
+        );
+    }
+}
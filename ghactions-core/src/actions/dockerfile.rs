@@ -0,0 +1,110 @@
+//! # Dockerfile
+//!
+//! Generate a multi-stage Dockerfile for container Actions.
+use std::path::Path;
+
+use crate::ActionsError;
+
+/// Dockerfile building the Action binary in a `rust` image and running it in a slim image
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dockerfile {
+    /// Rust version of the builder image (`rust:<version>`)
+    pub rust_version: String,
+    /// Base image of the runtime stage
+    pub base_image: String,
+    /// Name of the Action binary
+    pub binary: String,
+}
+
+impl Default for Dockerfile {
+    fn default() -> Self {
+        Self {
+            rust_version: "1".to_string(),
+            base_image: "debian:bookworm-slim".to_string(),
+            binary: super::models::binary_name(),
+        }
+    }
+}
+
+impl Dockerfile {
+    /// Render the Dockerfile
+    ///
+    /// ```
+    /// use ghactions_core::actions::dockerfile::Dockerfile;
+    ///
+    /// let dockerfile = Dockerfile {
+    ///     binary: "my-action".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// assert!(dockerfile.render().contains("FROM rust:1 AS builder"));
+    /// ```
+    pub fn render(&self) -> String {
+        format!(
+            r#"# This file is generated by ghactions
+# Do not edit this file manually unless you disable the `generate` feature.
+
+FROM rust:{rust_version} AS builder
+WORKDIR /app
+COPY . .
+RUN cargo build --release --bin {binary}
+
+FROM {base_image}
+COPY --from=builder /app/target/release/{binary} /usr/local/bin/{binary}
+ENTRYPOINT ["/usr/local/bin/{binary}"]
+"#,
+            rust_version = self.rust_version,
+            base_image = self.base_image,
+            binary = self.binary,
+        )
+    }
+
+    /// Write the Dockerfile, returns `false` if the file is already up to date
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<bool, ActionsError> {
+        let path = path.as_ref();
+        let content = self.render();
+        if std::fs::read_to_string(path).is_ok_and(|existing| existing == content) {
+            return Ok(false);
+        }
+        std::fs::write(path, content).map_err(|err| ActionsError::FileError {
+            path: path.to_path_buf(),
+            kind: err.kind(),
+            message: err.to_string(),
+        })?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dockerfile() {
+        let dockerfile = Dockerfile {
+            rust_version: "1.78".to_string(),
+            base_image: "gcr.io/distroless/cc".to_string(),
+            binary: "my-action".to_string(),
+        };
+        assert_eq!(
+            dockerfile.render(),
+            r#"# This file is generated by ghactions
+# Do not edit this file manually unless you disable the `generate` feature.
+
+FROM rust:1.78 AS builder
+WORKDIR /app
+COPY . .
+RUN cargo build --release --bin my-action
+
+FROM gcr.io/distroless/cc
+COPY --from=builder /app/target/release/my-action /usr/local/bin/my-action
+ENTRYPOINT ["/usr/local/bin/my-action"]
+"#
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Dockerfile");
+        assert_eq!(dockerfile.write(&path), Ok(true));
+        // Unchanged files are not rewritten
+        assert_eq!(dockerfile.write(&path), Ok(false));
+    }
+}
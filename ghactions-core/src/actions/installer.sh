@@ -0,0 +1,32 @@
+set -e
+# Download the prebuilt Action binary from the Action repository releases.
+# Release assets are named `<binary>-<target>` (with `.exe` on Windows).
+case "${RUNNER_OS}-${RUNNER_ARCH}" in
+  Linux-X64) TARGET="x86_64-unknown-linux-gnu" ;;
+  Linux-ARM64) TARGET="aarch64-unknown-linux-gnu" ;;
+  macOS-X64) TARGET="x86_64-apple-darwin" ;;
+  macOS-ARM64) TARGET="aarch64-apple-darwin" ;;
+  Windows-X64) TARGET="x86_64-pc-windows-msvc" ;;
+  *)
+    echo "::error::Unsupported runner ${RUNNER_OS}-${RUNNER_ARCH}"
+    exit 1
+    ;;
+esac
+
+INSTALL_DIR="${RUNNER_TEMP}/${ACTION_BINARY}-bin"
+mkdir -p "${INSTALL_DIR}"
+
+gh release download "${ACTION_REF}" \
+  --repo "${ACTION_REPOSITORY}" \
+  --pattern "${ACTION_BINARY}-${TARGET}*" \
+  --dir "${INSTALL_DIR}" \
+  --clobber
+
+for asset in "${INSTALL_DIR}/${ACTION_BINARY}-${TARGET}"*; do
+  case "${asset}" in
+    *.exe) mv "${asset}" "${INSTALL_DIR}/${ACTION_BINARY}.exe" ;;
+    *) mv "${asset}" "${INSTALL_DIR}/${ACTION_BINARY}" && chmod +x "${INSTALL_DIR}/${ACTION_BINARY}" ;;
+  esac
+done
+
+echo "${INSTALL_DIR}" >> "${GITHUB_PATH}"
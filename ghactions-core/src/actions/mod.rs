@@ -1,4 +1,10 @@
 //! This module contains the actions related code.
+pub mod branding;
+pub mod diff;
+pub mod dockerfile;
 pub mod models;
+pub mod names;
+pub mod validate;
 
+pub use diff::SchemaMismatch;
 pub use models::{ActionRuns, ActionYML};
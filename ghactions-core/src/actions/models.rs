@@ -1,23 +1,33 @@
 //! # Models
 
 use indexmap::IndexMap;
-use serde::{Deserialize, Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{
-    collections::HashMap,
     fmt::{Display, Formatter},
     io::Write,
     os::unix::fs::FileExt,
-    path::PathBuf,
+    path::{Component, Path, PathBuf},
 };
 
+use crate::actions::validate::Severity;
 use crate::ActionsError;
 
 const GHACTIONS_ROOT: &str = env!("CARGO_MANIFEST_DIR");
 
+/// Script downloading the prebuilt Action binary from the repository releases
+pub const INSTALLER_SCRIPT: &str = include_str!("installer.sh");
+/// Step ID of the installer step
+const INSTALLER_STEP_ID: &str = "installer";
+
+/// Sections of a user authored composite Action which are generated
+const GENERATED_SECTIONS: [&str; 4] = ["name", "description", "inputs", "outputs"];
+/// Sections of a user authored composite Action which are only generated when set
+const OPTIONAL_SECTIONS: [&str; 1] = ["branding"];
+
 /// Action YAML file structure
 ///
 /// https://docs.github.com/en/actions/creating-actions/metadata-syntax-for-github-actions
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ActionYML {
     /// Action Path
     #[serde(skip)]
@@ -38,12 +48,23 @@ pub struct ActionYML {
     pub branding: Option<ActionBranding>,
 
     /// Action Inputs
+    #[serde(default)]
     pub inputs: IndexMap<String, ActionInput>,
     /// Action Outputs
+    #[serde(default)]
     pub outputs: IndexMap<String, ActionOutput>,
     /// Output Value Step ID
     #[serde(skip)]
     pub output_value_step_id: Option<String>,
+    /// Prefix of the output names written by the Action (see `GHACTIONS_OUTPUT_PREFIX`)
+    #[serde(skip)]
+    pub output_prefix: Option<String>,
+    /// Allow writing the Action file through a symlink
+    #[serde(skip)]
+    pub follow_symlinks: bool,
+    /// User authored composite Action (only the metadata sections are generated)
+    #[serde(skip)]
+    pub composite: bool,
 
     /// Action Runs
     pub runs: ActionRuns,
@@ -60,6 +81,9 @@ impl Default for ActionYML {
             inputs: IndexMap::new(),
             outputs: IndexMap::new(),
             output_value_step_id: Some("cargo-run".to_string()),
+            output_prefix: None,
+            follow_symlinks: false,
+            composite: false,
             runs: ActionRuns::default(),
         }
     }
@@ -75,54 +99,590 @@ impl ActionYML {
         self.output_value_step_id = None;
     }
 
+    /// Set the Action to a JavaScript Action (e.g. a shim downloading the Action binary)
+    ///
+    /// The `version` is the Node.js major version (e.g. `20` for `node20`) and `main` is
+    /// the script run by the runner (relative to the Action repository).
+    pub fn set_node(&mut self, version: impl Into<String>, main: impl Into<String>) {
+        self.runs.using = ActionRunUsing::Node(version.into());
+        self.runs.main = Some(main.into());
+        self.runs.image = None;
+        self.runs.args = None;
+        self.runs.env = None;
+        self.runs.steps = None;
+        // JavaScript actions set the outputs directly
+        self.output_value_step_id = None;
+    }
+
+    /// Install the prebuilt Action binary from the repository releases instead of
+    /// compiling it (replaces the compile / install step)
+    ///
+    /// The default installer ([INSTALLER_SCRIPT]) uses the GitHub CLI (`gh`) to download
+    /// the `<binary>-<target>` release asset for the Action ref. A custom `script`
+    /// (relative to the Action repository) can be used instead.
+    pub fn add_installer_step(&mut self, script: Option<PathBuf>) {
+        let run = match script {
+            Some(script) => format!(
+                "${{{{ github.action_path }}}}/{}",
+                script.display().to_string().trim_start_matches("./")
+            ),
+            None => INSTALLER_SCRIPT.to_string(),
+        };
+        let mut env = IndexMap::new();
+        env.insert("ACTION_BINARY".to_string(), binary_name());
+        env.insert(
+            "ACTION_REPOSITORY".to_string(),
+            "${{ github.action_repository }}".to_string(),
+        );
+        env.insert(
+            "ACTION_REF".to_string(),
+            "${{ github.action_ref }}".to_string(),
+        );
+        env.insert("GH_TOKEN".to_string(), "${{ github.token }}".to_string());
+
+        let installer = ActionRunStep {
+            id: Some(INSTALLER_STEP_ID.to_string()),
+            name: Some("Install the Action binary".to_string()),
+            shell: Some("bash".to_string()),
+            run: Some(run),
+            env: Some(env),
+            ..Default::default()
+        };
+
+        self.runs.using = ActionRunUsing::Composite;
+        let steps = self.runs.steps.get_or_insert_with(default_composite_steps);
+        steps.retain(|step| {
+            !step
+                .run
+                .as_ref()
+                .is_some_and(|run| run.contains("cargo install"))
+        });
+        steps.insert(0, installer);
+        self.output_value_step_id = Some("cargo-run".to_string());
+    }
+
+    /// Set the Action to a user authored composite Action
+    ///
+    /// Only the `name`, `description`, `inputs` and `outputs` sections are generated, the
+    /// `runs` section of an existing Action file is kept (see [ActionYML::merge_yaml]).
+    pub fn set_composite(&mut self) {
+        self.composite = true;
+        self.runs.using = ActionRunUsing::Composite;
+        self.runs.image = None;
+        self.runs.args = None;
+        self.runs.env = None;
+        self.runs.steps = Some(Vec::new());
+        // Outputs are read from the steps set with the `output_step` attribute
+        self.output_value_step_id = None;
+    }
+
+    /// Run a script before the Action (e.g. to prepare files)
+    ///
+    /// Container Actions use `pre-entrypoint` (the script is a path in the container),
+    /// JavaScript Actions use `pre` and composite Actions run the script (relative to the
+    /// Action repository) as the first step. The script only runs if the `condition`
+    /// (e.g. `runner.os == 'Linux'`) is true.
+    pub fn set_pre_entrypoint(&mut self, script: impl Into<String>, condition: Option<String>) {
+        let script = script.into();
+        match self.runs.using {
+            ActionRunUsing::Docker => {
+                self.runs.pre_entrypoint = Some(script);
+                self.runs.pre_if = condition;
+            }
+            ActionRunUsing::Node(_) => {
+                self.runs.pre = Some(script);
+                self.runs.pre_if = condition;
+            }
+            ActionRunUsing::Composite => {
+                let step = script_step("pre-entrypoint", "Pre entrypoint", &script, condition);
+                self.runs.steps.get_or_insert_with(Vec::new).insert(0, step);
+            }
+        }
+    }
+
+    /// Run a script when the Action finishes (e.g. to restore files or report telemetry)
+    ///
+    /// Container Actions use `post-entrypoint` (run when the job finishes), JavaScript
+    /// Actions use `post` and composite Actions run the script as the last step. Use
+    /// `always()` as the `condition` to run the script even if the Action failed.
+    pub fn set_post_entrypoint(&mut self, script: impl Into<String>, condition: Option<String>) {
+        let script = script.into();
+        match self.runs.using {
+            ActionRunUsing::Docker => {
+                self.runs.post_entrypoint = Some(script);
+                self.runs.post_if = condition;
+            }
+            ActionRunUsing::Node(_) => {
+                self.runs.post = Some(script);
+                self.runs.post_if = condition;
+            }
+            ActionRunUsing::Composite => {
+                let step = script_step("post-entrypoint", "Post entrypoint", &script, condition);
+                self.runs.steps.get_or_insert_with(Vec::new).push(step);
+            }
+        }
+    }
+
+    /// Create a builder for an Action file (e.g. to generate it from a build script)
+    ///
+    /// The builder starts with the generated composite steps (compile and run the Action
+    /// binary) like `#[derive(Actions)]`.
+    ///
+    /// ```
+    /// use ghactions_core::ActionYML;
+    ///
+    /// let action = ActionYML::builder()
+    ///     .name("My Action")
+    ///     .description("My Action Description")
+    ///     .input("mode", |input| input.description("Mode").default("fast"))
+    ///     .output("version", |output| output.description("Version"))
+    ///     .map_steps(|steps| {
+    ///         for step in steps.iter_mut() {
+    ///             step.name = step.name.as_ref().map(|name| format!("[my-action] {}", name));
+    ///         }
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(action.run_step().unwrap().name.as_deref(), Some("[my-action] Run the Action"));
+    /// assert_eq!(
+    ///     action.outputs["version"].value.as_deref(),
+    ///     Some("${{ steps.cargo-run.outputs.version }}")
+    /// );
+    /// ```
+    pub fn builder() -> ActionYMLBuilder {
+        ActionYMLBuilder::default()
+    }
+
+    /// Get the composite step that runs the Action (the step outputs are read from)
+    pub fn run_step(&self) -> Option<&ActionRunStep> {
+        let id = self.output_value_step_id.as_ref()?;
+        self.runs
+            .steps
+            .as_ref()?
+            .iter()
+            .find(|step| step.id.as_ref() == Some(id))
+    }
+
+    /// Get the mutable composite step that runs the Action
+    pub fn run_step_mut(&mut self) -> Option<&mut ActionRunStep> {
+        let id = self.output_value_step_id.clone()?;
+        self.runs
+            .steps
+            .as_mut()?
+            .iter_mut()
+            .find(|step| step.id.as_ref() == Some(&id))
+    }
+
+    /// Get the mutable composite step that compiles / installs the Action binary
+    pub fn compile_step_mut(&mut self) -> Option<&mut ActionRunStep> {
+        self.runs.steps.as_mut()?.iter_mut().find(|step| {
+            step.id.as_deref() == Some(INSTALLER_STEP_ID)
+                || step
+                    .run
+                    .as_ref()
+                    .is_some_and(|run| run.contains("cargo install"))
+        })
+    }
+
     /// Load the Action YAML file
     pub fn load_action(path: String) -> Result<ActionYML, Box<dyn std::error::Error>> {
-        let fhandle = std::fs::File::open(&path)?;
-        let mut action_yml: ActionYML = serde_yaml::from_reader(fhandle)?;
+        let content = std::fs::read_to_string(&path)?;
+        let mut action_yml = ActionYML::from_yaml(&content)?;
         action_yml.path = Some(PathBuf::from(path.clone()));
         Ok(action_yml)
     }
 
+    /// Parse an Action from a YAML string
+    ///
+    /// Anchors, aliases and merge keys (`<<: *anchor`) are resolved so hand-written
+    /// files compare equal to their expanded form.
+    pub fn from_yaml(content: &str) -> Result<ActionYML, serde_yaml::Error> {
+        let mut value: serde_yaml::Value = serde_yaml::from_str(content)?;
+        value.apply_merge()?;
+        serde_yaml::from_value(value)
+    }
+
+    /// Serialize the Action to a YAML string
+    pub fn to_yaml(&self) -> Result<String, ActionsError> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+
+    /// Merge the generated sections (`name`, `description`, `inputs` and `outputs`) into
+    /// an existing Action YAML string, all other sections are kept
+    ///
+    /// Comments are not preserved and anchors / aliases are expanded.
+    pub fn merge_yaml(&self, existing: &str) -> Result<String, ActionsError> {
+        let mut existing: serde_yaml::Value = serde_yaml::from_str(existing)?;
+        existing.apply_merge()?;
+        let existing = match existing {
+            serde_yaml::Value::Mapping(mapping) => mapping,
+            // Empty file
+            serde_yaml::Value::Null => serde_yaml::Mapping::new(),
+            _ => {
+                return Err(ActionsError::YamlError(serde::de::Error::custom(
+                    "Action file must be a YAML mapping",
+                )))
+            }
+        };
+        let generated = match serde_yaml::to_value(self)? {
+            serde_yaml::Value::Mapping(mapping) => mapping,
+            _ => serde_yaml::Mapping::new(),
+        };
+
+        // Keep the order of the existing file, missing sections are appended
+        let mut merged = serde_yaml::Mapping::new();
+        for (key, value) in existing.iter() {
+            let generated_key = key.as_str().is_some_and(|key| {
+                GENERATED_SECTIONS.contains(&key)
+                    || (OPTIONAL_SECTIONS.contains(&key) && generated.contains_key(key))
+            });
+            if !generated_key {
+                merged.insert(key.clone(), value.clone());
+            } else if let Some(value) = generated.get(key) {
+                merged.insert(key.clone(), value.clone());
+            }
+        }
+        for section in GENERATED_SECTIONS.iter().chain(OPTIONAL_SECTIONS.iter()) {
+            if let Some(value) = generated.get(section) {
+                if !merged.contains_key(section) {
+                    merged.insert((*section).into(), value.clone());
+                }
+            }
+        }
+        if !merged.contains_key("runs") {
+            if let Some(runs) = generated.get("runs") {
+                merged.insert("runs".into(), runs.clone());
+            }
+        }
+        Ok(serde_yaml::to_string(&merged)?)
+    }
+
     /// Write the Action YAML file
+    ///
+    /// Symlinked files are not followed unless `follow_symlinks` is set and
+    /// missing parent directories are only created inside of the crate / workspace root.
     pub fn write(&self) -> Result<PathBuf, ActionsError> {
-        if let Some(ref path) = self.path {
-            if !path.exists() {
-                let parent = path.parent().unwrap();
-                std::fs::create_dir_all(parent)
-                    .map_err(|err| ActionsError::IOError(err.to_string()))?;
-            }
+        let root = match std::env::var("CARGO_MANIFEST_DIR") {
+            Ok(manifest) => workspace_root(Path::new(&manifest)),
+            Err(_) => std::env::current_dir().map_err(|err| ActionsError::FileError {
+                path: PathBuf::from("."),
+                kind: err.kind(),
+                message: err.to_string(),
+            })?,
+        };
+        self.write_in_root(&root)
+    }
 
-            let mut content = String::new();
-            content.push_str("# This file is generated by ghactions\n");
+    /// Render the content of the Action file (as written by [ActionYML::write])
+    ///
+    /// For user authored composite Actions the existing file is read and the generated
+    /// sections are merged into it.
+    pub fn render(&self) -> Result<String, ActionsError> {
+        let mut content = String::new();
+        if self.composite {
+            // Keep the user authored sections of the existing file
+            let existing = match self.path.as_ref().map(std::fs::read_to_string) {
+                Some(Ok(existing)) => existing,
+                Some(Err(err)) if err.kind() != std::io::ErrorKind::NotFound => {
+                    return Err(ActionsError::FileError {
+                        path: self.path.clone().unwrap_or_default(),
+                        kind: err.kind(),
+                        message: err.to_string(),
+                    })
+                }
+                _ => String::new(),
+            };
             content.push_str(
-                "# Do not edit this file manually unless you disable the `generate` feature.\n\n",
+                "# The `name`, `description`, `inputs` and `outputs` are generated by ghactions\n",
             );
+            content.push_str("# Comments in this file are not preserved.\n\n");
+            content.push_str(self.merge_yaml(&existing)?.as_str());
+        } else {
+            content.push_str("# This file is generated by ghactions\n");
             content.push_str(
-                serde_yaml::to_string(self)
-                    .map_err(|err| ActionsError::IOError(err.to_string()))?
-                    .as_str(),
+                "# Do not edit this file manually unless you disable the `generate` feature.\n\n",
             );
+            content.push_str(self.to_yaml()?.as_str());
+        }
+        Ok(content)
+    }
 
-            // Create or Open the file
-            let mut fhandle = std::fs::OpenOptions::new()
-                .write(true)
-                .create(true)
-                .truncate(true)
-                .open(path)
-                .map_err(|err| ActionsError::IOError(err.to_string()))?;
-            fhandle
-                .write_all(content.as_bytes())
-                .map_err(|err| ActionsError::IOError(err.to_string()))?;
+    fn write_in_root(&self, root: &Path) -> Result<PathBuf, ActionsError> {
+        let path = match self.path {
+            Some(ref path) => path,
+            None => return Err(ActionsError::NotImplemented),
+        };
+        let file_error = |path: &Path, err: std::io::Error| ActionsError::FileError {
+            path: path.to_path_buf(),
+            kind: err.kind(),
+            message: err.to_string(),
+        };
 
-            Ok(path.clone())
-        } else {
-            Err(ActionsError::NotImplemented)
+        // Refuse to write through symlinks (unless explicitly allowed)
+        if let Ok(metadata) = std::fs::symlink_metadata(path) {
+            if metadata.file_type().is_symlink() && !self.follow_symlinks {
+                let target = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+                return Err(ActionsError::SymlinkError {
+                    path: path.clone(),
+                    target,
+                });
+            }
+        }
+
+        // A bare file name (no parent) is written to the current working directory
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        if !parent.exists() {
+            let absolute = if parent.is_absolute() {
+                parent.clone()
+            } else {
+                std::env::current_dir()
+                    .map_err(|err| file_error(&parent, err))?
+                    .join(&parent)
+            };
+            if !normalize_path(&absolute).starts_with(normalize_path(root)) {
+                return Err(ActionsError::FileError {
+                    path: parent,
+                    kind: std::io::ErrorKind::PermissionDenied,
+                    message: format!(
+                        "refusing to create directories outside of `{}`",
+                        root.display()
+                    ),
+                });
+            }
+            std::fs::create_dir_all(&parent).map_err(|err| file_error(&parent, err))?;
+        }
+
+        let content = self.render()?;
+
+        // Create or Open the file
+        let mut fhandle = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .map_err(|err| file_error(path, err))?;
+        fhandle
+            .write_all(content.as_bytes())
+            .map_err(|err| file_error(path, err))?;
+
+        Ok(path.clone())
+    }
+}
+
+/// Action YAML builder
+#[derive(Debug, Default)]
+pub struct ActionYMLBuilder {
+    action: ActionYML,
+}
+
+impl ActionYMLBuilder {
+    /// Set the path the Action file is written to
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.action.path = Some(path.into());
+        self
+    }
+
+    /// Set the Action name
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.action.name = Some(name.into());
+        self
+    }
+
+    /// Set the Action description
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.action.description = Some(description.into());
+        self
+    }
+
+    /// Add an input (e.g. `.input("mode", |input| input.description("Mode").required(true))`)
+    pub fn input(
+        mut self,
+        name: impl Into<String>,
+        f: impl FnOnce(ActionInputBuilder) -> ActionInputBuilder,
+    ) -> Self {
+        let name = name.into();
+        let input = f(ActionInputBuilder {
+            input: ActionInput {
+                action_name: name.clone(),
+                field_name: name.clone(),
+                ..Default::default()
+            },
+        })
+        .input;
+        self.action.inputs.insert(name, input);
+        self
+    }
+
+    /// Add an output (e.g. `.output("version", |output| output.description("Version"))`)
+    ///
+    /// Outputs of composite Actions without a value are read from the run step.
+    pub fn output(
+        mut self,
+        name: impl Into<String>,
+        f: impl FnOnce(ActionOutputBuilder) -> ActionOutputBuilder,
+    ) -> Self {
+        let name = name.into();
+        let output = f(ActionOutputBuilder {
+            output: ActionOutput {
+                field_name: name.clone(),
+                ..Default::default()
+            },
+        })
+        .output;
+        self.action.outputs.insert(name, output);
+        self
+    }
+
+    /// Set the Action to a Container Image based Action
+    pub fn docker_image(mut self, image: impl Into<PathBuf>) -> Self {
+        self.action.set_container_image(image.into());
+        self
+    }
+
+    /// Set the Action to a JavaScript Action (see [ActionYML::set_node])
+    pub fn node(mut self, version: impl Into<String>, main: impl Into<String>) -> Self {
+        self.action.set_node(version, main);
+        self
+    }
+
+    /// Add a step to the composite Action (after the generated steps)
+    pub fn composite_step(mut self, step: ActionRunStep) -> Self {
+        self.action.runs.using = ActionRunUsing::Composite;
+        self.action
+            .runs
+            .steps
+            .get_or_insert_with(Vec::new)
+            .push(step);
+        self
+    }
+
+    /// Inspect and modify the composite steps before the Action file is written
+    ///
+    /// Does nothing for container Actions.
+    pub fn map_steps(mut self, f: impl FnOnce(&mut Vec<ActionRunStep>)) -> Self {
+        if let Some(ref mut steps) = self.action.runs.steps {
+            f(steps);
+        }
+        self
+    }
+
+    /// Build and validate the Action (see [ActionYML::validate])
+    ///
+    /// Only errors fail the build, warnings (e.g. inputs without a description) are
+    /// ignored.
+    pub fn build(mut self) -> Result<ActionYML, ActionsError> {
+        if let Some(ref step) = self.action.output_value_step_id {
+            for (name, output) in self.action.outputs.iter_mut() {
+                if output.value.is_none() {
+                    output.value = Some(format!(
+                        "${{{{ steps.{}.outputs.{}{} }}}}",
+                        step,
+                        self.action.output_prefix.as_deref().unwrap_or_default(),
+                        name
+                    ));
+                }
+            }
+        }
+
+        let errors: Vec<_> = self
+            .action
+            .validate()
+            .into_iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .collect();
+        match errors.is_empty() {
+            true => Ok(self.action),
+            false => Err(ActionsError::InvalidAction(errors)),
+        }
+    }
+}
+
+/// Action Input builder (see [ActionYMLBuilder::input])
+#[derive(Debug)]
+pub struct ActionInputBuilder {
+    input: ActionInput,
+}
+
+impl ActionInputBuilder {
+    /// Set the input description
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.input.description = Some(description.into());
+        self
+    }
+
+    /// Set if the input is required
+    pub fn required(mut self, required: bool) -> Self {
+        self.input.required = Some(required);
+        self
+    }
+
+    /// Set the default value of the input
+    pub fn default(mut self, default: impl Into<String>) -> Self {
+        self.input.default = Some(default.into());
+        self
+    }
+
+    /// Deprecate the input with a message
+    pub fn deprecated(mut self, message: impl Into<String>) -> Self {
+        self.input.deprecation_message = Some(message.into());
+        self
+    }
+}
+
+/// Action Output builder (see [ActionYMLBuilder::output])
+#[derive(Debug)]
+pub struct ActionOutputBuilder {
+    output: ActionOutput,
+}
+
+impl ActionOutputBuilder {
+    /// Set the output description
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.output.description = Some(description.into());
+        self
+    }
+
+    /// Set the output value (e.g. `${{ steps.tool.outputs.version }}`)
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.output.value = Some(value.into());
+        self
+    }
+}
+
+/// Find the workspace root for a crate (falls back to the crate root)
+fn workspace_root(manifest_dir: &Path) -> PathBuf {
+    manifest_dir
+        .ancestors()
+        .filter(|dir| {
+            std::fs::read_to_string(dir.join("Cargo.toml"))
+                .map(|content| content.contains("[workspace]"))
+                .unwrap_or(false)
+        })
+        .last()
+        .unwrap_or(manifest_dir)
+        .to_path_buf()
+}
+
+/// Normalize a path by resolving `.` and `..` components without touching the filesystem
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
         }
     }
+    normalized
 }
 
 /// Action Input structure
-#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct ActionInput {
     /// [internal] Action Field Name
     #[serde(skip)]
@@ -133,6 +693,9 @@ pub struct ActionInput {
     /// [internal] Input Type
     #[serde(skip)]
     pub r#type: String,
+    /// [internal] Struct Field of the flattened input group (if any)
+    #[serde(skip)]
+    pub group: Option<String>,
 
     /// Input Description
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -151,11 +714,55 @@ pub struct ActionInput {
     /// Separator
     #[serde(skip)]
     pub separator: Option<String>,
+    /// Regex pattern the value must match
+    #[serde(skip)]
+    pub pattern: Option<String>,
+    /// Allowed values
+    #[serde(skip)]
+    pub allowed_values: Option<Vec<String>>,
+    /// Compare allowed values case insensitively
+    #[serde(skip)]
+    pub ignore_case: Option<bool>,
+    /// Presence based boolean flag
+    #[serde(skip)]
+    pub flag: bool,
+    /// Also accept `1` / `0`, `yes` / `no` and `on` / `off` for boolean inputs
+    #[serde(skip)]
+    pub lenient: bool,
+    /// Trim surrounding whitespace from the value (enabled by the derive unless
+    /// `trim = false` is set)
+    #[serde(skip)]
+    pub trim: bool,
+    /// Split the value into lines
+    #[serde(skip)]
+    pub multiline: bool,
+    /// Allow the value to contain `${{ }}` expressions
+    #[serde(skip)]
+    pub allow_expressions: bool,
+    /// Mask the value in the workflow logs
+    #[serde(skip)]
+    pub secret: bool,
+    /// Other names the value is read from (e.g. the old name of a renamed input)
+    #[serde(skip)]
+    pub aliases: Vec<String>,
+    /// Add the aliases to the Action file as deprecated inputs
+    #[serde(skip)]
+    pub deprecate_aliases: bool,
 }
 
 /// Action Output structure
-#[derive(Debug, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct ActionOutput {
+    /// [internal] Struct Field Name
+    #[serde(skip)]
+    pub field_name: String,
+    /// [internal] Always write the output (even when empty)
+    #[serde(skip)]
+    pub always: bool,
+    /// [internal] Write the output value serialized as JSON
+    #[serde(skip)]
+    pub json: bool,
+
     /// Output Description
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -168,7 +775,7 @@ pub struct ActionOutput {
 /// Action Branding
 ///
 /// https://docs.github.com/en/actions/creating-actions/metadata-syntax-for-github-actions#branding
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ActionBranding {
     /// Action Color
     pub color: String,
@@ -177,7 +784,7 @@ pub struct ActionBranding {
 }
 
 /// Action Runs structure
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ActionRuns {
     /// Action Name
     pub using: ActionRunUsing,
@@ -188,6 +795,30 @@ pub struct ActionRuns {
     /// Arguments (container actions only)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub args: Option<Vec<String>>,
+    /// Environment Variables (container actions only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<IndexMap<String, String>>,
+    /// Main script (JavaScript actions only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub main: Option<String>,
+    /// Script run before `main` (JavaScript actions only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre: Option<String>,
+    /// Script run when the job finishes (JavaScript actions only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post: Option<String>,
+    /// Script run before the entrypoint (container actions only)
+    #[serde(rename = "pre-entrypoint", skip_serializing_if = "Option::is_none")]
+    pub pre_entrypoint: Option<String>,
+    /// Condition for running the pre-entrypoint / pre script
+    #[serde(rename = "pre-if", skip_serializing_if = "Option::is_none")]
+    pub pre_if: Option<String>,
+    /// Script run when the job finishes (container actions only)
+    #[serde(rename = "post-entrypoint", skip_serializing_if = "Option::is_none")]
+    pub post_entrypoint: Option<String>,
+    /// Condition for running the post-entrypoint / post script
+    #[serde(rename = "post-if", skip_serializing_if = "Option::is_none")]
+    pub post_if: Option<String>,
 
     /// Steps (composite actions only)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -200,14 +831,50 @@ impl Default for ActionRuns {
             using: ActionRunUsing::Composite,
             image: None,
             args: None,
+            env: None,
+            main: None,
+            pre: None,
+            post: None,
+            pre_entrypoint: None,
+            pre_if: None,
+            post_entrypoint: None,
+            post_if: None,
             steps: Some(default_composite_steps()),
         }
     }
 }
 
+/// Composite step running a script shipped with the Action
+fn script_step(id: &str, name: &str, script: &str, condition: Option<String>) -> ActionRunStep {
+    let shell = match script.ends_with(".ps1") {
+        true => "pwsh",
+        false => "bash",
+    };
+    let mut step = ActionRunStep {
+        id: Some(id.to_string()),
+        name: Some(name.to_string()),
+        shell: Some(shell.to_string()),
+        run: Some(format!(
+            "${{{{ github.action_path }}}}/{}",
+            script.trim_start_matches("./")
+        )),
+        ..Default::default()
+    };
+    if let Some(condition) = condition {
+        step.extra
+            .insert("if".to_string(), serde_yaml::Value::String(condition));
+    }
+    step
+}
+
+/// Name of the Action binary
+pub(crate) fn binary_name() -> String {
+    std::env::var("CARGO_BIN_NAME").unwrap_or_else(|_| "action".to_string())
+}
+
 fn default_composite_steps() -> Vec<ActionRunStep> {
     // Binary Name
-    let binary_name = std::env::var("CARGO_BIN_NAME").unwrap_or_else(|_| "action".to_string());
+    let binary_name = binary_name();
     vec![
         // Step 1 - Checking for Cargo/Rust (needs to be installed by the user)
         // ActionRunStep {
@@ -235,24 +902,40 @@ fn default_composite_steps() -> Vec<ActionRunStep> {
 }
 
 /// Action Run Using Enum
-#[derive(Debug, PartialEq, Deserialize)]
+///
+/// `Copy` is not implemented as JavaScript Actions include the Node.js version.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ActionRunUsing {
     /// Docker / Container Image
     Docker,
     /// Composite Action
     Composite,
+    /// JavaScript Action using the Node.js major version (e.g. `20` for `node20`)
+    Node(String),
 }
 
-impl From<&str> for ActionRunUsing {
-    fn from(value: &str) -> Self {
+impl ActionRunUsing {
+    /// Parse the `using` value (e.g. `docker`, `composite` or `node20`)
+    fn parse(value: &str) -> Option<Self> {
         match value {
-            "docker" => ActionRunUsing::Docker,
-            "composite" => ActionRunUsing::Composite,
-            _ => ActionRunUsing::Composite,
+            "docker" => Some(ActionRunUsing::Docker),
+            "composite" => Some(ActionRunUsing::Composite),
+            _ => value
+                .strip_prefix("node")
+                .filter(|version| {
+                    !version.is_empty() && version.chars().all(|c| c.is_ascii_digit())
+                })
+                .map(|version| ActionRunUsing::Node(version.to_string())),
         }
     }
 }
 
+impl From<&str> for ActionRunUsing {
+    fn from(value: &str) -> Self {
+        Self::parse(value).unwrap_or(ActionRunUsing::Composite)
+    }
+}
+
 impl From<String> for ActionRunUsing {
     fn from(value: String) -> Self {
         Self::from(value.as_str())
@@ -267,12 +950,30 @@ impl Serialize for ActionRunUsing {
         match self {
             ActionRunUsing::Docker => serializer.serialize_str("docker"),
             ActionRunUsing::Composite => serializer.serialize_str("composite"),
+            ActionRunUsing::Node(version) => serializer.serialize_str(&format!("node{}", version)),
         }
     }
 }
 
+impl<'de> Deserialize<'de> for ActionRunUsing {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Self::parse(&value).ok_or_else(|| {
+            serde::de::Error::unknown_variant(&value, &["docker", "composite", "node20"])
+        })
+    }
+}
+
 /// Action Run Step
-#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+///
+/// New step fields are likely to be added so the struct is `non_exhaustive`, use
+/// `ActionRunStep::default()` and set the fields. `Hash` is not implemented as the
+/// `env` and `extra` maps are ordered maps.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct ActionRunStep {
     /// Step ID
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -289,5 +990,308 @@ pub struct ActionRunStep {
 
     /// Environment Variables
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub env: Option<HashMap<String, String>>,
+    pub env: Option<IndexMap<String, String>>,
+
+    /// Other step fields which are preserved as-is (e.g. `uses`, `with` or `if`)
+    #[serde(flatten)]
+    pub extra: IndexMap<String, serde_yaml::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(path: PathBuf) -> ActionYML {
+        ActionYML {
+            path: Some(path),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_write_bare_filename() {
+        let root = tempfile::tempdir().unwrap();
+        let cwd = std::env::current_dir().unwrap();
+
+        std::env::set_current_dir(root.path()).unwrap();
+        let result = action(PathBuf::from("action.yml")).write_in_root(root.path());
+        std::env::set_current_dir(cwd).unwrap();
+
+        assert_eq!(result, Ok(PathBuf::from("action.yml")));
+        assert!(root.path().join("action.yml").exists());
+    }
+
+    #[test]
+    fn test_write_symlink() {
+        let root = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let target = outside.path().join("action.yml");
+        std::fs::write(&target, "original").unwrap();
+
+        let link = root.path().join("action.yml");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let result = action(link.clone()).write_in_root(root.path());
+        assert_eq!(
+            result,
+            Err(ActionsError::SymlinkError {
+                path: link.clone(),
+                target: std::fs::canonicalize(&target).unwrap(),
+            })
+        );
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "original");
+
+        // Explicitly allowed
+        let mut action = action(link);
+        action.follow_symlinks = true;
+        assert!(action.write_in_root(root.path()).is_ok());
+        assert_ne!(std::fs::read_to_string(&target).unwrap(), "original");
+    }
+
+    #[test]
+    fn test_run_step_serde() {
+        let mut step = ActionRunStep {
+            id: Some("test".to_string()),
+            shell: Some("bash".to_string()),
+            run: Some("echo test".to_string()),
+            ..Default::default()
+        };
+        step.extra.insert(
+            "if".to_string(),
+            serde_yaml::Value::String("always()".to_string()),
+        );
+
+        let yaml = serde_yaml::to_string(&step).unwrap();
+        assert_eq!(
+            yaml,
+            "id: test\nshell: bash\nrun: echo test\nif: always()\n"
+        );
+        assert_eq!(serde_yaml::from_str::<ActionRunStep>(&yaml).unwrap(), step);
+
+        // Cloned Actions are equal
+        let action = ActionYML::default();
+        assert_eq!(action.clone(), action);
+        assert_eq!(action.runs.using, ActionRunUsing::Composite);
+    }
+
+    #[test]
+    fn test_pre_post_entrypoint_docker() {
+        let mut action = ActionYML::default();
+        action.set_container_image(PathBuf::from("./Dockerfile"));
+        action.set_pre_entrypoint("/setup.sh", None);
+        action.set_post_entrypoint("/cleanup.sh", Some("always()".to_string()));
+
+        let yaml = serde_yaml::to_string(&action.runs).unwrap();
+        assert_eq!(
+            yaml,
+            "using: docker\nimage: ./Dockerfile\npre-entrypoint: /setup.sh\npost-entrypoint: /cleanup.sh\npost-if: always()\n"
+        );
+        assert_eq!(
+            serde_yaml::from_str::<ActionRuns>(&yaml).unwrap(),
+            action.runs
+        );
+    }
+
+    #[test]
+    fn test_pre_post_entrypoint_composite() {
+        let mut action = ActionYML::default();
+        action.set_pre_entrypoint("./setup.ps1", None);
+        action.set_post_entrypoint("./cleanup.sh", Some("always()".to_string()));
+
+        let steps = action.runs.steps.as_ref().unwrap();
+        assert_eq!(steps.len(), 4);
+        assert_eq!(
+            serde_yaml::to_string(&steps[0]).unwrap(),
+            "id: pre-entrypoint\nname: Pre entrypoint\nshell: pwsh\nrun: ${{ github.action_path }}/setup.ps1\n"
+        );
+        assert_eq!(
+            serde_yaml::to_string(&steps[3]).unwrap(),
+            "id: post-entrypoint\nname: Post entrypoint\nshell: bash\nrun: ${{ github.action_path }}/cleanup.sh\nif: always()\n"
+        );
+        // Container only fields are not used
+        assert_eq!(action.runs.post_if, None);
+    }
+
+    #[test]
+    fn test_builder_yaml() {
+        let action = ActionYML::builder()
+            .name("Builder Action")
+            .description("Action built using the builder")
+            .input("mode", |input| {
+                input
+                    .description("Mode")
+                    .required(true)
+                    .deprecated("Use `level`")
+            })
+            .input("level", |input| input.description("Level").default("1"))
+            .output("version", |output| output.description("Version"))
+            .map_steps(|steps| steps.clear())
+            .composite_step(ActionRunStep {
+                id: Some("tool".to_string()),
+                shell: Some("bash".to_string()),
+                run: Some("./tool.sh".to_string()),
+                ..Default::default()
+            })
+            .output("path", |output| {
+                output
+                    .description("Path")
+                    .value("${{ steps.tool.outputs.path }}")
+            })
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            action.to_yaml().unwrap(),
+            r#"name: Builder Action
+description: Action built using the builder
+inputs:
+  mode:
+    description: Mode
+    required: true
+    deprecationMessage: Use `level`
+  level:
+    description: Level
+    default: '1'
+outputs:
+  version:
+    description: Version
+    value: ${{ steps.cargo-run.outputs.version }}
+  path:
+    description: Path
+    value: ${{ steps.tool.outputs.path }}
+runs:
+  using: composite
+  steps:
+  - id: tool
+    shell: bash
+    run: ./tool.sh
+"#
+        );
+
+        let action = ActionYML::builder()
+            .name("Container Action")
+            .description("Container Action built using the builder")
+            .output("version", |output| output.description("Version"))
+            .docker_image("./Dockerfile")
+            .build()
+            .unwrap();
+        assert_eq!(action.outputs["version"].value, None);
+        assert_eq!(action.runs.image, Some(PathBuf::from("./Dockerfile")));
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        // Description is required (the name defaults to the package name)
+        let err = ActionYML::builder().build().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid Action:\n - error: `description` is required"
+        );
+
+        // Composite Actions need steps
+        let err = ActionYML::builder()
+            .name("Action")
+            .description("Action")
+            .map_steps(|steps| steps.clear())
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid Action:\n - error: `runs.steps` is required for composite Actions"
+        );
+
+        // Container Actions need an image
+        let mut builder = ActionYML::builder()
+            .name("Action")
+            .description("Action")
+            .docker_image("./Dockerfile");
+        builder.action.runs.image = None;
+        let err = builder.build().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid Action:\n - error: `runs.image` is required for container Actions"
+        );
+
+        // Invalid input names
+        let err = ActionYML::builder()
+            .name("Action")
+            .description("Action")
+            .input("my input", |input| input.description("Input"))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid Action:\n - error: `inputs.my input` is not a valid input name"
+        );
+
+        // Warnings do not fail the build
+        assert!(ActionYML::builder()
+            .name("Action")
+            .description("Action")
+            .input("mode", |input| input)
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_node_round_trip() {
+        let yaml = r#"name: Node Action
+description: JavaScript shim downloading the Action binary
+runs:
+  using: node20
+  main: dist/index.js
+  post: dist/cleanup.js
+  post-if: always()
+"#;
+        let action = ActionYML::from_yaml(yaml).unwrap();
+        assert_eq!(action.runs.using, ActionRunUsing::Node("20".to_string()));
+        assert_eq!(action.runs.main, Some("dist/index.js".to_string()));
+        assert_eq!(action.runs.post, Some("dist/cleanup.js".to_string()));
+
+        let generated = serde_yaml::to_string(&action.runs).unwrap();
+        assert_eq!(
+            generated,
+            "using: node20\nmain: dist/index.js\npost: dist/cleanup.js\npost-if: always()\n"
+        );
+        assert_eq!(
+            serde_yaml::from_str::<ActionRuns>(&generated).unwrap(),
+            action.runs
+        );
+
+        let mut action = ActionYML::default();
+        action.set_node("16", "./dist/index.js");
+        action.set_pre_entrypoint("./dist/setup.js", None);
+        assert_eq!(
+            serde_yaml::to_string(&action.runs).unwrap(),
+            "using: node16\nmain: ./dist/index.js\npre: ./dist/setup.js\n"
+        );
+        assert_eq!(action.output_value_step_id, None);
+
+        assert!(serde_yaml::from_str::<ActionRunUsing>("node").is_err());
+        assert!(serde_yaml::from_str::<ActionRunUsing>("python3").is_err());
+    }
+
+    #[test]
+    fn test_write_nested_directories() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join("nested/new/action.yml");
+
+        assert_eq!(
+            action(path.clone()).write_in_root(root.path()),
+            Ok(path.clone())
+        );
+        assert!(path.exists());
+
+        // Outside of the root
+        let outside = tempfile::tempdir().unwrap();
+        let path = outside.path().join("nested/action.yml");
+        let result = action(path).write_in_root(root.path());
+        assert!(matches!(
+            result,
+            Err(ActionsError::FileError {
+                kind: std::io::ErrorKind::PermissionDenied,
+                ..
+            })
+        ));
+    }
 }
@@ -0,0 +1,76 @@
+//! # Names
+//!
+//! Input and output names need to follow the GitHub Actions identifier rules:
+//! start with a letter or `_` and only contain alphanumeric characters, `-` or `_`.
+
+/// Check if the name is a valid Action input / output name
+///
+/// # Examples
+///
+/// ```
+/// use ghactions_core::actions::names::is_valid_name;
+///
+/// assert!(is_valid_name("html-url"));
+/// assert!(!is_valid_name("html url"));
+/// assert!(!is_valid_name("1url"));
+/// ```
+pub fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Sanitize a name so it is a valid Action input / output name
+///
+/// # Examples
+///
+/// ```
+/// use ghactions_core::actions::names::sanitize_name;
+///
+/// assert_eq!(sanitize_name("html url"), "html_url");
+/// assert_eq!(sanitize_name("1url"), "_1url");
+/// ```
+pub fn sanitize_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !sanitized.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_with_spaces() {
+        assert!(!is_valid_name("my output"));
+        assert_eq!(sanitize_name("my output"), "my_output");
+    }
+
+    #[test]
+    fn test_name_starting_with_digit() {
+        assert!(!is_valid_name("2fa"));
+        assert_eq!(sanitize_name("2fa"), "_2fa");
+    }
+
+    #[test]
+    fn test_name_kebab_case() {
+        assert!(is_valid_name("html-url"));
+        assert!(is_valid_name("_private"));
+        assert_eq!(sanitize_name("html-url"), "html-url");
+    }
+}
@@ -0,0 +1,307 @@
+//! # Validate
+//!
+//! Validate Action YAML files against the GitHub Actions metadata syntax.
+//!
+//! https://docs.github.com/en/actions/creating-actions/metadata-syntax-for-github-actions
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+use super::branding;
+use super::models::{ActionRunUsing, ActionYML};
+use super::names::is_valid_name;
+
+/// Top level keys supported by GitHub
+const ALLOWED_KEYS: [&str; 7] = [
+    "name",
+    "author",
+    "description",
+    "inputs",
+    "outputs",
+    "runs",
+    "branding",
+];
+
+/// Severity of a validation issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The Action will not work (or can't be published)
+    Error,
+    /// The Action works but something is likely wrong
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// Validation Issue found in an Action file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    /// Severity
+    pub severity: Severity,
+    /// Field the issue is about (e.g. `inputs.mode`)
+    pub field: String,
+    /// Message
+    pub message: String,
+    /// Line in the file (1-based, if known)
+    pub line: Option<usize>,
+}
+
+impl ValidationIssue {
+    fn error(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            field: field.into(),
+            message: message.into(),
+            line: None,
+        }
+    }
+
+    fn warning(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::error(field, message)
+        }
+    }
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.field.is_empty() {
+            true => write!(f, "{}: {}", self.severity, self.message),
+            false => write!(f, "{}: `{}` {}", self.severity, self.field, self.message),
+        }
+    }
+}
+
+impl ActionYML {
+    /// Validate the Action against the GitHub Actions metadata syntax
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        if self.name.as_deref().unwrap_or_default().is_empty() {
+            issues.push(ValidationIssue::error("name", "is required"));
+        }
+        if self.description.as_deref().unwrap_or_default().is_empty() {
+            issues.push(ValidationIssue::error("description", "is required"));
+        }
+
+        if let Some(branding) = &self.branding {
+            if !branding::is_valid_icon(&branding.icon) {
+                issues.push(ValidationIssue::error(
+                    "branding.icon",
+                    format!("`{}` is not a supported icon", branding.icon),
+                ));
+            }
+            if !branding::is_valid_colour(&branding.color) {
+                issues.push(ValidationIssue::error(
+                    "branding.color",
+                    format!("`{}` is not a supported colour", branding.color),
+                ));
+            }
+        }
+
+        for (name, input) in self.inputs.iter() {
+            let field = format!("inputs.{}", name);
+            if !is_valid_name(name) {
+                issues.push(ValidationIssue::error(&field, "is not a valid input name"));
+            }
+            if input.description.is_none() {
+                issues.push(ValidationIssue::warning(&field, "has no description"));
+            }
+            if input.required == Some(true) && input.default.is_some() {
+                issues.push(ValidationIssue::warning(
+                    &field,
+                    "is required but has a default value",
+                ));
+            }
+        }
+
+        let composite = self.runs.using == ActionRunUsing::Composite;
+        for (name, output) in self.outputs.iter() {
+            let field = format!("outputs.{}", name);
+            if !is_valid_name(name) {
+                issues.push(ValidationIssue::error(&field, "is not a valid output name"));
+            }
+            if composite && output.value.is_none() {
+                issues.push(ValidationIssue::error(
+                    &field,
+                    "needs a value for composite Actions",
+                ));
+            }
+        }
+
+        match self.runs.using {
+            ActionRunUsing::Docker => {
+                if self.runs.image.is_none() {
+                    issues.push(ValidationIssue::error(
+                        "runs.image",
+                        "is required for container Actions",
+                    ));
+                }
+            }
+            ActionRunUsing::Node(_) => {
+                if self.runs.main.is_none() {
+                    issues.push(ValidationIssue::error(
+                        "runs.main",
+                        "is required for JavaScript Actions",
+                    ));
+                }
+            }
+            ActionRunUsing::Composite => {
+                let steps = self.runs.steps.as_deref().unwrap_or_default();
+                if steps.is_empty() {
+                    issues.push(ValidationIssue::error(
+                        "runs.steps",
+                        "is required for composite Actions",
+                    ));
+                }
+                for (index, step) in steps.iter().enumerate() {
+                    if step.run.is_some() && step.shell.is_none() {
+                        issues.push(ValidationIssue::error(
+                            format!("runs.steps[{}]", index),
+                            "needs a shell to run a command",
+                        ));
+                    }
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Load and validate an Action file
+    ///
+    /// Issues include the line in the file where possible. Unknown top level keys are
+    /// reported as warnings.
+    pub fn validate_file(path: impl AsRef<Path>) -> Vec<ValidationIssue> {
+        let content = match std::fs::read_to_string(path.as_ref()) {
+            Ok(content) => content,
+            Err(err) => return vec![ValidationIssue::error("", err.to_string())],
+        };
+
+        let mut issues = Vec::new();
+        if let Ok(serde_yaml::Value::Mapping(mapping)) =
+            serde_yaml::from_str::<serde_yaml::Value>(&content)
+        {
+            for key in mapping.keys().filter_map(|key| key.as_str()) {
+                if !ALLOWED_KEYS.contains(&key) {
+                    issues.push(ValidationIssue::warning(key, "is not a supported key"));
+                }
+            }
+        }
+
+        match ActionYML::from_yaml(&content) {
+            Ok(action) => issues.extend(action.validate()),
+            Err(err) => {
+                let line = err.location().map(|location| location.line());
+                issues.push(ValidationIssue {
+                    line,
+                    ..ValidationIssue::error("", err.to_string())
+                });
+            }
+        }
+
+        for issue in issues.iter_mut() {
+            if issue.line.is_none() {
+                issue.line = find_line(&content, &issue.field);
+            }
+        }
+        issues
+    }
+}
+
+/// Find the line of a (dotted) field in a YAML file (e.g. `inputs.mode`)
+///
+/// Uses the indentation of the keys so this only works for block style mappings.
+fn find_line(content: &str, field: &str) -> Option<usize> {
+    if field.is_empty() {
+        return None;
+    }
+    let mut line_number = 0;
+    let mut parent: Option<usize> = None;
+    let mut lines = content.lines().enumerate();
+
+    for key in field.split('.') {
+        // Sequences (e.g. `steps[1]`) point at the key
+        let key = key.split('[').next().unwrap_or(key);
+        // Top level keys are not indented, nested keys use the indent of the first child
+        let mut level = parent.map_or(Some(0), |_| None);
+        loop {
+            let (number, line) = lines.next()?;
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - trimmed.len();
+            if parent.is_some_and(|parent| indent <= parent) {
+                // Left the parent mapping without finding the key
+                return None;
+            }
+            if *level.get_or_insert(indent) != indent {
+                continue;
+            }
+            if trimmed.starts_with(&format!("{}:", key))
+                || trimmed.starts_with(&format!("\"{}\":", key))
+            {
+                line_number = number + 1;
+                parent = Some(indent);
+                break;
+            }
+        }
+    }
+    Some(line_number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_line() {
+        let content = "name: Test\ninputs:\n  name:\n    description: Name\n  mode:\n    default: fast\nruns:\n  steps:\n  - run: echo\n";
+        assert_eq!(find_line(content, "name"), Some(1));
+        assert_eq!(find_line(content, "inputs.mode"), Some(5));
+        assert_eq!(find_line(content, "inputs.name"), Some(3));
+        assert_eq!(find_line(content, "runs.steps[0]"), Some(8));
+        assert_eq!(find_line(content, "outputs.version"), None);
+        assert_eq!(find_line(content, "description"), None);
+        assert_eq!(find_line(content, "inputs.default"), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut action = ActionYML::default();
+        assert_eq!(
+            action.validate(),
+            vec![ValidationIssue::error("description", "is required")]
+        );
+
+        action.description = Some("Test".to_string());
+        action.inputs.insert(
+            "bad name".to_string(),
+            crate::ActionInput {
+                description: Some("Bad".to_string()),
+                ..Default::default()
+            },
+        );
+        action
+            .outputs
+            .insert("version".to_string(), Default::default());
+        action.runs.steps.as_mut().unwrap()[0].shell = None;
+
+        assert_eq!(
+            action.validate(),
+            vec![
+                ValidationIssue::error("inputs.bad name", "is not a valid input name"),
+                ValidationIssue::error("outputs.version", "needs a value for composite Actions"),
+                ValidationIssue::error("runs.steps[0]", "needs a shell to run a command"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,638 @@
+//! # Artifacts
+//!
+//! Upload workflow artifacts using the Actions artifact (v4) API, the same API used by
+//! `actions/upload-artifact@v4`:
+//!
+//! 1. Create the artifact using the results service (`ACTIONS_RESULTS_URL`)
+//! 2. Zip the files and upload the archive in blocks to the signed blob URL
+//! 3. Finalize the artifact with the size and the SHA-256 digest of the archive
+//!
+//! ```no_run
+//! # async fn upload() -> Result<(), ghactions_core::ActionsError> {
+//! use std::path::PathBuf;
+//! use ghactions_core::artifacts::ArtifactClient;
+//!
+//! let client = ArtifactClient::from_env()?;
+//! let artifact = client
+//!     .upload("dist", &[PathBuf::from("target/release/my-action")], Some(7))
+//!     .await?;
+//! println!("Uploaded artifact {} ({} bytes)", artifact.id, artifact.size);
+//! # Ok(())
+//! # }
+//! ```
+use std::io::{Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+
+use base64::prelude::{Engine, BASE64_STANDARD, BASE64_URL_SAFE_NO_PAD};
+use bytes::Bytes;
+use http::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
+use http::{Method, Request, StatusCode};
+use http_body_util::{BodyExt, Full};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::proxy::{http_client, HttpClient, ProxySettings};
+use crate::ActionsError;
+
+/// Environment variable containing the URL of the results service
+pub const RESULTS_URL_ENV: &str = "ACTIONS_RESULTS_URL";
+/// Environment variable containing the runtime token of the job
+pub const RUNTIME_TOKEN_ENV: &str = "ACTIONS_RUNTIME_TOKEN";
+
+/// Default size of the uploaded blocks (8 MiB)
+pub const DEFAULT_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Twirp service of the artifact API
+const ARTIFACT_SERVICE: &str = "twirp/github.actions.results.api.v1.ArtifactService";
+
+/// Characters not allowed in artifact names
+const INVALID_NAME_CHARACTERS: [char; 10] = ['"', ':', '<', '>', '|', '*', '?', '\r', '\n', '\\'];
+
+/// Uploaded artifact
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadedArtifact {
+    /// ID of the artifact
+    pub id: u64,
+    /// Size of the zip archive (in bytes)
+    pub size: u64,
+}
+
+/// Client of the artifact (v4) API
+#[derive(Debug, Clone)]
+pub struct ArtifactClient {
+    results_url: String,
+    runtime_token: String,
+    run_backend_id: String,
+    job_backend_id: String,
+    chunk_size: usize,
+    client: HttpClient<Full<Bytes>>,
+}
+
+impl ArtifactClient {
+    /// Create the client using `ACTIONS_RESULTS_URL` and `ACTIONS_RUNTIME_TOKEN`
+    ///
+    /// Both are only set by the runner for (JavaScript / Docker) Actions, `run` steps do
+    /// not have access to them.
+    pub fn from_env() -> Result<Self, ActionsError> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+                .ok_or_else(|| ActionsError::ArtifactError(format!("`{}` is not set", name)))
+        };
+        Self::new(var(RESULTS_URL_ENV)?, var(RUNTIME_TOKEN_ENV)?)
+    }
+
+    /// Create the client using the URL of the results service and the runtime token
+    ///
+    /// The workflow run and job (backend) IDs are read from the claims of the token.
+    pub fn new(
+        results_url: impl Into<String>,
+        runtime_token: impl Into<String>,
+    ) -> Result<Self, ActionsError> {
+        let runtime_token = runtime_token.into();
+        let (run_backend_id, job_backend_id) = backend_ids(&runtime_token)?;
+
+        Ok(Self {
+            results_url: results_url.into().trim().trim_end_matches('/').to_string(),
+            runtime_token,
+            run_backend_id,
+            job_backend_id,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            client: http_client(&ProxySettings::from_env())?,
+        })
+    }
+
+    /// Set the size of the blocks the archive is uploaded in (see [DEFAULT_CHUNK_SIZE])
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Upload `paths` (files or directories) as the artifact `name`
+    ///
+    /// Files are added to the root of the archive and directories are added recursively
+    /// (e.g. `dist/bin/app` for the directory `dist`). The artifact expires after
+    /// `retention_days` (the retention of the repository is used if not set).
+    ///
+    /// Fails with [ActionsError::ArtifactConflict] if an artifact with the same name exists
+    /// in the workflow run and [ActionsError::ArtifactQuotaExceeded] if the storage quota of
+    /// the account has been hit.
+    pub async fn upload(
+        &self,
+        name: &str,
+        paths: &[PathBuf],
+        retention_days: Option<u32>,
+    ) -> Result<UploadedArtifact, ActionsError> {
+        validate_name(name)?;
+        let archive = zip_files(paths)?;
+        let size = archive.len() as u64;
+        let digest = format!("sha256:{:x}", Sha256::digest(&archive));
+
+        let mut request = json!({
+            "workflowRunBackendId": self.run_backend_id,
+            "workflowJobRunBackendId": self.job_backend_id,
+            "name": name,
+            "version": 4,
+        });
+        if let Some(days) = retention_days {
+            request["expiresAt"] = json!(expires_at(days)?);
+        }
+        let response = self.call(name, "CreateArtifact", request).await?;
+        let upload_url = match response.get("signedUploadUrl").and_then(Value::as_str) {
+            Some(url) if response_ok(&response) => url.to_string(),
+            _ => {
+                return Err(ActionsError::ArtifactError(
+                    "failed to create the artifact".to_string(),
+                ))
+            }
+        };
+
+        self.upload_blocks(&upload_url, Bytes::from(archive))
+            .await?;
+
+        let response = self
+            .call(
+                name,
+                "FinalizeArtifact",
+                json!({
+                    "workflowRunBackendId": self.run_backend_id,
+                    "workflowJobRunBackendId": self.job_backend_id,
+                    "name": name,
+                    "size": size.to_string(),
+                    "hash": digest,
+                }),
+            )
+            .await?;
+        let id = match response.get("artifactId") {
+            Some(Value::String(id)) => id.parse().ok(),
+            Some(Value::Number(id)) => id.as_u64(),
+            _ => None,
+        };
+        match id {
+            Some(id) if response_ok(&response) => Ok(UploadedArtifact { id, size }),
+            _ => Err(ActionsError::ArtifactError(
+                "failed to finalize the artifact".to_string(),
+            )),
+        }
+    }
+
+    /// Call a method of the artifact service
+    async fn call(&self, name: &str, method: &str, body: Value) -> Result<Value, ActionsError> {
+        let url = format!("{}/{}/{}", self.results_url, ARTIFACT_SERVICE, method);
+        let request = Request::post(url.as_str())
+            .header(AUTHORIZATION, format!("Bearer {}", self.runtime_token))
+            .header(CONTENT_TYPE, "application/json")
+            .header(ACCEPT, "application/json")
+            .header(USER_AGENT, "ghactions")
+            .body(Full::new(Bytes::from(body.to_string())))
+            .map_err(|err| ActionsError::ArtifactError(err.to_string()))?;
+
+        let (status, body) = self.send(request).await?;
+        if !status.is_success() {
+            return Err(service_error(name, method, status, &body));
+        }
+        serde_json::from_slice(&body)
+            .map_err(|err| ActionsError::ArtifactError(format!("invalid response: {}", err)))
+    }
+
+    /// Upload the archive to the signed blob URL in blocks and commit the block list
+    async fn upload_blocks(&self, upload_url: &str, archive: Bytes) -> Result<(), ActionsError> {
+        let separator = match upload_url.contains('?') {
+            true => '&',
+            false => '?',
+        };
+        let mut block_list = String::from(r#"<?xml version="1.0" encoding="utf-8"?><BlockList>"#);
+
+        let mut offset = 0;
+        let mut index = 0;
+        while offset < archive.len() || index == 0 {
+            let end = archive.len().min(offset + self.chunk_size);
+            // Fixed length IDs of digits (the encoded ID has no characters to escape)
+            let block_id = BASE64_STANDARD.encode(format!("{:012}", index));
+            let url = format!("{}{}comp=block&blockid={}", upload_url, separator, block_id);
+            let request = Request::put(url.as_str())
+                .body(Full::new(archive.slice(offset..end)))
+                .map_err(|err| ActionsError::ArtifactError(err.to_string()))?;
+            let (status, body) = self.send(request).await?;
+            if !status.is_success() {
+                return Err(blob_error(status, &body));
+            }
+
+            block_list.push_str(&format!("<Latest>{}</Latest>", block_id));
+            offset = end;
+            index += 1;
+        }
+        block_list.push_str("</BlockList>");
+
+        let url = format!("{}{}comp=blocklist", upload_url, separator);
+        let request = Request::put(url.as_str())
+            .header("x-ms-blob-content-type", "application/zip")
+            .header(CONTENT_TYPE, "application/xml")
+            .body(Full::new(Bytes::from(block_list)))
+            .map_err(|err| ActionsError::ArtifactError(err.to_string()))?;
+        let (status, body) = self.send(request).await?;
+        if !status.is_success() {
+            return Err(blob_error(status, &body));
+        }
+        Ok(())
+    }
+
+    /// Send a request (through the proxy of the runner) and read the response body
+    async fn send(
+        &self,
+        request: Request<Full<Bytes>>,
+    ) -> Result<(StatusCode, Bytes), ActionsError> {
+        let response = self
+            .client
+            .request(request)
+            .await
+            .map_err(|err| ActionsError::ArtifactError(err.to_string()))?;
+        let status = response.status();
+        let body = response
+            .into_body()
+            .collect()
+            .await
+            .map_err(|err| ActionsError::ArtifactError(err.to_string()))?
+            .to_bytes();
+        Ok((status, body))
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenClaims {
+    #[serde(default)]
+    scp: String,
+}
+
+/// Get the workflow run and job backend IDs from the `Actions.Results:<run>:<job>` scope of
+/// the runtime token
+fn backend_ids(runtime_token: &str) -> Result<(String, String), ActionsError> {
+    let invalid = || ActionsError::ArtifactError(format!("invalid `{}`", RUNTIME_TOKEN_ENV));
+
+    let payload = runtime_token.split('.').nth(1).ok_or_else(invalid)?;
+    let payload = BASE64_URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|_| invalid())?;
+    let claims: TokenClaims = serde_json::from_slice(&payload).map_err(|_| invalid())?;
+
+    claims
+        .scp
+        .split_whitespace()
+        .find_map(|scope| {
+            let mut parts = scope.strip_prefix("Actions.Results:")?.split(':');
+            match (parts.next(), parts.next()) {
+                (Some(run), Some(job)) => Some((run.to_string(), job.to_string())),
+                _ => None,
+            }
+        })
+        .ok_or_else(invalid)
+}
+
+/// Check the artifact name (same rules as `actions/upload-artifact`)
+fn validate_name(name: &str) -> Result<(), ActionsError> {
+    if name.trim().is_empty() {
+        return Err(ActionsError::ArtifactError(
+            "the artifact name is empty".to_string(),
+        ));
+    }
+    match name
+        .chars()
+        .find(|c| INVALID_NAME_CHARACTERS.contains(c) || *c == '/')
+    {
+        Some(c) => Err(ActionsError::ArtifactError(format!(
+            "the artifact name `{}` contains the invalid character {:?}",
+            name, c
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Expiration timestamp (RFC 3339) of an artifact kept for `days`
+fn expires_at(days: u32) -> Result<String, ActionsError> {
+    let expires_at = time::OffsetDateTime::now_utc() + time::Duration::days(days.into());
+    expires_at
+        .format(&time::format_description::well_known::Rfc3339)
+        .map_err(|err| ActionsError::ArtifactError(err.to_string()))
+}
+
+fn response_ok(response: &Value) -> bool {
+    response.get("ok").and_then(Value::as_bool).unwrap_or(false)
+}
+
+/// Map the (Twirp) error of the artifact service
+fn service_error(name: &str, method: &str, status: StatusCode, body: &[u8]) -> ActionsError {
+    let error: Value = serde_json::from_slice(body).unwrap_or_default();
+    let code = error
+        .get("code")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let message = error
+        .get("msg")
+        .and_then(Value::as_str)
+        .map(String::from)
+        .unwrap_or_else(|| String::from_utf8_lossy(body).trim().to_string());
+
+    if status == StatusCode::CONFLICT || code == "already_exists" {
+        ActionsError::ArtifactConflict(name.to_string())
+    } else if code == "resource_exhausted" || message.contains("insufficient usage") {
+        ActionsError::ArtifactQuotaExceeded
+    } else {
+        ActionsError::ArtifactError(format!("{} failed: HTTP {} ({})", method, status, message))
+    }
+}
+
+fn blob_error(status: StatusCode, body: &[u8]) -> ActionsError {
+    ActionsError::ArtifactError(format!(
+        "upload failed: HTTP {} ({})",
+        status,
+        String::from_utf8_lossy(body).trim()
+    ))
+}
+
+/// Zip the files and directories (recursively) into an in-memory archive
+fn zip_files(paths: &[PathBuf]) -> Result<Vec<u8>, ActionsError> {
+    if paths.is_empty() {
+        return Err(ActionsError::ArtifactError(
+            "no files to upload".to_string(),
+        ));
+    }
+
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let root = path.parent().unwrap_or_else(|| Path::new(""));
+            collect_files(root, path, &mut files)?;
+        } else if path.is_file() {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            files.push((name, path.clone()));
+        } else {
+            return Err(ActionsError::ArtifactError(format!(
+                "`{}` does not exist",
+                path.display()
+            )));
+        }
+    }
+
+    let mut names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+    names.sort_unstable();
+    if let Some(name) = names.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Err(ActionsError::ArtifactError(format!(
+            "the file `{}` is added twice",
+            name[0]
+        )));
+    }
+
+    let zip_error = |err: zip::result::ZipError| ActionsError::ArtifactError(err.to_string());
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, path) in files {
+        let options = zip::write::FileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .large_file(std::fs::metadata(&path)?.len() >= u32::MAX as u64);
+        writer.start_file(name, options).map_err(zip_error)?;
+        std::io::copy(&mut std::fs::File::open(&path)?, &mut writer)?;
+    }
+    Ok(writer.finish().map_err(zip_error)?.into_inner())
+}
+
+/// Collect the files of `dir` (recursively) with their name relative to `root`
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<(String, PathBuf)>,
+) -> Result<(), ActionsError> {
+    let mut entries = std::fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else {
+            let name = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((name, path));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, body_string, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn runtime_token() -> String {
+        let claims = json!({ "scp": "Actions.ExampleScope Actions.Results:run-id:job-id" });
+        format!(
+            "header.{}.signature",
+            BASE64_URL_SAFE_NO_PAD.encode(claims.to_string())
+        )
+    }
+
+    fn files() -> (tempfile::TempDir, Vec<PathBuf>) {
+        let dir = tempfile::tempdir().unwrap();
+        let dist = dir.path().join("dist");
+        std::fs::create_dir_all(dist.join("bin")).unwrap();
+        std::fs::write(dist.join("bin").join("app"), "binary".repeat(100)).unwrap();
+        std::fs::write(dist.join("README.md"), "# App").unwrap();
+        std::fs::write(dir.path().join("report.txt"), "ok").unwrap();
+
+        let paths = vec![dist, dir.path().join("report.txt")];
+        (dir, paths)
+    }
+
+    #[test]
+    fn test_backend_ids() {
+        assert_eq!(
+            backend_ids(&runtime_token()).unwrap(),
+            ("run-id".to_string(), "job-id".to_string())
+        );
+        assert!(backend_ids("not-a-token").is_err());
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("dist-linux_x64.1").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("dist/linux").is_err());
+        assert!(validate_name("dist:linux").is_err());
+    }
+
+    #[test]
+    fn test_zip_files() {
+        let (_dir, paths) = files();
+        let archive = zip_files(&paths).unwrap();
+
+        let mut zip = zip::ZipArchive::new(Cursor::new(archive)).unwrap();
+        let mut names: Vec<&str> = zip.file_names().collect();
+        names.sort_unstable();
+        assert_eq!(names, vec!["dist/README.md", "dist/bin/app", "report.txt"]);
+
+        let mut content = String::new();
+        zip.by_name("dist/README.md")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "# App");
+
+        assert!(zip_files(&[]).is_err());
+        assert!(zip_files(&[PathBuf::from("does/not/exist")]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_upload() {
+        let server = MockServer::start().await;
+        let (_dir, paths) = files();
+        let archive = zip_files(&paths).unwrap();
+        let chunk_size = 100;
+        let blocks = (archive.len() + chunk_size - 1) / chunk_size;
+        assert!(blocks > 1);
+
+        Mock::given(method("POST"))
+            .and(path(format!("/{}/CreateArtifact", ARTIFACT_SERVICE)))
+            .and(header(
+                "authorization",
+                format!("Bearer {}", runtime_token()).as_str(),
+            ))
+            .and(body_partial_json(json!({
+                "workflowRunBackendId": "run-id",
+                "workflowJobRunBackendId": "job-id",
+                "name": "dist",
+                "version": 4,
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "ok": true,
+                "signedUploadUrl": format!("{}/blob/dist.zip?sig=signature", server.uri()),
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/blob/dist.zip"))
+            .and(query_param("sig", "signature"))
+            .and(query_param("comp", "block"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(blocks as u64)
+            .mount(&server)
+            .await;
+        let block_list: String = (0..blocks)
+            .map(|index| {
+                format!(
+                    "<Latest>{}</Latest>",
+                    BASE64_STANDARD.encode(format!("{:012}", index))
+                )
+            })
+            .collect();
+        Mock::given(method("PUT"))
+            .and(path("/blob/dist.zip"))
+            .and(query_param("comp", "blocklist"))
+            .and(body_string(format!(
+                r#"<?xml version="1.0" encoding="utf-8"?><BlockList>{}</BlockList>"#,
+                block_list
+            )))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("/{}/FinalizeArtifact", ARTIFACT_SERVICE)))
+            .and(body_partial_json(json!({
+                "name": "dist",
+                "size": archive.len().to_string(),
+                "hash": format!("sha256:{:x}", Sha256::digest(&archive)),
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "ok": true,
+                "artifactId": "1234",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ArtifactClient::new(format!("{}/", server.uri()), runtime_token())
+            .unwrap()
+            .with_chunk_size(chunk_size);
+        let artifact = client.upload("dist", &paths, Some(7)).await.unwrap();
+        assert_eq!(
+            artifact,
+            UploadedArtifact {
+                id: 1234,
+                size: archive.len() as u64
+            }
+        );
+
+        // Blocks are uploaded in order and cover the whole archive
+        let uploaded: Vec<u8> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|request| {
+                request
+                    .url
+                    .query()
+                    .unwrap_or_default()
+                    .contains("comp=block&")
+            })
+            .flat_map(|request| request.body.clone())
+            .collect();
+        assert_eq!(uploaded, archive);
+    }
+
+    #[tokio::test]
+    async fn test_upload_errors() {
+        let server = MockServer::start().await;
+        let (_dir, paths) = files();
+
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "name": "exists" })))
+            .respond_with(ResponseTemplate::new(409).set_body_json(json!({
+                "code": "already_exists",
+                "msg": "an artifact with this name already exists on the workflow run",
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "name": "quota" })))
+            .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+                "code": "permission_denied",
+                "msg": "insufficient usage to create artifact",
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "name": "failure" })))
+            .respond_with(ResponseTemplate::new(500).set_body_string("Internal Error"))
+            .mount(&server)
+            .await;
+
+        let client = ArtifactClient::new(server.uri(), runtime_token()).unwrap();
+        assert_eq!(
+            client.upload("exists", &paths, None).await,
+            Err(ActionsError::ArtifactConflict("exists".to_string()))
+        );
+        assert_eq!(
+            client.upload("quota", &paths, None).await,
+            Err(ActionsError::ArtifactQuotaExceeded)
+        );
+        assert_eq!(
+            client.upload("failure", &paths, None).await,
+            Err(ActionsError::ArtifactError(
+                "CreateArtifact failed: HTTP 500 Internal Server Error (Internal Error)"
+                    .to_string()
+            ))
+        );
+    }
+}
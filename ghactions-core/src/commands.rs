@@ -0,0 +1,442 @@
+//! # Workflow Commands
+//!
+//! Commands printed to stdout which are processed by the runner (e.g. `::add-mask::`).
+//!
+//! https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Escape the data of a workflow command (`%`, `\r` and `\n`)
+///
+/// ```
+/// use ghactions_core::commands::escape_data;
+///
+/// assert_eq!(escape_data("100%\r\n"), "100%25%0D%0A");
+/// ```
+pub fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a property value of a workflow command (also escapes `:` and `,`)
+///
+/// ```
+/// use ghactions_core::commands::escape_property;
+///
+/// assert_eq!(escape_property("tests::a, b"), "tests%3A%3Aa%2C b");
+/// ```
+pub fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Get the `::set-output` command of an output (used by runners without output files)
+///
+/// The name is escaped as a property and the value as data, so values can not start new
+/// workflow commands.
+///
+/// ```
+/// use ghactions_core::commands::set_output_command;
+///
+/// assert_eq!(
+///     set_output_command("branch", "main\n::set-env name=PATH::/tmp"),
+///     "::set-output name=branch::main%0A::set-env name=PATH::/tmp"
+/// );
+/// ```
+pub fn set_output_command(name: &str, value: &str) -> String {
+    format!(
+        "::set-output name={}::{}",
+        escape_property(name),
+        escape_data(value)
+    )
+}
+
+/// Get the `::add-mask::` commands for a value (one per line, empty lines are skipped)
+///
+/// ```
+/// use ghactions_core::commands::mask_commands;
+///
+/// assert_eq!(
+///     mask_commands("first\r\nsecond"),
+///     vec!["::add-mask::first", "::add-mask::second"]
+/// );
+/// assert!(mask_commands("").is_empty());
+/// ```
+pub fn mask_commands(value: &str) -> Vec<String> {
+    value
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .filter(|line| !line.is_empty())
+        .map(|line| format!("::add-mask::{}", escape_data(line)))
+        .collect()
+}
+
+/// Mask a value so the runner redacts it in all following log lines
+///
+/// Multi-line values are masked line by line. The commands are always printed to stdout
+/// (even when the logger filters `info` messages) as a missed mask would leak the value.
+pub fn add_mask(value: &str) {
+    for command in mask_commands(value) {
+        println!("{}", command);
+    }
+}
+
+/// Active `::stop-commands::` guards (guard IDs) and the token resuming the commands
+static STOPPED: Mutex<(Vec<usize>, String)> = Mutex::new((Vec::new(), String::new()));
+static GUARD_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Generate an unpredictable token which does not occur in `content`
+///
+/// The token is derived from the randomly keyed hasher of the standard library, the
+/// current time and a counter (128 bits, hex encoded).
+fn stop_commands_token(content: &str) -> String {
+    loop {
+        let mut token = String::with_capacity(32);
+        for _ in 0..2 {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u128(
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|duration| duration.as_nanos())
+                    .unwrap_or_default(),
+            );
+            hasher.write_usize(GUARD_ID.fetch_add(1, Ordering::SeqCst));
+            hasher.write_u32(std::process::id());
+            token.push_str(&format!("{:016x}", hasher.finish()));
+        }
+        if !content.contains(&token) {
+            return token;
+        }
+    }
+}
+
+/// Guard stopping the processing of workflow commands until it is dropped
+///
+/// Created using [stop_commands], the runner ignores workflow commands (e.g. `::error::`
+/// or `::add-mask::`) in the log lines printed while the guard is alive.
+#[must_use = "the commands are resumed when the guard is dropped"]
+#[derive(Debug)]
+pub struct StopCommandsGuard {
+    id: usize,
+}
+
+/// Stop processing workflow commands until the returned guard is dropped
+///
+/// Prints `::stop-commands::{token}` with a random token and the matching `::{token}::`
+/// when the guard is dropped. Nested guards reuse the token of the outer guard (and do
+/// not print anything), the commands are resumed when the last guard is dropped.
+pub fn stop_commands() -> StopCommandsGuard {
+    stop_commands_excluding("")
+}
+
+fn stop_commands_excluding(content: &str) -> StopCommandsGuard {
+    let id = GUARD_ID.fetch_add(1, Ordering::SeqCst);
+    let mut stopped = STOPPED.lock().unwrap_or_else(|err| err.into_inner());
+    if stopped.0.is_empty() {
+        stopped.1 = stop_commands_token(content);
+        println!("::stop-commands::{}", stopped.1);
+    }
+    stopped.0.push(id);
+    StopCommandsGuard { id }
+}
+
+impl Drop for StopCommandsGuard {
+    fn drop(&mut self) {
+        let mut stopped = STOPPED.lock().unwrap_or_else(|err| err.into_inner());
+        stopped.0.retain(|id| *id != self.id);
+        if stopped.0.is_empty() {
+            println!("::{}::", stopped.1);
+        }
+    }
+}
+
+/// Print untrusted text (e.g. a pull request title) without the runner processing the
+/// workflow commands it contains
+///
+/// The text is printed between `::stop-commands::` and the resume command (see
+/// [stop_commands]), the token never occurs in the text.
+pub fn log_untrusted(text: &str) {
+    let _guard = stop_commands_excluding(text);
+    let token = STOPPED
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .1
+        .clone();
+    // Only possible inside an outer guard (the token can not be changed once printed)
+    println!("{}", text.replace(&format!("::{}::", token), "::***::"));
+}
+
+/// Maximum length of an annotation message (longer messages are truncated)
+pub const MAX_MESSAGE_LENGTH: usize = 4096;
+
+/// Annotation Level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnnotationLevel {
+    /// Error
+    Error,
+    /// Warning
+    Warning,
+    /// Notice
+    Notice,
+}
+
+impl std::fmt::Display for AnnotationLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnnotationLevel::Error => write!(f, "error"),
+            AnnotationLevel::Warning => write!(f, "warning"),
+            AnnotationLevel::Notice => write!(f, "notice"),
+        }
+    }
+}
+
+/// Annotation (rendered as an `::error` / `::warning` / `::notice` workflow command)
+///
+/// ```
+/// use ghactions_core::commands::{Annotation, AnnotationLevel};
+///
+/// let annotation = Annotation::new(AnnotationLevel::Warning, "Unused import")
+///     .file("src/lib.rs")
+///     .line(3)
+///     .column(5);
+/// assert_eq!(
+///     annotation.to_string(),
+///     "::warning file=src/lib.rs,line=3,col=5::Unused import"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    /// Level
+    pub level: AnnotationLevel,
+    /// Title
+    pub title: Option<String>,
+    /// File (relative to the workspace)
+    pub file: Option<PathBuf>,
+    /// Start line
+    pub line: Option<usize>,
+    /// End line
+    pub end_line: Option<usize>,
+    /// Start column
+    pub column: Option<usize>,
+    /// End column
+    pub end_column: Option<usize>,
+    /// Message
+    pub message: String,
+}
+
+impl Annotation {
+    /// Create an annotation without a location
+    pub fn new(level: AnnotationLevel, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            title: None,
+            file: None,
+            line: None,
+            end_line: None,
+            column: None,
+            end_column: None,
+            message: message.into(),
+        }
+    }
+
+    /// Set the message
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Set the file (relative to the workspace)
+    pub fn file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// Set the start line
+    pub fn line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Set the start column
+    pub fn column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Set the end line
+    pub fn end_line(mut self, end_line: usize) -> Self {
+        self.end_line = Some(end_line);
+        self
+    }
+
+    /// Set the end column
+    pub fn end_column(mut self, end_column: usize) -> Self {
+        self.end_column = Some(end_column);
+        self
+    }
+
+    /// Set the title
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Emit the annotation (logged at the level of the annotation)
+    ///
+    /// When running locally (see `logging::is_local_mode`) the annotation is logged as a
+    /// plain line prefixed with its location.
+    pub fn emit(&self) {
+        #[cfg(feature = "log")]
+        {
+            let line = match crate::logging::is_local_mode() {
+                true => self.plain(),
+                false => self.to_string(),
+            };
+            match self.level {
+                AnnotationLevel::Error => log::error!("{}", line),
+                AnnotationLevel::Warning => log::warn!("{}", line),
+                AnnotationLevel::Notice => log::info!("{}", line),
+            }
+        }
+        #[cfg(not(feature = "log"))]
+        println!("{}", self);
+    }
+
+    /// Render the annotation as a plain line (`file:line: title: message`)
+    pub(crate) fn plain(&self) -> String {
+        let mut line = String::new();
+        if let Some(file) = &self.file {
+            line.push_str(&file.display().to_string());
+            if let Some(number) = self.line {
+                line.push_str(&format!(":{}", number));
+            }
+            line.push_str(": ");
+        }
+        if let Some(title) = &self.title {
+            line.push_str(&format!("{}: ", title));
+        }
+        line.push_str(&self.message);
+        line
+    }
+}
+
+impl std::fmt::Display for Annotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut properties = Vec::new();
+        if let Some(file) = &self.file {
+            properties.push(format!(
+                "file={}",
+                escape_property(&file.display().to_string())
+            ));
+        }
+        for (name, value) in [
+            ("line", self.line),
+            ("endLine", self.end_line),
+            ("col", self.column),
+            ("endColumn", self.end_column),
+        ] {
+            if let Some(value) = value {
+                properties.push(format!("{}={}", name, value));
+            }
+        }
+        if let Some(title) = &self.title {
+            properties.push(format!("title={}", escape_property(title)));
+        }
+
+        let mut message = self.message.clone();
+        if message.len() > MAX_MESSAGE_LENGTH {
+            let mut end = MAX_MESSAGE_LENGTH;
+            while !message.is_char_boundary(end) {
+                end -= 1;
+            }
+            message.truncate(end);
+            message.push_str("...");
+        }
+
+        match properties.is_empty() {
+            true => write!(f, "::{}::{}", self.level, escape_data(&message)),
+            false => write!(
+                f,
+                "::{} {}::{}",
+                self.level,
+                properties.join(","),
+                escape_data(&message)
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_commands_token() {
+        let token = stop_commands_token("");
+        assert_eq!(token.len(), 32);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, stop_commands_token(""));
+        assert!(!stop_commands_token(&token).contains(&token));
+    }
+
+    #[test]
+    fn test_annotation_command() {
+        let notice = Annotation::new(AnnotationLevel::Notice, "Done");
+        assert_eq!(notice.to_string(), "::notice::Done");
+        assert_eq!(
+            notice.clone().message("100% done\r\nnext").to_string(),
+            "::notice::100%25 done%0D%0Anext"
+        );
+        assert_eq!(
+            notice
+                .message("Slow test")
+                .title("tests::slow, flaky")
+                .to_string(),
+            "::notice title=tests%3A%3Aslow%2C flaky::Slow test"
+        );
+        assert_eq!(
+            Annotation::new(AnnotationLevel::Warning, "Deprecated API")
+                .file("src/a,b.rs")
+                .line(10)
+                .column(4)
+                .end_line(12)
+                .end_column(8)
+                .title("Deprecated")
+                .to_string(),
+            "::warning file=src/a%2Cb.rs,line=10,endLine=12,col=4,endColumn=8,title=Deprecated::Deprecated API"
+        );
+        assert_eq!(
+            Annotation::new(AnnotationLevel::Error, "a: b")
+                .line(1)
+                .to_string(),
+            "::error line=1::a: b"
+        );
+    }
+
+    #[test]
+    fn test_annotation_plain() {
+        let notice = Annotation::new(AnnotationLevel::Notice, "Done");
+        assert_eq!(notice.plain(), "Done");
+        assert_eq!(
+            notice
+                .message("Deprecated API")
+                .file("src/lib.rs")
+                .line(3)
+                .title("Deprecated")
+                .plain(),
+            "src/lib.rs:3: Deprecated: Deprecated API"
+        );
+    }
+
+    #[test]
+    fn test_mask_commands() {
+        assert_eq!(
+            mask_commands("a%b\n\nc\rd\r\n"),
+            vec!["::add-mask::a%25b", "::add-mask::c%0Dd"]
+        );
+    }
+}
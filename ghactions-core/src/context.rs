@@ -0,0 +1,197 @@
+//! # Context
+//!
+//! Typed GitHub context of the workflow run, parsed once from the default environment
+//! variables set by the runner.
+//!
+//! https://docs.github.com/en/actions/learn-github-actions/variables#default-environment-variables
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::urls::GitHubUrls;
+use crate::{ActionsError, RepositoryReference};
+
+/// Type of the ref which triggered the workflow run (`GITHUB_REF_TYPE`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RefType {
+    /// Branch
+    Branch,
+    /// Tag
+    Tag,
+}
+
+/// GitHub context of the workflow run
+///
+/// ```
+/// use ghactions_core::context::{GitHubContext, RefType};
+///
+/// let context = GitHubContext::from_vars([
+///     ("GITHUB_REPOSITORY", "42ByteLabs/ghactions"),
+///     ("GITHUB_REF_TYPE", "tag"),
+/// ])
+/// .unwrap();
+/// assert_eq!(context.repository.name, "ghactions");
+/// assert_eq!(context.ref_type, Some(RefType::Tag));
+/// assert_eq!(context.server_url, "https://github.com");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GitHubContext {
+    /// Repository (`GITHUB_REPOSITORY`)
+    pub repository: RepositoryReference,
+    /// Commit SHA (`GITHUB_SHA`)
+    pub sha: Option<String>,
+    /// Short ref name, e.g. the branch or tag name (`GITHUB_REF_NAME`)
+    pub ref_name: Option<String>,
+    /// Type of the ref (`GITHUB_REF_TYPE`)
+    pub ref_type: Option<RefType>,
+    /// User which triggered the workflow run (`GITHUB_ACTOR`)
+    pub actor: Option<String>,
+    /// Event which triggered the workflow run (`GITHUB_EVENT_NAME`)
+    pub event_name: Option<String>,
+    /// Workflow run ID (`GITHUB_RUN_ID`)
+    pub run_id: Option<u64>,
+    /// Server URL (`GITHUB_SERVER_URL`, default: `https://github.com`)
+    pub server_url: String,
+    /// API URL (`GITHUB_API_URL`, default: `https://api.github.com`)
+    pub api_url: String,
+    /// Workspace (`GITHUB_WORKSPACE`)
+    pub workspace: Option<PathBuf>,
+}
+
+impl GitHubContext {
+    /// Parse the context from the environment of the process
+    pub fn from_env() -> Result<Self, ActionsError> {
+        Self::from_vars(std::env::vars())
+    }
+
+    /// Parse the context from environment variables (name and value)
+    ///
+    /// `GITHUB_REPOSITORY` is required, the other variables are optional (empty values are
+    /// treated as missing).
+    pub fn from_vars<K, V>(vars: impl IntoIterator<Item = (K, V)>) -> Result<Self, ActionsError>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let vars: HashMap<String, String> = vars
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.into()))
+            .filter(|(_, value)| !value.trim().is_empty())
+            .collect();
+        let var = |name: &str| vars.get(name).map(|value| value.trim().to_string());
+
+        let repository = var("GITHUB_REPOSITORY")
+            .ok_or_else(|| ActionsError::InputError("GITHUB_REPOSITORY".to_string()))?;
+        let run_id =
+            match var("GITHUB_RUN_ID") {
+                Some(run_id) => Some(run_id.parse::<u64>().map_err(|_| {
+                    ActionsError::InputTypeError("GITHUB_RUN_ID".into(), "u64".into())
+                })?),
+                None => None,
+            };
+        let urls = GitHubUrls::resolve(
+            var("GITHUB_SERVER_URL").as_deref(),
+            var("GITHUB_API_URL").as_deref(),
+            None,
+        );
+
+        Ok(Self {
+            repository: RepositoryReference::parse(&repository)?,
+            sha: var("GITHUB_SHA"),
+            ref_name: var("GITHUB_REF_NAME"),
+            ref_type: var("GITHUB_REF_TYPE").and_then(|ref_type| match ref_type.as_str() {
+                "branch" => Some(RefType::Branch),
+                "tag" => Some(RefType::Tag),
+                _ => None,
+            }),
+            actor: var("GITHUB_ACTOR"),
+            event_name: var("GITHUB_EVENT_NAME"),
+            run_id,
+            server_url: urls.server_url,
+            api_url: urls.api_url,
+            workspace: var("GITHUB_WORKSPACE").map(PathBuf::from),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::urls::{DEFAULT_API_URL, DEFAULT_SERVER_URL};
+
+    #[test]
+    fn test_context_full() {
+        let context = GitHubContext::from_vars([
+            ("GITHUB_REPOSITORY", "42ByteLabs/ghactions"),
+            ("GITHUB_SHA", "0123456789abcdef"),
+            ("GITHUB_REF_NAME", "main"),
+            ("GITHUB_REF_TYPE", "branch"),
+            ("GITHUB_ACTOR", "geekmasher"),
+            ("GITHUB_EVENT_NAME", "push"),
+            ("GITHUB_RUN_ID", "9876543210"),
+            ("GITHUB_SERVER_URL", "https://github.example.com"),
+            ("GITHUB_API_URL", "https://github.example.com/api/v3"),
+            ("GITHUB_WORKSPACE", "/home/runner/work/ghactions/ghactions"),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            context,
+            GitHubContext {
+                repository: RepositoryReference {
+                    owner: "42ByteLabs".to_string(),
+                    name: "ghactions".to_string(),
+                    path: None,
+                    reference: None,
+                },
+                sha: Some("0123456789abcdef".to_string()),
+                ref_name: Some("main".to_string()),
+                ref_type: Some(RefType::Branch),
+                actor: Some("geekmasher".to_string()),
+                event_name: Some("push".to_string()),
+                run_id: Some(9876543210),
+                server_url: "https://github.example.com".to_string(),
+                api_url: "https://github.example.com/api/v3".to_string(),
+                workspace: Some(PathBuf::from("/home/runner/work/ghactions/ghactions")),
+            }
+        );
+
+        let json = serde_json::to_value(&context).unwrap();
+        assert_eq!(json["ref_type"], "branch");
+        assert_eq!(json["repository"]["owner"], "42ByteLabs");
+    }
+
+    #[test]
+    fn test_context_minimal() {
+        let context =
+            GitHubContext::from_vars([("GITHUB_REPOSITORY", "octo/action"), ("GITHUB_SHA", "")])
+                .unwrap();
+        assert_eq!(context.repository.owner, "octo");
+        assert_eq!(context.sha, None);
+        assert_eq!(context.ref_type, None);
+        assert_eq!(context.run_id, None);
+        assert_eq!(context.workspace, None);
+        assert_eq!(context.server_url, DEFAULT_SERVER_URL);
+        assert_eq!(context.api_url, DEFAULT_API_URL);
+    }
+
+    #[test]
+    fn test_context_errors() {
+        assert_eq!(
+            GitHubContext::from_vars([("GITHUB_SHA", "0123456789abcdef")]),
+            Err(ActionsError::InputError("GITHUB_REPOSITORY".to_string()))
+        );
+        assert_eq!(
+            GitHubContext::from_vars([
+                ("GITHUB_REPOSITORY", "octo/action"),
+                ("GITHUB_RUN_ID", "latest")
+            ]),
+            Err(ActionsError::InputTypeError(
+                "GITHUB_RUN_ID".to_string(),
+                "u64".to_string()
+            ))
+        );
+    }
+}
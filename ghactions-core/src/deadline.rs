@@ -0,0 +1,165 @@
+//! # Deadline
+//!
+//! Bound the total run time of an Action (tighter than the job `timeout-minutes`).
+//!
+//! When the deadline expires the registered cleanup hooks are run, an error annotation
+//! including the current phase is printed and the process exits with [DEADLINE_EXIT_CODE].
+//! The phase is set using [set_phase] (the `group!` macro sets it to the group name).
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use crate::ActionsError;
+
+/// Exit code used when the deadline expires (same as the coreutils `timeout` command)
+pub const DEADLINE_EXIT_CODE: i32 = 124;
+
+/// Phase used before any phase is set
+const DEFAULT_PHASE: &str = "main";
+
+static PHASE: RwLock<Option<String>> = RwLock::new(None);
+
+type CleanupHook = Box<dyn FnOnce() + Send>;
+static CLEANUP_HOOKS: Mutex<Vec<CleanupHook>> = Mutex::new(Vec::new());
+
+/// Set the current phase of the Action (reported when the deadline expires)
+pub fn set_phase(phase: impl Into<String>) {
+    *PHASE.write().unwrap_or_else(|err| err.into_inner()) = Some(phase.into());
+}
+
+/// Get the current phase of the Action
+pub fn current_phase() -> String {
+    PHASE
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone()
+        .unwrap_or_else(|| DEFAULT_PHASE.to_string())
+}
+
+/// Register a hook which is run before exiting when the deadline expires (or when the Action
+/// fails using [crate::exit::fail])
+pub fn add_cleanup_hook(hook: impl FnOnce() + Send + 'static) {
+    CLEANUP_HOOKS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .push(Box::new(hook));
+}
+
+/// Parse a duration (e.g. `90`, `30s`, `10m`, `1h30m` or `500ms`)
+///
+/// Numbers without a unit are seconds. Returns `None` if the value is invalid.
+///
+/// ```
+/// use std::time::Duration;
+/// use ghactions_core::deadline::parse_duration;
+///
+/// assert_eq!(parse_duration("10m"), Some(Duration::from_secs(600)));
+/// assert_eq!(parse_duration("1h30m"), Some(Duration::from_secs(5400)));
+/// assert_eq!(parse_duration("90"), Some(Duration::from_secs(90)));
+/// assert_eq!(parse_duration("ten minutes"), None);
+/// ```
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let number: u64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let unit = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        total += match &rest[..unit] {
+            "ms" => Duration::from_millis(number),
+            "s" => Duration::from_secs(number),
+            "m" => Duration::from_secs(number * 60),
+            "h" => Duration::from_secs(number * 60 * 60),
+            _ => return None,
+        };
+        rest = &rest[unit..];
+    }
+    Some(total)
+}
+
+/// Start the deadline using the value of an input
+///
+/// An empty or zero value disables the deadline.
+pub fn start_from_input(name: &str, value: &str) -> Result<(), ActionsError> {
+    if value.trim().is_empty() {
+        return Ok(());
+    }
+    let duration = parse_duration(value)
+        .ok_or_else(|| ActionsError::InputTypeError(name.to_string(), "duration".to_string()))?;
+    if !duration.is_zero() {
+        start(duration, value.trim());
+    }
+    Ok(())
+}
+
+/// Start the deadline, `label` is used in the error message (e.g. `10m`)
+pub fn start(duration: Duration, label: impl Into<String>) {
+    let label = label.into();
+    std::thread::spawn(move || {
+        std::thread::sleep(duration);
+        expire(&label);
+    });
+}
+
+/// Run the registered cleanup hooks (each hook is only run once)
+pub(crate) fn run_cleanup_hooks() {
+    let hooks: Vec<CleanupHook> = CLEANUP_HOOKS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .drain(..)
+        .collect();
+    for hook in hooks {
+        hook();
+    }
+}
+
+fn expire(label: &str) -> ! {
+    run_cleanup_hooks();
+
+    println!(
+        "::error::Action exceeded its {} timeout during phase `{}`",
+        label,
+        current_phase()
+    );
+    std::process::exit(DEADLINE_EXIT_CODE);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("0"), Some(Duration::ZERO));
+        assert_eq!(parse_duration("250ms"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_duration(" 2h "), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_duration("1m30s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("10"), Some(Duration::from_secs(10)));
+        assert_eq!(parse_duration("10d"), None);
+        assert_eq!(parse_duration("m"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn test_start_from_input() {
+        assert_eq!(start_from_input("timeout", ""), Ok(()));
+        assert_eq!(start_from_input("timeout", "0"), Ok(()));
+        assert_eq!(
+            start_from_input("timeout", "soon"),
+            Err(ActionsError::InputTypeError(
+                "timeout".to_string(),
+                "duration".to_string()
+            ))
+        );
+    }
+}
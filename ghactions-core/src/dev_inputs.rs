@@ -0,0 +1,187 @@
+//! # Dev Inputs
+//!
+//! Load the inputs from a local YAML (or JSON) file instead of exporting the `INPUT_*`
+//! environment variables when running the Action locally.
+//!
+//! The file is set using `GHACTIONS_INPUTS_FILE` or the `dev_inputs` attribute of the
+//! Action (e.g. `#[action(dev_inputs = "./inputs.local.yml")]`) and is a flat map of the
+//! input names to their values:
+//!
+//! ```yaml
+//! repository: 42ByteLabs/ghactions
+//! retries: 3
+//! dry-run: true
+//! ```
+//!
+//! The `INPUT_*` environment variables take precedence over the file. The file is ignored
+//! when running in GitHub Actions (`GITHUB_ACTIONS=true`).
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use crate::inputs::input_env_name;
+use crate::ActionsError;
+
+/// Environment variable of the inputs file (takes precedence over the `dev_inputs` attribute)
+pub const INPUTS_FILE_ENV: &str = "GHACTIONS_INPUTS_FILE";
+
+/// Inputs loaded from the inputs file (by environment variable name, e.g. `INPUT_RETRIES`)
+static DEV_INPUTS: RwLock<Option<HashMap<String, String>>> = RwLock::new(None);
+
+/// Load the inputs file (called by `init` before parsing the inputs)
+///
+/// `default_path` is the `dev_inputs` attribute of the Action. Missing files are ignored,
+/// files which are not a flat map of inputs are an error.
+pub fn load_dev_inputs(default_path: Option<&str>) -> Result<(), ActionsError> {
+    let inputs = match inputs_file(default_path) {
+        Some(path) => {
+            let content =
+                std::fs::read_to_string(&path).map_err(|err| ActionsError::FileError {
+                    path: path.clone(),
+                    kind: err.kind(),
+                    message: err.to_string(),
+                })?;
+            let inputs =
+                parse_inputs_file(&content).map_err(|message| ActionsError::FileError {
+                    path: path.clone(),
+                    kind: std::io::ErrorKind::InvalidData,
+                    message,
+                })?;
+            #[cfg(feature = "log")]
+            log::debug!("Loaded {} inputs from `{}`", inputs.len(), path.display());
+            Some(
+                inputs
+                    .into_iter()
+                    .map(|(name, value)| (input_env_name(&name), value))
+                    .collect(),
+            )
+        }
+        None => None,
+    };
+    *DEV_INPUTS.write().unwrap_or_else(|err| err.into_inner()) = inputs;
+    Ok(())
+}
+
+/// Get the inputs file to load (`None` in GitHub Actions or if the file does not exist)
+fn inputs_file(default_path: Option<&str>) -> Option<PathBuf> {
+    if crate::env::var("GITHUB_ACTIONS").as_deref() == Some("true") {
+        return None;
+    }
+    match crate::env::var(INPUTS_FILE_ENV).filter(|path| !path.is_empty()) {
+        Some(path) => {
+            let path = PathBuf::from(path);
+            if !path.is_file() {
+                #[cfg(feature = "log")]
+                log::warn!(
+                    "Inputs file `{}` (`{}`) does not exist",
+                    path.display(),
+                    INPUTS_FILE_ENV
+                );
+                return None;
+            }
+            Some(path)
+        }
+        None => default_path
+            .map(PathBuf::from)
+            .filter(|path| path.is_file()),
+    }
+}
+
+/// Parse an inputs file (a flat YAML or JSON map of input names to values)
+///
+/// Strings, numbers and booleans are used as-is, `null` is an empty value.
+///
+/// ```
+/// use ghactions_core::dev_inputs::parse_inputs_file;
+///
+/// assert_eq!(
+///     parse_inputs_file("repository: a/b\nretries: 3").unwrap(),
+///     vec![
+///         ("repository".to_string(), "a/b".to_string()),
+///         ("retries".to_string(), "3".to_string()),
+///     ]
+/// );
+/// assert!(parse_inputs_file("labels: [bug, docs]").is_err());
+/// ```
+pub fn parse_inputs_file(content: &str) -> Result<Vec<(String, String)>, String> {
+    let value: serde_yaml::Value = serde_yaml::from_str(content).map_err(|err| err.to_string())?;
+    let mapping = match value {
+        serde_yaml::Value::Null => return Ok(Vec::new()),
+        serde_yaml::Value::Mapping(mapping) => mapping,
+        _ => return Err("the inputs file must be a map of input names to values".to_string()),
+    };
+
+    mapping
+        .into_iter()
+        .map(|(name, value)| {
+            let name = match name {
+                serde_yaml::Value::String(name) => name,
+                name => return Err(format!("input name `{:?}` is not a string", name)),
+            };
+            let value = match value {
+                serde_yaml::Value::String(value) => value,
+                serde_yaml::Value::Number(value) => value.to_string(),
+                serde_yaml::Value::Bool(value) => value.to_string(),
+                serde_yaml::Value::Null => String::new(),
+                _ => {
+                    return Err(format!(
+                        "input `{}` must be a string, a number or a boolean",
+                        name
+                    ))
+                }
+            };
+            Ok((name, value))
+        })
+        .collect()
+}
+
+/// Get an input environment variable (e.g. `INPUT_RETRIES`), falling back to the inputs file
+pub fn input_var(env_name: &str) -> Option<String> {
+    crate::env::var(env_name).or_else(|| {
+        DEV_INPUTS
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .as_ref()
+            .and_then(|inputs| inputs.get(env_name).cloned())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_inputs_file() {
+        let content = "repository: a/b\nretries: 3\nratio: 0.5\ndry-run: true\nempty:\n";
+        assert_eq!(
+            parse_inputs_file(content).unwrap(),
+            vec![
+                ("repository".to_string(), "a/b".to_string()),
+                ("retries".to_string(), "3".to_string()),
+                ("ratio".to_string(), "0.5".to_string()),
+                ("dry-run".to_string(), "true".to_string()),
+                ("empty".to_string(), String::new()),
+            ]
+        );
+
+        // JSON is valid YAML
+        assert_eq!(
+            parse_inputs_file(r#"{"repository": "a/b", "retries": 3}"#).unwrap(),
+            vec![
+                ("repository".to_string(), "a/b".to_string()),
+                ("retries".to_string(), "3".to_string()),
+            ]
+        );
+        assert_eq!(parse_inputs_file("").unwrap(), vec![]);
+
+        assert_eq!(
+            parse_inputs_file("- a/b").unwrap_err(),
+            "the inputs file must be a map of input names to values"
+        );
+        assert_eq!(
+            parse_inputs_file("config:\n  nested: true").unwrap_err(),
+            "input `config` must be a string, a number or a boolean"
+        );
+        assert!(parse_inputs_file("repository: [a/b").is_err());
+    }
+}
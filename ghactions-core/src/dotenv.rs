@@ -0,0 +1,64 @@
+//! # Dotenv
+//!
+//! Load a dotenv file before parsing the Action (requires the `dotenvy` feature).
+//!
+//! The file is selected using (in order of precedence):
+//!
+//! 1. The `dotenv` attribute of the Action (e.g. `#[action(dotenv = ".env.local")]`)
+//! 2. The `GHACTIONS_DOTENV` environment variable
+//! 3. `./.env` (ignored if the file does not exist)
+//!
+//! `#[action(dotenv = false)]` disables dotenv for the Action. Variables already set in the
+//! environment are not overridden by the file.
+use std::path::PathBuf;
+
+use crate::ActionsError;
+
+/// Environment variable of the dotenv file (used when the Action does not set a file)
+pub const DOTENV_ENV: &str = "GHACTIONS_DOTENV";
+
+/// Dotenv file of an Action (set using the `dotenv` attribute)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DotenvSource<'a> {
+    /// `GHACTIONS_DOTENV` or `./.env`
+    #[default]
+    Default,
+    /// Explicit file (takes precedence over `GHACTIONS_DOTENV`)
+    File(&'a str),
+    /// Do not load a dotenv file
+    Disabled,
+}
+
+/// Load the dotenv file of an Action (called by `init` before parsing the inputs)
+///
+/// Returns the path of the loaded file. Failing to read a file set using the attribute or
+/// `GHACTIONS_DOTENV` is an [ActionsError::FailedLoading] error, a missing `./.env` is
+/// ignored.
+pub fn load_dotenv(source: DotenvSource<'_>) -> Result<Option<PathBuf>, ActionsError> {
+    let explicit = match source {
+        DotenvSource::Disabled => return Ok(None),
+        DotenvSource::File(path) => Some(path.to_string()),
+        DotenvSource::Default => crate::env::var(DOTENV_ENV).filter(|path| !path.is_empty()),
+    };
+
+    #[cfg(feature = "dotenvy")]
+    {
+        match explicit {
+            Some(path) => dotenvy::from_path(&path)
+                .map(|_| Some(PathBuf::from(&path)))
+                .map_err(|err| ActionsError::FailedLoading(format!("{}: {}", path, err))),
+            // The default file is optional
+            None => Ok(dotenvy::dotenv().ok()),
+        }
+    }
+    #[cfg(not(feature = "dotenvy"))]
+    {
+        match explicit {
+            Some(path) => Err(ActionsError::FailedLoading(format!(
+                "{}: the `dotenvy` feature is disabled",
+                path
+            ))),
+            None => Ok(None),
+        }
+    }
+}
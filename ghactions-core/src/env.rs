@@ -0,0 +1,136 @@
+//! # Environment
+//!
+//! Source of the environment variables read by the Action (the inputs, `GITHUB_EVENT_NAME`,
+//! the runner version, ...).
+//!
+//! The process environment is used by default. [with_env] reads the variables from an other
+//! [EnvSource] (e.g. a map) on the current thread, this is used by
+//! [crate::ActionTrait::init_from] so tests do not race on `std::env::set_var`.
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use ghactions_core::env::{var, with_env};
+//!
+//! let env = HashMap::from([("INPUT_NAME".to_string(), "ghactions".to_string())]);
+//! assert_eq!(with_env(env, || var("INPUT_NAME")), Some("ghactions".to_string()));
+//! ```
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+use std::rc::Rc;
+
+/// Source of environment variables
+pub trait EnvSource {
+    /// Get the value of a variable (`None` if the variable is not set)
+    fn var(&self, key: &str) -> Option<String>;
+}
+
+/// Environment of the process (the default source)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessEnv;
+
+impl EnvSource for ProcessEnv {
+    fn var(&self, key: &str) -> Option<String> {
+        std::env::var(key).ok()
+    }
+}
+
+impl<S: BuildHasher> EnvSource for HashMap<String, String, S> {
+    fn var(&self, key: &str) -> Option<String> {
+        self.get(key).cloned()
+    }
+}
+
+impl EnvSource for BTreeMap<String, String> {
+    fn var(&self, key: &str) -> Option<String> {
+        self.get(key).cloned()
+    }
+}
+
+thread_local! {
+    static SOURCE: RefCell<Option<Rc<dyn EnvSource>>> = RefCell::new(None);
+}
+
+/// Get the value of a variable from the source of the current thread
+///
+/// Uses the process environment outside of [with_env].
+pub fn var(key: &str) -> Option<String> {
+    match SOURCE.with(|source| source.borrow().clone()) {
+        Some(source) => source.var(key),
+        None => ProcessEnv.var(key),
+    }
+}
+
+/// Check if the variables are read from a [with_env] source on the current thread
+pub(crate) fn is_scoped() -> bool {
+    SOURCE.with(|source| source.borrow().is_some())
+}
+
+/// Run `f` reading the variables from `source` (on the current thread only)
+///
+/// The variables are only read from `source`, the process environment is not used as a
+/// fallback. The previous source is restored when `f` returns (or panics).
+pub fn with_env<T>(source: impl EnvSource + 'static, f: impl FnOnce() -> T) -> T {
+    let previous = SOURCE.with(|current| current.replace(Some(Rc::new(source))));
+    let _restore = RestoreSource(previous);
+    f()
+}
+
+/// Restore the previous source when dropped
+struct RestoreSource(Option<Rc<dyn EnvSource>>);
+
+impl Drop for RestoreSource {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        SOURCE.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> HashMap<String, String> {
+        vars.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_with_env() {
+        std::env::set_var("GHACTIONS_ENV_TEST", "process");
+        assert_eq!(var("GHACTIONS_ENV_TEST"), Some("process".to_string()));
+
+        with_env(env(&[("GHACTIONS_ENV_TEST", "outer")]), || {
+            assert_eq!(var("GHACTIONS_ENV_TEST"), Some("outer".to_string()));
+            // The process environment is not a fallback
+            assert_eq!(var("PATH"), None);
+
+            let nested = BTreeMap::from([("OTHER".to_string(), "nested".to_string())]);
+            with_env(nested, || {
+                assert_eq!(var("GHACTIONS_ENV_TEST"), None);
+                assert_eq!(var("OTHER"), Some("nested".to_string()));
+            });
+            assert_eq!(var("GHACTIONS_ENV_TEST"), Some("outer".to_string()));
+
+            // Other threads use the process environment
+            std::thread::spawn(|| {
+                assert_eq!(var("GHACTIONS_ENV_TEST"), Some("process".to_string()));
+            })
+            .join()
+            .unwrap();
+        });
+        assert_eq!(var("GHACTIONS_ENV_TEST"), Some("process".to_string()));
+        std::env::remove_var("GHACTIONS_ENV_TEST");
+    }
+
+    #[test]
+    fn test_with_env_panic() {
+        let result = std::panic::catch_unwind(|| {
+            with_env(env(&[("GHACTIONS_ENV_PANIC", "set")]), || panic!("failed"))
+        });
+        assert!(result.is_err());
+        assert_eq!(var("GHACTIONS_ENV_PANIC"), None);
+        assert!(SOURCE.with(|source| source.borrow().is_none()));
+    }
+}
@@ -0,0 +1,392 @@
+//! # Environment Files
+//!
+//! Paths of the files the runner uses to communicate with the Action
+//! (`GITHUB_OUTPUT`, `GITHUB_ENV`, `GITHUB_PATH`, `GITHUB_STATE` and `GITHUB_STEP_SUMMARY`).
+//!
+//! The paths are resolved once and cached as they don't change while the Action runs.
+//! Tests which change the environment variables need to call [reset_paths_for_testing].
+//!
+//! Outputs are written using an [OutputWriter] which can prefix the output names so the
+//! same binary can be run multiple times in one step (see [OUTPUT_PREFIX_ENV]).
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+
+use crate::ActionsError;
+
+/// Fallback output file when `GITHUB_OUTPUT` is not set (e.g. running locally)
+const OUTPUT_FALLBACK: &str = "/tmp/github_actions.env";
+
+/// Fallback environment file when `GITHUB_ENV` is not set (e.g. running locally)
+const ENV_FALLBACK: &str = "/tmp/github_actions_exports.env";
+
+/// Environment variable used to prefix all output names (e.g. `plan-`)
+pub const OUTPUT_PREFIX_ENV: &str = "GHACTIONS_OUTPUT_PREFIX";
+
+/// Cached environment files (environment variable name and resolved path)
+static PATHS: RwLock<Vec<(&'static str, Option<PathBuf>)>> = RwLock::new(Vec::new());
+/// Serialises the writes to the environment files (see [append])
+static APPEND_LOCK: Mutex<()> = Mutex::new(());
+
+/// Get the (cached) path of an environment file from its environment variable
+///
+/// The variable is read using [crate::env::var], paths read from a [crate::env::with_env]
+/// source are not cached.
+fn env_file(name: &'static str) -> Option<PathBuf> {
+    let path = || {
+        crate::env::var(name)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+    };
+    if crate::env::is_scoped() {
+        return path();
+    }
+    if let Some((_, path)) = PATHS
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .iter()
+        .find(|(key, _)| *key == name)
+    {
+        return path.clone();
+    }
+
+    let path = path();
+    PATHS
+        .write()
+        .unwrap_or_else(|err| err.into_inner())
+        .push((name, path.clone()));
+    path
+}
+
+/// Get the path of the output file (`GITHUB_OUTPUT`)
+///
+/// Falls back to `/tmp/github_actions.env` when not running in GitHub Actions.
+pub fn get_output_path() -> PathBuf {
+    env_file("GITHUB_OUTPUT").unwrap_or_else(|| PathBuf::from(OUTPUT_FALLBACK))
+}
+
+/// Get the path of the environment file (`GITHUB_ENV`)
+pub fn get_env_path() -> Option<PathBuf> {
+    env_file("GITHUB_ENV")
+}
+
+/// Get the path of the system path file (`GITHUB_PATH`)
+pub fn get_path_file() -> Option<PathBuf> {
+    env_file("GITHUB_PATH")
+}
+
+/// Get the path of the state file (`GITHUB_STATE`)
+pub fn get_state_path() -> Option<PathBuf> {
+    env_file("GITHUB_STATE")
+}
+
+/// Get the path of the job summary file (`GITHUB_STEP_SUMMARY`)
+pub fn get_step_summary_path() -> Option<PathBuf> {
+    env_file("GITHUB_STEP_SUMMARY")
+}
+
+/// Clear the cached paths so they are resolved from the environment again
+///
+/// Only needed by tests which change the environment variables between runs.
+pub fn reset_paths_for_testing() {
+    PATHS.write().unwrap_or_else(|err| err.into_inner()).clear();
+}
+
+/// Format an output using the `GITHUB_OUTPUT` file format
+///
+/// Multi-line values use the heredoc (`name<<delimiter`) format.
+///
+/// ```
+/// use ghactions_core::env_files::format_output;
+///
+/// assert_eq!(format_output("count", "42"), "count=42\n");
+/// assert!(format_output("notes", "a\nb").starts_with("notes<<ghadelimiter_"));
+/// ```
+pub fn format_output(key: &str, value: &str) -> String {
+    if !value.contains('\n') && !value.contains('\r') {
+        return format!("{}={}\n", key, value);
+    }
+
+    // The delimiter must not be part of the value
+    let mut seed = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or_default();
+    let mut delimiter = format!("ghadelimiter_{}", seed);
+    while value.contains(&delimiter) {
+        seed += 1;
+        delimiter = format!("ghadelimiter_{}", seed);
+    }
+    format!("{}<<{}\n{}\n{}\n", key, delimiter, value, delimiter)
+}
+
+/// Serialize an output value as (pretty printed) JSON
+///
+/// The JSON spans multiple lines so it is written using the heredoc format (see
+/// [format_output]).
+///
+/// ```
+/// use ghactions_core::env_files::to_json_output;
+///
+/// assert_eq!(to_json_output(&vec![1, 2]).unwrap(), "[\n  1,\n  2\n]");
+/// ```
+pub fn to_json_output<T: serde::Serialize + ?Sized>(value: &T) -> Result<String, ActionsError> {
+    serde_json::to_string_pretty(value)
+        .map_err(|err| ActionsError::SerializationError(err.to_string()))
+}
+
+/// Export an environment variable for the following steps of the job (`GITHUB_ENV`)
+///
+/// Multi-line values use the heredoc format. The name can not be empty or contain `=`
+/// or newlines, and the variable is also set for the current process. Falls back to
+/// `/tmp/github_actions_exports.env` when not running in GitHub Actions.
+pub fn set_env(key: &str, value: &str) -> Result<(), ActionsError> {
+    if key.is_empty() || key.contains(['=', '\n', '\r']) {
+        return Err(ActionsError::EnvNameError(key.to_string()));
+    }
+    crate::safety::check_untrusted("set_env", &format!("{}={}", key, value))?;
+
+    let path = get_env_path().unwrap_or_else(|| PathBuf::from(ENV_FALLBACK));
+    append(&path, &format_output(key, value))?;
+    std::env::set_var(key, value);
+    Ok(())
+}
+
+/// Save a value for the `post` step of the Action (`GITHUB_STATE`)
+///
+/// The runner passes the value to the `post` step as the `STATE_<key>` environment
+/// variable (see [get_state]). Multi-line values use the heredoc format.
+pub fn save_state(key: &str, value: &str) -> Result<(), ActionsError> {
+    if key.is_empty() || key.contains(['=', '\n', '\r']) {
+        return Err(ActionsError::EnvNameError(key.to_string()));
+    }
+    match get_state_path() {
+        Some(path) => append(&path, &format_output(key, value)),
+        None => {
+            #[cfg(feature = "log")]
+            log::debug!("`GITHUB_STATE` is not set, state `{}` is not saved", key);
+            Ok(())
+        }
+    }
+}
+
+/// Get a value saved by the main step of the Action (see [save_state])
+pub fn get_state(key: &str) -> Option<String> {
+    crate::env::var(&format!("STATE_{}", key))
+}
+
+/// Parse the content of an environment file (e.g. to check the values written by an Action)
+///
+/// Supports `name=value` lines and the heredoc (`name<<delimiter`) format.
+///
+/// ```
+/// use ghactions_core::env_files::{format_output, parse_env_file};
+///
+/// let content = format!("{}{}", format_output("count", "42"), format_output("notes", "a\nb"));
+/// assert_eq!(
+///     parse_env_file(&content),
+///     vec![
+///         ("count".to_string(), "42".to_string()),
+///         ("notes".to_string(), "a\nb".to_string())
+///     ]
+/// );
+/// ```
+pub fn parse_env_file(content: &str) -> Vec<(String, String)> {
+    let mut values = Vec::new();
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        if let Some((key, delimiter)) = line.split_once("<<") {
+            let value: Vec<&str> = lines
+                .by_ref()
+                .take_while(|line| *line != delimiter)
+                .collect();
+            values.push((key.to_string(), value.join("\n")));
+        } else if let Some((key, value)) = line.split_once('=') {
+            values.push((key.to_string(), value.to_string()));
+        }
+    }
+    values
+}
+
+/// Prepend a directory to the `PATH` of the following steps of the job (`GITHUB_PATH`)
+///
+/// Relative paths are resolved from the current directory. The `PATH` of the current
+/// process is also updated so the Action can run the tools it installed. When not
+/// running in GitHub Actions only the process `PATH` is updated.
+pub fn add_path(path: impl AsRef<Path>) -> Result<(), ActionsError> {
+    let path = match path.as_ref().is_absolute() {
+        true => path.as_ref().to_path_buf(),
+        false => std::env::current_dir()?.join(path),
+    };
+    let display = path.display().to_string();
+    crate::safety::check_untrusted("add_path", &display)?;
+
+    match get_path_file() {
+        Some(file) => append(&file, &format!("{}\n", display))?,
+        None => {
+            #[cfg(feature = "log")]
+            log::debug!(
+                "`GITHUB_PATH` is not set, only adding `{}` to the process PATH",
+                display
+            );
+        }
+    }
+
+    let current = std::env::var_os("PATH").unwrap_or_default();
+    let paths = std::iter::once(path).chain(std::env::split_paths(&current));
+    let joined = std::env::join_paths(paths).map_err(|err| {
+        ActionsError::IOError(std::io::Error::new(std::io::ErrorKind::InvalidInput, err))
+    })?;
+    std::env::set_var("PATH", joined);
+    Ok(())
+}
+
+/// Append content to an environment file (creating it if needed)
+///
+/// Records are written using a single `write_all` while holding [APPEND_LOCK] so records
+/// written by multiple threads are never interleaved.
+fn append(path: &PathBuf, content: &str) -> Result<(), ActionsError> {
+    let _lock = APPEND_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|err| ActionsError::FileError {
+            path: path.clone(),
+            kind: err.kind(),
+            message: err.to_string(),
+        })
+}
+
+/// Write multiple outputs to the output file (`GITHUB_OUTPUT`) at once
+///
+/// Uses the output prefix from the environment (see [OutputWriter::new]).
+pub fn write_outputs<K: AsRef<str>, V: AsRef<str>>(outputs: &[(K, V)]) -> Result<(), ActionsError> {
+    OutputWriter::new().write_all(outputs)
+}
+
+/// Output Writer prefixing the output names
+///
+/// ```
+/// use ghactions_core::env_files::OutputWriter;
+///
+/// let writer = OutputWriter::with_prefix("plan-");
+/// assert_eq!(writer.key("version"), "plan-version");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutputWriter {
+    prefix: String,
+}
+
+impl OutputWriter {
+    /// Create an Output Writer using the prefix from `GHACTIONS_OUTPUT_PREFIX` (if set)
+    pub fn new() -> Self {
+        Self::with_prefix(crate::env::var(OUTPUT_PREFIX_ENV).unwrap_or_default())
+    }
+
+    /// Create an Output Writer using a prefix
+    pub fn with_prefix(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    /// Get the output name including the prefix
+    pub fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    /// Write an output
+    ///
+    /// Older runners which don't support the output file use the (escaped) `::set-output`
+    /// command (see [crate::commands::set_output_command]).
+    pub fn set(&self, key: &str, value: &str) -> Result<(), ActionsError> {
+        if crate::runner::runner_supports_output_files() {
+            self.write_all(&[(key, value)])
+        } else {
+            println!(
+                "{}",
+                crate::commands::set_output_command(&self.key(key), value)
+            );
+            Ok(())
+        }
+    }
+
+    /// Write multiple outputs to the output file at once
+    pub fn write_all<K: AsRef<str>, V: AsRef<str>>(
+        &self,
+        outputs: &[(K, V)],
+    ) -> Result<(), ActionsError> {
+        let content: String = outputs
+            .iter()
+            .map(|(key, value)| format_output(&self.key(key.as_ref()), value.as_ref()))
+            .collect();
+
+        append(&get_output_path(), &content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths_cached_until_reset() {
+        std::env::set_var("GITHUB_STEP_SUMMARY", "/tmp/first-summary.md");
+        reset_paths_for_testing();
+        assert_eq!(
+            get_step_summary_path(),
+            Some(PathBuf::from("/tmp/first-summary.md"))
+        );
+
+        // Cached value is used until the paths are reset
+        std::env::set_var("GITHUB_STEP_SUMMARY", "/tmp/second-summary.md");
+        assert_eq!(
+            get_step_summary_path(),
+            Some(PathBuf::from("/tmp/first-summary.md"))
+        );
+
+        reset_paths_for_testing();
+        assert_eq!(
+            get_step_summary_path(),
+            Some(PathBuf::from("/tmp/second-summary.md"))
+        );
+
+        // Paths of a scoped source are read from it and not cached
+        let env = std::collections::HashMap::from([(
+            "GITHUB_STEP_SUMMARY".to_string(),
+            "/tmp/scoped-summary.md".to_string(),
+        )]);
+        assert_eq!(
+            crate::env::with_env(env, get_step_summary_path),
+            Some(PathBuf::from("/tmp/scoped-summary.md"))
+        );
+        assert_eq!(
+            get_step_summary_path(),
+            Some(PathBuf::from("/tmp/second-summary.md"))
+        );
+
+        std::env::remove_var("GITHUB_STEP_SUMMARY");
+        reset_paths_for_testing();
+        assert_eq!(get_step_summary_path(), None);
+    }
+
+    #[test]
+    fn test_output_prefix_scoped() {
+        let env =
+            std::collections::HashMap::from([(OUTPUT_PREFIX_ENV.to_string(), "plan-".to_string())]);
+        let writer = crate::env::with_env(env, OutputWriter::new);
+        assert_eq!(writer.key("version"), "plan-version");
+    }
+
+    #[test]
+    fn test_set_env_invalid_names() {
+        for key in ["", "KEY=VALUE", "KEY\nOTHER", "KEY\r"] {
+            assert_eq!(
+                set_env(key, "value"),
+                Err(ActionsError::EnvNameError(key.to_string()))
+            );
+        }
+    }
+}
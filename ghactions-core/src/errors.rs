@@ -1,8 +1,24 @@
 //! Errors for the core library
+//!
+//! # Migrating from `0.10`
+//!
+//! The underlying errors are kept as the [std::error::Error::source] of the error instead
+//! of being converted to strings:
+//!
+//! - `IOError(String)` is now `IOError(std::io::Error)`
+//! - YAML errors are reported as `YamlError(serde_yaml::Error)` (previously `IOError`)
+//! - `OctocrabError(String)` is now `OctocrabError(Box<octocrab::Error>)`
+//!
+//! Use [ActionsError::is_not_found] / [ActionsError::is_permission_denied] instead of
+//! matching on the error message.
+use std::path::PathBuf;
 use thiserror::Error;
 
+use crate::actions::diff::SchemaMismatch;
+use crate::actions::validate::ValidationIssue;
+
 /// Actions Error
-#[derive(Error, Debug, PartialEq)]
+#[derive(Error, Debug)]
 pub enum ActionsError {
     /// Failed to load the environment
     #[error("Failed to load environment: `{0}`")]
@@ -12,14 +28,56 @@ pub enum ActionsError {
     #[error("Failed to get input value: `{0}`")]
     InputError(String),
 
+    /// Required input is missing (the Action input name and description)
+    #[error(
+        "Missing required input '{input}'{} \u{2014} set it under `with:` in your workflow",
+        .description.as_ref().map(|description| format!(" ({})", description)).unwrap_or_default()
+    )]
+    MissingInput {
+        /// Action input name
+        input: String,
+        /// Input description
+        description: Option<String>,
+    },
+
     /// Input Type Error
     #[error("Input Type Error: `{0}` (Expected: `{1}`)")]
     InputTypeError(String, String),
 
+    /// Input value could not be parsed (see `FromInput`)
+    #[error("Input `{0}` is invalid: {1}")]
+    InputParseError(String, String),
+
+    /// Input value does not match the required pattern
+    #[error("Input `{0}` value `{1}` does not match pattern `{2}`")]
+    InputPatternError(String, String, String),
+
+    /// Input value is not one of the allowed values (input, value, allowed values)
+    #[error("Input `{0}` value `{1}` is not one of: {}", .2.join(", "))]
+    InputAllowedError(String, String, Vec<String>),
+
+    /// Invalid output name
+    #[error("Invalid output name: `{0}` (must start with a letter or `_` and only contain alphanumeric characters, `-` or `_`)")]
+    OutputNameError(String),
+
+    /// Invalid environment variable name
+    #[error(
+        "Invalid environment variable name: `{0}` (must not be empty or contain `=` or newlines)"
+    )]
+    EnvNameError(String),
+
+    /// Multiple Errors (one per line)
+    #[error("Multiple errors:\n{}", .0.iter().map(|err| format!(" - {}", err)).collect::<Vec<String>>().join("\n"))]
+    MultipleErrors(Vec<ActionsError>),
+
+    /// Operation blocked by the safety policy (see `ActionTrait::assert_safe_context`)
+    #[error("Blocked by safety policy: {0}")]
+    UnsafeContext(String),
+
     /// Octocrab Error
     #[cfg(feature = "octocrab")]
     #[error("Octocrab Error: `{0}`")]
-    OctocrabError(String),
+    OctocrabError(#[source] Box<octocrab::Error>),
 
     /// Failed parsing the repository reference
     #[error("Unable to parse repo reference: `{0}`")]
@@ -27,9 +85,332 @@ pub enum ActionsError {
 
     /// IO Error
     #[error("IO Error: `{0}`")]
-    IOError(String),
+    IOError(#[from] std::io::Error),
+
+    /// YAML (de)serialization Error
+    #[error("YAML Error: `{0}`")]
+    YamlError(#[from] serde_yaml::Error),
+
+    /// Report parsing Error (see [crate::reports])
+    #[cfg(feature = "reports")]
+    #[error("Report Error: `{0}`")]
+    ReportError(#[from] quick_xml::Error),
+
+    /// Action file does not match the schema the code expects
+    #[error("Action file mismatch:\n{}", crate::actions::diff::render_text(.0))]
+    ActionFileMismatch(Vec<SchemaMismatch>),
+
+    /// Action built using [crate::ActionYML::builder] is not valid
+    #[error("Invalid Action:\n{}", .0.iter().map(|issue| format!(" - {}", issue)).collect::<Vec<String>>().join("\n"))]
+    InvalidAction(Vec<ValidationIssue>),
+
+    /// Workflow event is not supported by the Action
+    #[error("Unsupported event: `{got}` (supported: {})", .supported.join(", "))]
+    UnsupportedEvent {
+        /// Event name the workflow was triggered by
+        got: String,
+        /// Events the Action supports
+        supported: Vec<String>,
+    },
+
+    /// Workflow event is not supported and the Action is skipped (`events_mode = "skip"`)
+    ///
+    /// [crate::exit::run] and [crate::exit::run_blocking] exit successfully on this error,
+    /// other callers check `should_skip()` before `init()`.
+    #[error("Skipping Action, event `{got}` is not supported (supported: {})", .supported.join(", "))]
+    SkippedEvent {
+        /// Event name the workflow was triggered by
+        got: String,
+        /// Events the Action supports
+        supported: Vec<String>,
+    },
+
+    /// Runner is older than the version the Action requires
+    #[error("Runner version `{found}` is not supported (requires `{required}` or newer), please update the runner")]
+    RunnerVersionError {
+        /// Version of the runner
+        found: String,
+        /// Minimum version the Action requires
+        required: String,
+    },
+
+    /// File Error (with the underlying IO error kind)
+    #[error("File Error: `{}` ({message})", .path.display())]
+    FileError {
+        /// Path of the file
+        path: PathBuf,
+        /// IO Error kind
+        kind: std::io::ErrorKind,
+        /// Error message
+        message: String,
+    },
+
+    /// Refusing to write through a symlink
+    #[error("Refusing to write through symlink: `{}` (resolves to `{}`)", .path.display(), .target.display())]
+    SymlinkError {
+        /// Path of the symlink
+        path: PathBuf,
+        /// Resolved target of the symlink
+        target: PathBuf,
+    },
+
+    /// Event payload is not available (`GITHUB_EVENT_PATH` is not set)
+    #[error("Event payload is not available: `GITHUB_EVENT_PATH` is not set")]
+    EventPathMissing,
+
+    /// Event payload is not valid JSON (or does not match the requested type)
+    #[error("Invalid event payload `{}`: {message}", .path.display())]
+    EventPayloadError {
+        /// Path of the event payload
+        path: PathBuf,
+        /// Error message
+        message: String,
+    },
+
+    /// Job summary file is not available (`GITHUB_STEP_SUMMARY` is not set)
+    #[error("Job summary is not available: `GITHUB_STEP_SUMMARY` is not set")]
+    SummaryUnavailable,
+
+    /// Job summary template uses a variable without a value
+    #[error("Summary template `{}` uses the undefined variable `{name}`", .path.display())]
+    SummaryTemplateError {
+        /// Template file
+        path: PathBuf,
+        /// Name of the variable
+        name: String,
+    },
+
+    /// Failed to serialize a value (e.g. an output as JSON)
+    #[error("Serialization Error: `{0}`")]
+    SerializationError(String),
+
+    /// OIDC token can not be requested (the request environment variable is not set)
+    #[error("OIDC token is not available: `{0}` is not set (the workflow needs `permissions: id-token: write`)")]
+    IdTokenUnavailable(&'static str),
+
+    /// Failed to request the OIDC token
+    #[error("Failed to get the OIDC token: {0}")]
+    IdTokenError(String),
+
+    /// Failed to upload an artifact
+    #[error("Artifact Error: {0}")]
+    ArtifactError(String),
+
+    /// An artifact with the same name already exists in the workflow run
+    #[error("An artifact named `{0}` already exists in the workflow run")]
+    ArtifactConflict(String),
+
+    /// The artifact storage quota of the account has been hit
+    #[error("Artifact storage quota has been hit, unable to upload new artifacts")]
+    ArtifactQuotaExceeded,
+
+    /// Invalid proxy settings (`https_proxy` / `http_proxy`) or the proxy refused the tunnel
+    #[error("Proxy Error: {0}")]
+    ProxyError(String),
+
+    /// The GraphQL API returned errors (or an invalid response)
+    #[error("GraphQL Error:\n{}", .0.iter().map(|err| format!(" - {}", err)).collect::<Vec<String>>().join("\n"))]
+    GraphQLError(Vec<String>),
 
     /// Not Implemented
     #[error("Not Implemented")]
     NotImplemented,
 }
+
+#[cfg(feature = "octocrab")]
+impl From<octocrab::Error> for ActionsError {
+    fn from(err: octocrab::Error) -> Self {
+        ActionsError::OctocrabError(Box::new(err))
+    }
+}
+
+impl ActionsError {
+    /// Check if the error is caused by a missing file (or directory)
+    pub fn is_not_found(&self) -> bool {
+        self.io_error_kind() == Some(std::io::ErrorKind::NotFound)
+    }
+
+    /// Check if the error is caused by missing permissions (or a path the Action is not
+    /// allowed to use)
+    pub fn is_permission_denied(&self) -> bool {
+        self.io_error_kind() == Some(std::io::ErrorKind::PermissionDenied)
+    }
+
+    fn io_error_kind(&self) -> Option<std::io::ErrorKind> {
+        match self {
+            ActionsError::IOError(err) => Some(err.kind()),
+            ActionsError::FileError { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
+}
+
+/// Errors are compared by their values, source errors which do not implement `PartialEq`
+/// are compared by their kind (IO errors) and message
+impl PartialEq for ActionsError {
+    fn eq(&self, other: &Self) -> bool {
+        use ActionsError::*;
+        match (self, other) {
+            (FailedLoading(a), FailedLoading(b)) => a == b,
+            (InputError(a), InputError(b)) => a == b,
+            (
+                MissingInput {
+                    input: a_input,
+                    description: a_description,
+                },
+                MissingInput {
+                    input: b_input,
+                    description: b_description,
+                },
+            ) => a_input == b_input && a_description == b_description,
+            (InputTypeError(a1, a2), InputTypeError(b1, b2)) => a1 == b1 && a2 == b2,
+            (InputParseError(a1, a2), InputParseError(b1, b2)) => a1 == b1 && a2 == b2,
+            (InputPatternError(a1, a2, a3), InputPatternError(b1, b2, b3)) => {
+                a1 == b1 && a2 == b2 && a3 == b3
+            }
+            (InputAllowedError(a1, a2, a3), InputAllowedError(b1, b2, b3)) => {
+                a1 == b1 && a2 == b2 && a3 == b3
+            }
+            (OutputNameError(a), OutputNameError(b)) => a == b,
+            (EnvNameError(a), EnvNameError(b)) => a == b,
+            (MultipleErrors(a), MultipleErrors(b)) => a == b,
+            (UnsafeContext(a), UnsafeContext(b)) => a == b,
+            #[cfg(feature = "octocrab")]
+            (OctocrabError(a), OctocrabError(b)) => a.to_string() == b.to_string(),
+            (RepositoryReferenceError(a), RepositoryReferenceError(b)) => a == b,
+            (IOError(a), IOError(b)) => a.kind() == b.kind() && a.to_string() == b.to_string(),
+            (YamlError(a), YamlError(b)) => a.to_string() == b.to_string(),
+            #[cfg(feature = "reports")]
+            (ReportError(a), ReportError(b)) => a.to_string() == b.to_string(),
+            (ActionFileMismatch(a), ActionFileMismatch(b)) => a == b,
+            (InvalidAction(a), InvalidAction(b)) => a == b,
+            (
+                UnsupportedEvent {
+                    got: a_got,
+                    supported: a_supported,
+                },
+                UnsupportedEvent {
+                    got: b_got,
+                    supported: b_supported,
+                },
+            ) => a_got == b_got && a_supported == b_supported,
+            (
+                SkippedEvent {
+                    got: a_got,
+                    supported: a_supported,
+                },
+                SkippedEvent {
+                    got: b_got,
+                    supported: b_supported,
+                },
+            ) => a_got == b_got && a_supported == b_supported,
+            (
+                RunnerVersionError {
+                    found: a_found,
+                    required: a_required,
+                },
+                RunnerVersionError {
+                    found: b_found,
+                    required: b_required,
+                },
+            ) => a_found == b_found && a_required == b_required,
+            (
+                FileError {
+                    path: a_path,
+                    kind: a_kind,
+                    message: a_message,
+                },
+                FileError {
+                    path: b_path,
+                    kind: b_kind,
+                    message: b_message,
+                },
+            ) => a_path == b_path && a_kind == b_kind && a_message == b_message,
+            (
+                SymlinkError {
+                    path: a_path,
+                    target: a_target,
+                },
+                SymlinkError {
+                    path: b_path,
+                    target: b_target,
+                },
+            ) => a_path == b_path && a_target == b_target,
+            (EventPathMissing, EventPathMissing) => true,
+            (
+                EventPayloadError {
+                    path: a_path,
+                    message: a_message,
+                },
+                EventPayloadError {
+                    path: b_path,
+                    message: b_message,
+                },
+            ) => a_path == b_path && a_message == b_message,
+            (SummaryUnavailable, SummaryUnavailable) => true,
+            (
+                SummaryTemplateError {
+                    path: a_path,
+                    name: a_name,
+                },
+                SummaryTemplateError {
+                    path: b_path,
+                    name: b_name,
+                },
+            ) => a_path == b_path && a_name == b_name,
+            (SerializationError(a), SerializationError(b)) => a == b,
+            (ProxyError(a), ProxyError(b)) => a == b,
+            (GraphQLError(a), GraphQLError(b)) => a == b,
+            (IdTokenUnavailable(a), IdTokenUnavailable(b)) => a == b,
+            (IdTokenError(a), IdTokenError(b)) => a == b,
+            (ArtifactError(a), ArtifactError(b)) => a == b,
+            (ArtifactConflict(a), ArtifactConflict(b)) => a == b,
+            (ArtifactQuotaExceeded, ArtifactQuotaExceeded) => true,
+            (NotImplemented, NotImplemented) => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_source_chain() {
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "action.yml");
+        let err = ActionsError::from(io);
+        assert_eq!(err.to_string(), "IO Error: `action.yml`");
+        let source = err.source().unwrap().downcast_ref::<std::io::Error>();
+        assert_eq!(
+            source.map(|io| io.kind()),
+            Some(std::io::ErrorKind::NotFound)
+        );
+
+        let yaml = serde_yaml::from_str::<crate::ActionYML>("runs: [").unwrap_err();
+        let err = ActionsError::from(yaml);
+        assert!(err
+            .source()
+            .unwrap()
+            .downcast_ref::<serde_yaml::Error>()
+            .is_some());
+        assert!(ActionsError::NotImplemented.source().is_none());
+    }
+
+    #[test]
+    fn test_io_predicates() {
+        let not_found = ActionsError::from(std::io::Error::from(std::io::ErrorKind::NotFound));
+        assert!(not_found.is_not_found());
+        assert!(!not_found.is_permission_denied());
+
+        let denied = ActionsError::FileError {
+            path: PathBuf::from("action.yml"),
+            kind: std::io::ErrorKind::PermissionDenied,
+            message: "denied".to_string(),
+        };
+        assert!(denied.is_permission_denied());
+        assert!(!denied.is_not_found());
+        assert!(!ActionsError::NotImplemented.is_not_found());
+    }
+}
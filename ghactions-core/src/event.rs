@@ -0,0 +1,38 @@
+//! # Event
+//!
+//! Webhook event payload of the workflow run (the file at `GITHUB_EVENT_PATH`).
+//!
+//! https://docs.github.com/en/webhooks/webhook-events-and-payloads
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
+
+use crate::ActionsError;
+
+/// Get the path of the event payload file (`GITHUB_EVENT_PATH`)
+pub fn get_event_path() -> Result<PathBuf, ActionsError> {
+    std::env::var("GITHUB_EVENT_PATH")
+        .ok()
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .ok_or(ActionsError::EventPathMissing)
+}
+
+/// Read the event payload as JSON
+pub fn get_event_payload() -> Result<serde_json::Value, ActionsError> {
+    get_event()
+}
+
+/// Read the event payload into a type (e.g. a struct with the fields the Action uses)
+pub fn get_event<T: DeserializeOwned>() -> Result<T, ActionsError> {
+    let path = get_event_path()?;
+    let content = std::fs::read_to_string(&path).map_err(|err| ActionsError::FileError {
+        path: path.clone(),
+        kind: err.kind(),
+        message: format!("failed to read the event payload: {}", err),
+    })?;
+    serde_json::from_str(&content).map_err(|err| ActionsError::EventPayloadError {
+        path,
+        message: err.to_string(),
+    })
+}
@@ -0,0 +1,243 @@
+//! # Exit
+//!
+//! Fail the Action with an error annotation and a non-zero exit code.
+//!
+//! [run] and [run_blocking] wrap the `main` of an Action: the Action is parsed using
+//! [ActionTrait::init], the closure is run and an error (of the parsing or of the closure)
+//! fails the Action using [fail_with_error]. An Action skipped as the workflow event is not
+//! supported ([ActionsError::SkippedEvent]) exits successfully without running `main`.
+//!
+//! ```no_run
+//! use ghactions_core::exit::run_blocking;
+//! # use ghactions_core::{ActionTrait, ActionsError};
+//! # struct MyAction;
+//! # impl ActionTrait for MyAction {
+//! #     fn init() -> Result<Self, ActionsError> { Ok(MyAction) }
+//! #     fn name(&self) -> &str { "My Action" }
+//! #     fn description(&self) -> &str { "" }
+//! # }
+//!
+//! fn main() {
+//!     run_blocking(|action: MyAction| {
+//!         println!("Running {}", action.name());
+//!         Ok::<(), ActionsError>(())
+//!     });
+//! }
+//! ```
+use std::error::Error;
+use std::future::Future;
+use std::io::Write;
+
+use crate::commands::{Annotation, AnnotationLevel};
+use crate::{ActionTrait, ActionsError};
+
+/// Exit code used when the Action fails
+pub const FAILURE_EXIT_CODE: i32 = 1;
+
+/// Fail the Action: print an error annotation and exit with [FAILURE_EXIT_CODE]
+///
+/// The cleanup hooks (see [crate::deadline::add_cleanup_hook]) are run and stdout / stderr
+/// are flushed before exiting. Write the job summary and the outputs before failing.
+pub fn fail(message: impl std::fmt::Display) -> ! {
+    exit(&[Annotation::new(AnnotationLevel::Error, message.to_string())])
+}
+
+/// Fail the Action with an error (see [fail] and [error_annotations])
+pub fn fail_with_error(error: impl Into<Box<dyn Error>>) -> ! {
+    exit(&error_annotations(error.into().as_ref()))
+}
+
+/// Parse the Action, run `main` and fail the Action if an error is returned
+///
+/// ```no_run
+/// # use ghactions_core::{ActionTrait, ActionsError};
+/// # struct MyAction;
+/// # impl ActionTrait for MyAction {
+/// #     fn init() -> Result<Self, ActionsError> { Ok(MyAction) }
+/// #     fn name(&self) -> &str { "My Action" }
+/// #     fn description(&self) -> &str { "" }
+/// # }
+/// #[tokio::main]
+/// async fn main() {
+///     ghactions_core::exit::run(|action: MyAction| async move {
+///         println!("Running {}", action.name());
+///         Ok::<(), ActionsError>(())
+///     })
+///     .await;
+/// }
+/// ```
+pub async fn run<A, F, Fut, T, E>(main: F) -> T
+where
+    A: ActionTrait,
+    F: FnOnce(A) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: Into<Box<dyn Error>>,
+{
+    let action = init::<A>();
+    main(action)
+        .await
+        .unwrap_or_else(|err| fail_with_error(err))
+}
+
+/// Parse the Action, run `main` and fail the Action if an error is returned (see [run])
+pub fn run_blocking<A, F, T, E>(main: F) -> T
+where
+    A: ActionTrait,
+    F: FnOnce(A) -> Result<T, E>,
+    E: Into<Box<dyn Error>>,
+{
+    let action = init::<A>();
+    main(action).unwrap_or_else(|err| fail_with_error(err))
+}
+
+/// Parse the Action, exit successfully if it is skipped and fail if the parsing fails
+fn init<A: ActionTrait>() -> A {
+    match A::init() {
+        Ok(action) => action,
+        Err(ActionsError::SkippedEvent { .. }) => {
+            crate::deadline::run_cleanup_hooks();
+            let _ = std::io::stdout().flush();
+            let _ = std::io::stderr().flush();
+            std::process::exit(0);
+        }
+        Err(err) => fail_with_error(err),
+    }
+}
+
+/// Get the error annotations reported for an error
+///
+/// The message includes the sources of the error (`error: source`). When the error (or one
+/// of its sources) is an [ActionsError], the annotation is titled using the kind of error and
+/// [ActionsError::MultipleErrors] is reported as one annotation per error.
+///
+/// ```
+/// use ghactions_core::exit::error_annotations;
+/// use ghactions_core::ActionsError;
+///
+/// let error = ActionsError::MultipleErrors(vec![
+///     ActionsError::InputTypeError("count".to_string(), "int".to_string()),
+///     ActionsError::RepositoryReferenceError("main".to_string()),
+/// ]);
+/// let annotations = error_annotations(&error);
+/// assert_eq!(annotations.len(), 2);
+/// assert_eq!(annotations[0].title.as_deref(), Some("Invalid input"));
+/// ```
+pub fn error_annotations(error: &(dyn Error + 'static)) -> Vec<Annotation> {
+    if let Some(ActionsError::MultipleErrors(errors)) = error.downcast_ref::<ActionsError>() {
+        return errors
+            .iter()
+            .flat_map(|error| error_annotations(error))
+            .collect();
+    }
+
+    let mut message = error.to_string();
+    let mut title = None;
+    let mut source = Some(error);
+    while let Some(error) = source {
+        let cause = error.to_string();
+        // Errors often include the message of their source
+        if !message.contains(&cause) {
+            message.push_str(&format!(": {}", cause));
+        }
+        if let Some(error) = error.downcast_ref::<ActionsError>() {
+            title = title.or_else(|| error_title(error));
+        }
+        source = error.source();
+    }
+
+    let annotation = Annotation::new(AnnotationLevel::Error, message);
+    vec![match title {
+        Some(title) => annotation.title(title),
+        None => annotation,
+    }]
+}
+
+/// Title of the annotation of an error
+fn error_title(error: &ActionsError) -> Option<&'static str> {
+    match error {
+        ActionsError::InputError(_)
+        | ActionsError::MissingInput { .. }
+        | ActionsError::InputTypeError(..)
+        | ActionsError::InputParseError(..)
+        | ActionsError::InputPatternError(..)
+        | ActionsError::InputAllowedError(..) => Some("Invalid input"),
+        ActionsError::UnsafeContext(_) => Some("Unsafe context"),
+        #[cfg(feature = "octocrab")]
+        ActionsError::OctocrabError(_) => Some("GitHub API error"),
+        ActionsError::GraphQLError(_) => Some("GitHub API error"),
+        ActionsError::UnsupportedEvent { .. } => Some("Unsupported event"),
+        ActionsError::RunnerVersionError { .. } => Some("Unsupported runner"),
+        _ => None,
+    }
+}
+
+fn exit(annotations: &[Annotation]) -> ! {
+    crate::deadline::run_cleanup_hooks();
+
+    for annotation in annotations {
+        #[cfg(feature = "log")]
+        if crate::logging::is_local_mode() {
+            eprintln!("Error: {}", annotation.plain());
+            continue;
+        }
+        println!("{}", annotation);
+    }
+    let _ = std::io::stdout().flush();
+    let _ = std::io::stderr().flush();
+    std::process::exit(FAILURE_EXIT_CODE);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("Failed to load the config")]
+    struct ConfigError(#[source] ActionsError);
+
+    #[test]
+    fn test_error_annotations() {
+        let error = ActionsError::InputParseError("mode".to_string(), "unknown `x`".to_string());
+        assert_eq!(
+            error_annotations(&error),
+            vec![Annotation::new(
+                AnnotationLevel::Error,
+                "Input `mode` is invalid: unknown `x`"
+            )
+            .title("Invalid input")]
+        );
+
+        // Sources are added to the message and used for the title
+        let error = ConfigError(ActionsError::InputError("config".to_string()));
+        assert_eq!(
+            error_annotations(&error),
+            vec![Annotation::new(
+                AnnotationLevel::Error,
+                "Failed to load the config: Failed to get input value: `config`"
+            )
+            .title("Invalid input")]
+        );
+
+        let error = ActionsError::MultipleErrors(vec![
+            ActionsError::FailedLoading("dotenv".to_string()),
+            ActionsError::MultipleErrors(vec![ActionsError::InputTypeError(
+                "count".to_string(),
+                "int".to_string(),
+            )]),
+        ]);
+        let annotations = error_annotations(&error);
+        assert_eq!(
+            annotations
+                .iter()
+                .map(|annotation| (annotation.title.as_deref(), annotation.message.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (None, "Failed to load environment: `dotenv`"),
+                (
+                    Some("Invalid input"),
+                    "Input Type Error: `count` (Expected: `int`)"
+                ),
+            ]
+        );
+    }
+}
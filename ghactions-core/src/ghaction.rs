@@ -174,8 +174,10 @@ impl GHAction {
     }
 
     fn encode_envvar(prefix: &str, key: &str) -> String {
-        let new_key: String = key.to_owned().replace('-', "_").to_uppercase();
-        format!("{}_{}", prefix, &new_key)
+        match prefix {
+            "INPUT" => crate::inputs::input_env_name(key),
+            _ => format!("{}_{}", prefix, key.replace(' ', "_").to_uppercase()),
+        }
     }
 
     /// Get an environment variable from the GitHub Action
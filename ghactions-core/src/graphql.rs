@@ -0,0 +1,282 @@
+//! # GraphQL
+//!
+//! Run GraphQL queries against the GraphQL API of the GitHub instance (see
+//! [crate::GitHubUrls::graphql_url], `<server>/api/graphql` on GitHub Enterprise Server).
+//!
+//! The `errors` of the response are returned as [ActionsError::GraphQLError] (one message per
+//! error, including the error type and path) and the `data` is deserialised. Mutations are
+//! blocked by the safety policy for pull requests from forks (see [crate::safety]).
+//!
+//! ```no_run
+//! use ghactions_core::graphql::query;
+//!
+//! # async fn run(token: &str) -> Result<(), ghactions_core::ActionsError> {
+//! let data: serde_json::Value = query(
+//!     "https://api.github.com/graphql",
+//!     Some(token),
+//!     "query($owner: String!, $name: String!) { repository(owner: $owner, name: $name) { id } }",
+//!     serde_json::json!({ "owner": "42ByteLabs", "name": "ghactions" }),
+//! )
+//! .await?;
+//! # Ok(())
+//! # }
+//! ```
+use http::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
+use http_body_util::BodyExt;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::proxy::{http_client, ProxySettings};
+use crate::ActionsError;
+
+/// Response of the GraphQL API
+#[derive(Debug, Deserialize)]
+struct GraphQLResponse {
+    #[serde(default)]
+    data: Option<serde_json::Value>,
+    #[serde(default)]
+    errors: Vec<GraphQLErrorEntry>,
+}
+
+/// Error of a GraphQL response
+#[derive(Debug, Deserialize)]
+struct GraphQLErrorEntry {
+    message: String,
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    #[serde(default)]
+    path: Vec<serde_json::Value>,
+}
+
+impl GraphQLErrorEntry {
+    /// Error message (e.g. ``NOT_FOUND: Could not resolve to a Repository (at `repository`)``)
+    fn describe(&self) -> String {
+        let mut message = match &self.kind {
+            Some(kind) => format!("{}: {}", kind, self.message),
+            None => self.message.clone(),
+        };
+        if !self.path.is_empty() {
+            let path = self
+                .path
+                .iter()
+                .map(|segment| match segment {
+                    serde_json::Value::String(field) => field.clone(),
+                    segment => segment.to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join(".");
+            message.push_str(&format!(" (at `{}`)", path));
+        }
+        message
+    }
+}
+
+/// Post a GraphQL query (and its variables) to `url` and deserialise the `data`
+///
+/// The request is sent through the proxy of the runner (see [crate::proxy]), HTTP errors are
+/// returned as [ActionsError::GraphQLError].
+pub async fn query<T: DeserializeOwned>(
+    url: &str,
+    token: Option<&str>,
+    query: &str,
+    variables: serde_json::Value,
+) -> Result<T, ActionsError> {
+    if crate::safety::writes_forbidden() && query.trim_start().starts_with("mutation") {
+        return Err(ActionsError::UnsafeContext(
+            "GraphQL mutations are blocked for pull requests from forks".to_string(),
+        ));
+    }
+    let error = |message: String| ActionsError::GraphQLError(vec![message]);
+
+    let client = http_client(&ProxySettings::from_env())?;
+    let mut request = http::Request::post(url)
+        .header(ACCEPT, "application/json")
+        .header(CONTENT_TYPE, "application/json")
+        .header(USER_AGENT, "ghactions");
+    if let Some(token) = token {
+        request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let body = serde_json::json!({ "query": query, "variables": variables });
+    let request = request
+        .body(body.to_string())
+        .map_err(|err| error(err.to_string()))?;
+
+    let response = client
+        .request(request)
+        .await
+        .map_err(|err| error(err.to_string()))?;
+    let status = response.status();
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .map_err(|err| error(err.to_string()))?
+        .to_bytes();
+
+    // GraphQL errors are returned with a `200` status
+    if !status.is_success() {
+        return Err(error(format!(
+            "HTTP {} ({})",
+            status,
+            String::from_utf8_lossy(&body).trim()
+        )));
+    }
+    let response: GraphQLResponse =
+        serde_json::from_slice(&body).map_err(|err| error(format!("invalid response: {}", err)))?;
+    parse_response(response)
+}
+
+fn parse_response<T: DeserializeOwned>(response: GraphQLResponse) -> Result<T, ActionsError> {
+    if !response.errors.is_empty() {
+        return Err(ActionsError::GraphQLError(
+            response
+                .errors
+                .iter()
+                .map(GraphQLErrorEntry::describe)
+                .collect(),
+        ));
+    }
+    let data = response
+        .data
+        .ok_or_else(|| ActionsError::GraphQLError(vec!["the response has no `data`".into()]))?;
+    serde_json::from_value(data)
+        .map_err(|err| ActionsError::GraphQLError(vec![format!("invalid `data`: {}", err)]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Repository {
+        repository: RepositoryId,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct RepositoryId {
+        id: String,
+    }
+
+    #[tokio::test]
+    async fn test_query() {
+        let server = MockServer::start().await;
+        // GitHub Enterprise Server GraphQL path
+        Mock::given(method("POST"))
+            .and(path("/api/graphql"))
+            .and(header("authorization", "Bearer ghs_graphql"))
+            .and(body_partial_json(
+                json!({ "variables": { "name": "ghactions" } }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "repository": { "id": "R_1" } }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let data: Repository = query(
+            &format!("{}/api/graphql", server.uri()),
+            Some("ghs_graphql"),
+            "query($name: String!) { repository(owner: \"octo\", name: $name) { id } }",
+            json!({ "name": "ghactions" }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(data.repository.id, "R_1");
+    }
+
+    #[tokio::test]
+    async fn test_query_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/graphql"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "repository": null },
+                "errors": [
+                    {
+                        "type": "NOT_FOUND",
+                        "path": ["repository"],
+                        "message": "Could not resolve to a Repository with the name 'octo/missing'."
+                    },
+                    { "message": "Something went wrong", "path": ["repository", "issues", 0] }
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let err = query::<Repository>(
+            &format!("{}/api/graphql", server.uri()),
+            None,
+            "query { repository(owner: \"octo\", name: \"missing\") { id } }",
+            json!({}),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ActionsError::GraphQLError(vec![
+                "NOT_FOUND: Could not resolve to a Repository with the name 'octo/missing'. (at `repository`)".to_string(),
+                "Something went wrong (at `repository.issues.0`)".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let response = |value: serde_json::Value| serde_json::from_value(value).unwrap();
+
+        assert_eq!(
+            parse_response::<serde_json::Value>(response(json!({}))).unwrap_err(),
+            ActionsError::GraphQLError(vec!["the response has no `data`".to_string()])
+        );
+        assert!(matches!(
+            parse_response::<Repository>(response(json!({ "data": { "viewer": {} } }))),
+            Err(ActionsError::GraphQLError(_))
+        ));
+    }
+
+    struct GraphQLAction;
+
+    impl crate::ActionTrait for GraphQLAction {
+        fn init() -> Result<Self, ActionsError> {
+            Ok(GraphQLAction)
+        }
+        fn name(&self) -> &str {
+            "GraphQL Action"
+        }
+        fn description(&self) -> &str {
+            ""
+        }
+    }
+
+    #[tokio::test]
+    async fn test_action_graphql() {
+        use crate::ActionTrait;
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/graphql"))
+            .and(header("authorization", "Bearer ghs_input"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "viewer": { "login": "octocat" } }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // GitHub Enterprise Server (the GraphQL URL is derived from the server URL)
+        let env = std::collections::HashMap::from([
+            ("GITHUB_SERVER_URL".to_string(), server.uri()),
+            ("INPUT_TOKEN".to_string(), "ghs_input".to_string()),
+        ]);
+        let data: serde_json::Value = crate::env::with_env(env, || {
+            GraphQLAction.graphql("query { viewer { login } }", json!({}))
+        })
+        .await
+        .unwrap();
+        assert_eq!(data["viewer"]["login"], "octocat");
+    }
+}
@@ -0,0 +1,366 @@
+//! Input parsing and validation helpers used by the derive macro
+use regex::Regex;
+
+use crate::ActionsError;
+
+/// Parse a boolean following the GitHub Actions rules (`true`, `True`, `TRUE`, `false`, `False` or `FALSE`)
+///
+/// # Examples
+///
+/// ```
+/// use ghactions_core::inputs::parse_bool;
+///
+/// assert_eq!(parse_bool("True"), Some(true));
+/// assert_eq!(parse_bool("FALSE"), Some(false));
+/// assert_eq!(parse_bool("yes"), None);
+/// ```
+pub fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim() {
+        "true" | "True" | "TRUE" => Some(true),
+        "false" | "False" | "FALSE" => Some(false),
+        _ => None,
+    }
+}
+
+/// Boolean spellings accepted by [parse_bool] (used in error messages)
+pub const BOOL_SPELLINGS: &str = "true, True, TRUE, false, False or FALSE";
+
+/// Boolean spellings accepted by [parse_bool_lenient] (used in error messages)
+pub const BOOL_LENIENT_SPELLINGS: &str =
+    "true, True, TRUE, false, False, FALSE, 1, 0, yes, no, on or off";
+
+/// Parse a boolean accepting the [parse_bool] spellings and `1` / `0`, `yes` / `no` and
+/// `on` / `off` (case insensitive)
+///
+/// # Examples
+///
+/// ```
+/// use ghactions_core::inputs::parse_bool_lenient;
+///
+/// assert_eq!(parse_bool_lenient(" Yes "), Some(true));
+/// assert_eq!(parse_bool_lenient("0"), Some(false));
+/// assert_eq!(parse_bool_lenient("maybe"), None);
+/// ```
+pub fn parse_bool_lenient(value: &str) -> Option<bool> {
+    parse_bool(value).or_else(|| match value.trim().to_lowercase().as_str() {
+        "1" | "yes" | "on" => Some(true),
+        "0" | "no" | "off" => Some(false),
+        _ => None,
+    })
+}
+
+/// Parse an Action input from its raw string value
+///
+/// Implement this trait for custom types (e.g. newtypes with their own validation) to
+/// use them as input fields with the `Actions` derive. Fields also need to implement
+/// `Default` which is used for missing optional inputs.
+///
+/// # Examples
+///
+/// ```
+/// use ghactions_core::inputs::FromInput;
+///
+/// #[derive(Debug, Default, PartialEq)]
+/// struct Port(u16);
+///
+/// impl FromInput for Port {
+///     fn from_input(raw: &str) -> Result<Self, String> {
+///         match u16::from_input(raw)? {
+///             0 => Err("port can not be `0`".to_string()),
+///             port => Ok(Port(port)),
+///         }
+///     }
+/// }
+///
+/// assert_eq!(Port::from_input("8080"), Ok(Port(8080)));
+/// assert!(Port::from_input("0").is_err());
+/// ```
+pub trait FromInput: Sized {
+    /// Parse the raw input value
+    fn from_input(raw: &str) -> Result<Self, String>;
+}
+
+impl FromInput for String {
+    fn from_input(raw: &str) -> Result<Self, String> {
+        Ok(raw.to_string())
+    }
+}
+
+impl FromInput for bool {
+    fn from_input(raw: &str) -> Result<Self, String> {
+        parse_bool(raw).ok_or_else(|| format!("`{}` is not a valid boolean", raw))
+    }
+}
+
+/// Comma separated list of values
+impl FromInput for Vec<String> {
+    fn from_input(raw: &str) -> Result<Self, String> {
+        Ok(raw.split(',').map(|value| value.to_string()).collect())
+    }
+}
+
+macro_rules! from_input_int {
+    ($($int:ty),*) => {
+        $(
+            impl FromInput for $int {
+                fn from_input(raw: &str) -> Result<Self, String> {
+                    raw.trim()
+                        .parse::<$int>()
+                        .map_err(|err| format!("`{}` is not a valid `{}` ({})", raw, stringify!($int), err))
+                }
+            }
+        )*
+    };
+}
+
+from_input_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// Check if an input value contains a `${{ }}` expression that was not expanded by the runner
+///
+/// This happens when a workflow quotes the expression (e.g. `'${{ secrets.TOKEN }}'`).
+///
+/// # Examples
+///
+/// ```
+/// use ghactions_core::inputs::has_unexpanded_expression;
+///
+/// assert!(has_unexpanded_expression("${{ secrets.MY_TOKEN }}"));
+/// assert!(!has_unexpanded_expression("ghp_token"));
+/// ```
+pub fn has_unexpanded_expression(value: &str) -> bool {
+    value
+        .find("${{")
+        .is_some_and(|start| value[start..].contains("}}"))
+}
+
+/// Get the environment variable the runner sets for an input
+///
+/// GitHub uppercases the input name and replaces spaces with `_` (dashes are kept).
+///
+/// # Examples
+///
+/// ```
+/// use ghactions_core::inputs::input_env_name;
+///
+/// assert_eq!(input_env_name("token"), "INPUT_TOKEN");
+/// assert_eq!(input_env_name("my-input"), "INPUT_MY-INPUT");
+/// assert_eq!(input_env_name("my input"), "INPUT_MY_INPUT");
+/// ```
+pub fn input_env_name(name: &str) -> String {
+    format!("INPUT_{}", name.replace(' ', "_").to_uppercase())
+}
+
+/// Get the environment variable an input is read from (see [input_env_name])
+///
+/// Keys which are already environment variables (starting with `INPUT_`) are used as-is.
+/// This is deprecated and only supported for compatibility, use the input name instead.
+///
+/// # Examples
+///
+/// ```
+/// use ghactions_core::inputs::input_key;
+///
+/// assert_eq!(input_key("my-input"), "INPUT_MY-INPUT");
+/// assert_eq!(input_key("INPUT_MY-INPUT"), "INPUT_MY-INPUT");
+/// ```
+pub fn input_key(name: &str) -> String {
+    if name.starts_with("INPUT_") {
+        #[cfg(feature = "log")]
+        if !INPUT_KEY_DEPRECATED.swap(true, std::sync::atomic::Ordering::Relaxed) {
+            log::warn!(
+                "Reading inputs using the environment variable (`{}`) is deprecated, use the input name instead",
+                name
+            );
+        }
+        return name.to_string();
+    }
+    input_env_name(name)
+}
+
+/// The deprecation warning of [input_key] is only logged once
+#[cfg(feature = "log")]
+static INPUT_KEY_DEPRECATED: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+/// Select the input name an input with aliases is read from
+///
+/// The input is used if it is set (and not empty), otherwise the first alias which is
+/// set. Returns the name to read and the alias it belongs to (if an alias is used).
+///
+/// # Examples
+///
+/// ```
+/// use ghactions_core::inputs::input_source;
+///
+/// std::env::set_var("INPUT_GH-TOKEN", "ghp_token");
+/// let aliases = [("gh-token", "gh-token"), ("gh-token", "gh_token")];
+/// assert_eq!(input_source("token", &aliases), ("gh-token", Some("gh-token")));
+///
+/// std::env::set_var("INPUT_TOKEN", "ghp_new_token");
+/// assert_eq!(input_source("token", &aliases), ("token", None));
+/// ```
+pub fn input_source(
+    name: &'static str,
+    aliases: &[(&'static str, &'static str)],
+) -> (&'static str, Option<&'static str>) {
+    let is_set = |name: &str| {
+        crate::dev_inputs::input_var(&input_env_name(name)).is_some_and(|value| !value.is_empty())
+    };
+    if is_set(name) {
+        return (name, None);
+    }
+    aliases
+        .iter()
+        .find(|(_, alias_name)| is_set(alias_name))
+        .map(|(alias, alias_name)| (*alias_name, Some(*alias)))
+        .unwrap_or((name, None))
+}
+
+/// Replace the environment variable name in an input error with the Action input name
+///
+/// # Examples
+///
+/// ```
+/// use ghactions_core::{inputs::with_input_name, ActionsError};
+///
+/// let err = with_input_name(ActionsError::InputError("INPUT_TOKEN".into()), "token");
+/// assert_eq!(err, ActionsError::InputError("token".into()));
+/// ```
+pub fn with_input_name(err: ActionsError, name: &str) -> ActionsError {
+    match err {
+        ActionsError::InputError(_) => ActionsError::InputError(name.to_string()),
+        ActionsError::InputTypeError(_, r#type) => {
+            ActionsError::InputTypeError(name.to_string(), r#type)
+        }
+        ActionsError::InputParseError(_, message) => {
+            ActionsError::InputParseError(name.to_string(), message)
+        }
+        err => err,
+    }
+}
+
+/// Check an input value matches the provided regex pattern
+///
+/// # Examples
+///
+/// ```
+/// use ghactions_core::inputs::check_input_pattern;
+/// use regex::Regex;
+///
+/// let regex = Regex::new(r"^v[0-9]+$").unwrap();
+/// assert!(check_input_pattern("version", "v1", &regex).is_ok());
+/// assert!(check_input_pattern("version", "1", &regex).is_err());
+/// ```
+pub fn check_input_pattern(name: &str, value: &str, regex: &Regex) -> Result<(), ActionsError> {
+    // Empty values are not validated (optional inputs)
+    if value.is_empty() || regex.is_match(value) {
+        Ok(())
+    } else {
+        Err(ActionsError::InputPatternError(
+            name.to_string(),
+            value.to_string(),
+            regex.as_str().to_string(),
+        ))
+    }
+}
+
+/// Check an input value is one of the allowed values
+///
+/// Values are compared case sensitively unless `ignore_case` is set.
+///
+/// # Examples
+///
+/// ```
+/// use ghactions_core::inputs::check_input_allowed;
+///
+/// let allowed = ["debug", "info"];
+/// assert!(check_input_allowed("level", "info", &allowed, false).is_ok());
+/// assert!(check_input_allowed("level", "INFO", &allowed, false).is_err());
+/// assert!(check_input_allowed("level", "INFO", &allowed, true).is_ok());
+/// ```
+pub fn check_input_allowed(
+    name: &str,
+    value: &str,
+    allowed: &[&str],
+    ignore_case: bool,
+) -> Result<(), ActionsError> {
+    let found = allowed.iter().any(|allowed| {
+        if ignore_case {
+            allowed.eq_ignore_ascii_case(value)
+        } else {
+            *allowed == value
+        }
+    });
+    // Empty values are not validated (optional inputs)
+    if value.is_empty() || found {
+        Ok(())
+    } else {
+        Err(ActionsError::InputAllowedError(
+            name.to_string(),
+            value.to_string(),
+            allowed.iter().map(|allowed| allowed.to_string()).collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_env_name() {
+        // Plain words
+        assert_eq!(input_env_name("token"), "INPUT_TOKEN");
+        assert_eq!(input_env_name("TOKEN"), "INPUT_TOKEN");
+        assert_eq!(input_env_name("my_input"), "INPUT_MY_INPUT");
+        // Dashes are kept
+        assert_eq!(input_env_name("my-input"), "INPUT_MY-INPUT");
+        assert_eq!(input_env_name("-leading"), "INPUT_-LEADING");
+        assert_eq!(input_env_name("a--b"), "INPUT_A--B");
+        // Spaces become underscores
+        assert_eq!(input_env_name("my input"), "INPUT_MY_INPUT");
+        assert_eq!(input_env_name("my  input"), "INPUT_MY__INPUT");
+        assert_eq!(input_env_name("my-input name"), "INPUT_MY-INPUT_NAME");
+        // Mixed case
+        assert_eq!(input_env_name("myInput"), "INPUT_MYINPUT");
+        assert_eq!(input_env_name("My-Input_Name"), "INPUT_MY-INPUT_NAME");
+        assert_eq!(input_env_name("api Key-2"), "INPUT_API_KEY-2");
+    }
+
+    #[test]
+    fn test_input_key() {
+        assert_eq!(input_key("token"), "INPUT_TOKEN");
+        assert_eq!(input_key("my input"), "INPUT_MY_INPUT");
+        // Deprecated environment variable keys
+        assert_eq!(input_key("INPUT_TOKEN"), "INPUT_TOKEN");
+        assert_eq!(input_key("input_token"), "INPUT_INPUT_TOKEN");
+    }
+
+    #[test]
+    fn test_parse_bool() {
+        // value, strict, lenient
+        let cases = [
+            ("true", Some(true), Some(true)),
+            ("True", Some(true), Some(true)),
+            ("TRUE", Some(true), Some(true)),
+            ("  true\n", Some(true), Some(true)),
+            ("false", Some(false), Some(false)),
+            ("False", Some(false), Some(false)),
+            ("FALSE", Some(false), Some(false)),
+            ("tRUE", None, None),
+            ("1", None, Some(true)),
+            ("0", None, Some(false)),
+            ("yes", None, Some(true)),
+            ("NO", None, Some(false)),
+            ("On", None, Some(true)),
+            ("off", None, Some(false)),
+            ("y", None, None),
+            ("2", None, None),
+            ("", None, None),
+        ];
+        for (value, strict, lenient) in cases {
+            assert_eq!(parse_bool(value), strict, "strict `{}`", value);
+            assert_eq!(parse_bool_lenient(value), lenient, "lenient `{}`", value);
+        }
+    }
+}
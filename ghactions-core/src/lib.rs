@@ -7,15 +7,61 @@
 extern crate log;
 
 pub mod actions;
+#[cfg(feature = "artifacts")]
+pub mod artifacts;
+pub mod commands;
+pub mod context;
+pub mod deadline;
+pub mod dev_inputs;
+pub mod dotenv;
+pub mod env;
+pub mod env_files;
 pub mod errors;
+pub mod event;
+pub mod exit;
 // pub mod ghaction;
+#[cfg(feature = "octocrab")]
+pub mod graphql;
+pub mod inputs;
 #[cfg(feature = "log")]
 pub mod logging;
+#[cfg(feature = "oidc")]
+pub mod oidc;
+pub mod proxy;
+#[cfg(feature = "reports")]
+pub mod reports;
 pub mod repository;
+pub mod resources;
+#[cfg(feature = "octocrab")]
+pub mod retry;
+pub mod runner;
+pub mod safety;
+pub mod summary;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "octocrab")]
+pub mod updates;
+pub mod urls;
 
+pub use crate::actions::diff::SchemaMismatch;
 pub use crate::actions::models::{ActionInput, ActionRuns, ActionYML};
+pub use crate::actions::validate::{Severity, ValidationIssue};
+pub use crate::context::GitHubContext;
 pub use crate::errors::ActionsError;
+pub use crate::inputs::FromInput;
 pub use crate::repository::reference::RepositoryReference;
+pub use crate::safety::SafetyPolicy;
+pub use crate::summary::{AlertKind, Summary};
+pub use crate::urls::GitHubUrls;
+
+/// Group of inputs shared between Actions
+///
+/// Implemented using `#[derive(ActionInputs)]` and embedded in an Action using
+/// `#[input(flatten)]`.
+pub trait InputGroup: Sized {
+    /// Parse the inputs of the group using the Action `A`, errors are added to `errors`
+    fn from_inputs<A: ActionTrait>(errors: &mut Vec<ActionsError>) -> Self;
+}
 
 /// Action Trait
 pub trait ActionTrait {
@@ -24,42 +70,184 @@ pub trait ActionTrait {
     where
         Self: Sized;
 
+    /// Parse the action input using the variables of `env` instead of the process environment
+    ///
+    /// The inputs, `GITHUB_EVENT_NAME`, the runner version, ... are only read from `env`
+    /// (see [env::with_env]), so tests can create Actions in parallel.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use ghactions::prelude::*;
+    ///
+    /// #[derive(Actions, Debug)]
+    /// #[action(name = "Greeter")]
+    /// struct Greeter {
+    ///     #[input(description = "Who to greet", required = true)]
+    ///     name: String,
+    ///     #[input(description = "Times to greet", default = "1")]
+    ///     times: i32,
+    /// }
+    ///
+    /// let env = HashMap::from([
+    ///     ("INPUT_NAME".to_string(), "Octocat".to_string()),
+    ///     ("INPUT_TIMES".to_string(), "2".to_string()),
+    /// ]);
+    /// let action = Greeter::init_from(&env).unwrap();
+    /// assert_eq!(action.name, "Octocat");
+    /// assert_eq!(action.times, 2);
+    ///
+    /// assert!(Greeter::init_from(&HashMap::new()).is_err());
+    /// ```
+    fn init_from(env: &std::collections::HashMap<String, String>) -> Result<Self, ActionsError>
+    where
+        Self: Sized,
+    {
+        env::with_env(env.clone(), Self::init)
+    }
+
     /// Get the action name
     fn name(&self) -> &str;
 
     /// Get the action description
     fn description(&self) -> &str;
 
-    /// Get the input value for a provided key
-    fn get_input(key: impl Into<String> + Copy) -> Result<String, ActionsError> {
-        std::env::var(&key.into()).map_err(|_| ActionsError::InputError(key.into()))
+    /// Get the value of an environment variable (e.g. `GITHUB_TOKEN`)
+    fn get_env(key: impl Into<String> + Copy) -> Result<String, ActionsError> {
+        env::var(&key.into()).ok_or_else(|| ActionsError::InputError(key.into()))
+    }
+
+    /// Get the input value for a provided input name (e.g. `my-input`)
+    ///
+    /// The name is mapped to the environment variable the runner sets for the input (see
+    /// [inputs::input_key]).
+    ///
+    /// **Migration:** previously the key was used as the environment variable. Keys
+    /// starting with `INPUT_` still work but are deprecated, other environment variables
+    /// (e.g. `GITHUB_TOKEN`) have to be read using [ActionTrait::get_env].
+    ///
+    /// Surrounding whitespace (e.g. the trailing newline of a step output) is trimmed, use
+    /// [ActionTrait::get_input_untrimmed] to get the value as-is.
+    fn get_input(name: impl Into<String> + Copy) -> Result<String, ActionsError> {
+        Self::get_input_untrimmed(name).map(|value| value.trim().to_string())
+    }
+
+    /// Get the input value for a provided input name without trimming whitespace
+    fn get_input_untrimmed(name: impl Into<String> + Copy) -> Result<String, ActionsError> {
+        Self::get_input_raw(name).ok_or_else(|| ActionsError::InputError(name.into()))
+    }
+
+    /// Get the raw input value for a provided input name
+    ///
+    /// Returns `None` if the input is missing which is different from an empty input.
+    fn get_input_raw(name: impl Into<String>) -> Option<String> {
+        dev_inputs::input_var(&inputs::input_key(&name.into()))
+    }
+
+    /// Get the input value for a provided key as a presence based flag
+    ///
+    /// - Missing: `false`
+    /// - Empty: `true`
+    /// - Otherwise the value is parsed as a GitHub Actions boolean (`true` / `false`)
+    fn get_input_flag(key: impl Into<String> + Copy) -> Result<bool, ActionsError> {
+        match Self::get_input_raw(key) {
+            None => Ok(false),
+            Some(value) if value.trim().is_empty() => Ok(true),
+            Some(value) => crate::inputs::parse_bool(&value).ok_or_else(|| {
+                ActionsError::InputTypeError(
+                    key.into(),
+                    format!("bool: {}", crate::inputs::BOOL_SPELLINGS),
+                )
+            }),
+        }
     }
 
     /// Get the input value for a provided key as a boolean
+    ///
+    /// Accepts the same values as `getBooleanInput` of `actions/core` (see
+    /// [inputs::parse_bool]), surrounding whitespace is trimmed before parsing.
     fn get_input_bool(key: impl Into<String> + Copy) -> Result<bool, ActionsError> {
-        Self::get_input(key)?
-            .parse::<bool>()
-            .map_err(|_| ActionsError::InputTypeError(key.into(), "bool".into()))
+        crate::inputs::parse_bool(&Self::get_input(key)?).ok_or_else(|| {
+            ActionsError::InputTypeError(
+                key.into(),
+                format!("bool: {}", crate::inputs::BOOL_SPELLINGS),
+            )
+        })
+    }
+
+    /// Get the input value for a provided key as a boolean, also accepting `1` / `0`,
+    /// `yes` / `no` and `on` / `off` (see [inputs::parse_bool_lenient])
+    fn get_input_bool_lenient(key: impl Into<String> + Copy) -> Result<bool, ActionsError> {
+        crate::inputs::parse_bool_lenient(&Self::get_input(key)?).ok_or_else(|| {
+            ActionsError::InputTypeError(
+                key.into(),
+                format!("bool: {}", crate::inputs::BOOL_LENIENT_SPELLINGS),
+            )
+        })
     }
 
     /// Get the input value for a provided key as an integer
+    ///
+    /// Surrounding whitespace is trimmed before parsing.
     fn get_input_int(key: impl Into<String> + Copy) -> Result<i32, ActionsError> {
         Self::get_input(key)?
+            .trim()
             .parse::<i32>()
             .map_err(|_| ActionsError::InputTypeError(key.into(), "int".into()))
     }
 
+    /// Get the input value for a provided key as a duration (e.g. `90`, `30s`, `5m` or `1h30m`)
+    ///
+    /// Numbers without a unit are seconds (see [deadline::parse_duration]).
+    fn get_input_duration(
+        key: impl Into<String> + Copy,
+    ) -> Result<std::time::Duration, ActionsError> {
+        let value = Self::get_input(key)?;
+        deadline::parse_duration(&value).ok_or_else(|| {
+            ActionsError::InputTypeError(
+                key.into(),
+                format!("duration, found \"{}\"", value.trim()),
+            )
+        })
+    }
+
+    /// Get the input value for a provided key using the [FromInput] trait
+    fn get_input_from<T: FromInput>(key: impl Into<String> + Copy) -> Result<T, ActionsError> {
+        let value = Self::get_input(key)?;
+        T::from_input(&value).map_err(|err| ActionsError::InputParseError(key.into(), err))
+    }
+
     /// Get the input value for a provided key as a vector using a seperator
+    ///
+    /// Surrounding whitespace is trimmed from each value.
     fn get_input_vec(
         key: impl Into<String> + Copy,
         seperator: &str,
     ) -> Result<Vec<String>, ActionsError> {
         Ok(Self::get_input(key)?
             .split(seperator)
-            .map(|s| s.to_string())
+            .map(|s| s.trim().to_string())
             .collect::<Vec<String>>())
     }
 
+    /// Get the input value for a provided key as a list of lines (`getMultilineInput` of
+    /// `actions/core`)
+    ///
+    /// Each line is trimmed and empty lines are removed (supports `\n` and `\r\n` line endings).
+    fn get_multiline_input(key: impl Into<String> + Copy) -> Result<Vec<String>, ActionsError> {
+        Ok(Self::get_input(key)?
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| line.to_string())
+            .collect::<Vec<String>>())
+    }
+
+    /// Get the input value for a provided key as a list of lines (see
+    /// [ActionTrait::get_multiline_input])
+    fn get_input_lines(key: impl Into<String> + Copy) -> Result<Vec<String>, ActionsError> {
+        Self::get_multiline_input(key)
+    }
+
     /// Set the output value for a provided key
     fn set_output(
         key: impl Into<String> + Copy,
@@ -68,97 +256,331 @@ pub trait ActionTrait {
         let key = key.into();
         let value = value.into();
 
-        setoutput!(key, value);
+        if !crate::actions::names::is_valid_name(&key) {
+            return Err(ActionsError::OutputNameError(key));
+        }
+
+        let writer = env_files::OutputWriter::new();
+        #[cfg(feature = "log")]
+        log::debug!("Setting output `{}`", writer.key(&key));
+
+        writer.set(&key, &value)
+    }
+
+    /// Set the output value for a provided key to a value serialized as JSON
+    ///
+    /// The JSON is pretty printed and written using the multi-line (heredoc) format, so it
+    /// can be parsed using `fromJSON` in the workflow (see [env_files::to_json_output]).
+    fn set_output_json<T: serde::Serialize + ?Sized>(
+        key: impl Into<String> + Copy,
+        value: &T,
+    ) -> Result<(), ActionsError> {
+        let value = env_files::to_json_output(value)?;
+        let key: String = key.into();
+        Self::set_output(key.as_str(), value.as_str())
+    }
+
+    /// Export an environment variable for the following steps of the job (`GITHUB_ENV`)
+    ///
+    /// The variable is also set for the current process (see [env_files::set_env]).
+    fn set_env(key: impl AsRef<str>, value: impl AsRef<str>) -> Result<(), ActionsError> {
+        env_files::set_env(key.as_ref(), value.as_ref())
+    }
+
+    /// Save a value for the `post` step of the Action (`GITHUB_STATE`)
+    fn save_state(key: impl AsRef<str>, value: impl AsRef<str>) -> Result<(), ActionsError> {
+        env_files::save_state(key.as_ref(), value.as_ref())
+    }
+
+    /// Get a value saved by the main step using [ActionTrait::save_state] (in the `post` step)
+    fn get_state(key: impl AsRef<str>) -> Option<String> {
+        env_files::get_state(key.as_ref())
+    }
+
+    /// Prepend a directory to the `PATH` of the following steps of the job (`GITHUB_PATH`)
+    ///
+    /// The `PATH` of the current process is also updated (see [env_files::add_path]).
+    fn add_path(path: impl AsRef<std::path::Path>) -> Result<(), ActionsError> {
+        env_files::add_path(path)
+    }
+
+    /// Mask a value so the runner redacts it in all following log lines
+    ///
+    /// Empty values are ignored and multi-line values are masked line by line (see
+    /// [commands::add_mask]).
+    fn add_mask(value: impl AsRef<str>) {
+        commands::add_mask(value.as_ref());
+    }
+
+    /// Create a notice annotation (emitted using [commands::Annotation::emit])
+    fn notice(message: impl Into<String>) -> commands::Annotation {
+        commands::Annotation::new(commands::AnnotationLevel::Notice, message)
+    }
+
+    /// Create a warning annotation (emitted using [commands::Annotation::emit])
+    fn warning(message: impl Into<String>) -> commands::Annotation {
+        commands::Annotation::new(commands::AnnotationLevel::Warning, message)
+    }
+
+    /// Check if debug logging is enabled (`RUNNER_DEBUG`, see [runner::is_debug])
+    fn is_debug(&self) -> bool {
+        runner::is_debug()
+    }
+
+    /// Create an empty job summary (written using [Summary::write] to `GITHUB_STEP_SUMMARY`)
+    fn summary(&self) -> Summary {
+        Summary::new()
+    }
+
+    /// Get the directory of the Action (see [resources::action_path] for the resolution order)
+    fn action_path() -> Result<std::path::PathBuf, ActionsError> {
+        resources::action_path()
+    }
+
+    /// Get the path of a resource (e.g. a template) shipped alongside the `action.yml` file
+    ///
+    /// The resource has to exist and can not escape the Action directory.
+    fn action_resource(
+        relative: impl AsRef<std::path::Path>,
+    ) -> Result<std::path::PathBuf, ActionsError> {
+        resources::action_resource(relative)
+    }
 
-        Ok(())
+    /// Get the workspace of the job (`GITHUB_WORKSPACE`)
+    fn get_workspace(&self) -> Result<std::path::PathBuf, ActionsError> {
+        resources::get_workspace()
+    }
+
+    /// Resolve a path (e.g. from an input) inside the workspace
+    ///
+    /// Relative paths are joined to the workspace and paths escaping the workspace are
+    /// rejected (see [resources::resolve_workspace_path]).
+    fn resolve_workspace_path(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<std::path::PathBuf, ActionsError> {
+        resources::resolve_workspace_path(path)
     }
 
     /// Get the Octocrab instance
     ///
-    /// Uses the `GITHUB_API_URL` and `GITHUB_TOKEN` environment variable to create an Octocrab instance
+    /// Uses the `GITHUB_API_URL` environment variable and the token (see [ActionTrait::get_token])
+    /// to create an Octocrab instance.
+    /// The requests are sent through the proxy of the runner (`https_proxy`, see [proxy]) unless
+    /// the API host is listed in `no_proxy`.
     #[cfg(feature = "octocrab")]
     fn octocrab(&self) -> Result<octocrab::Octocrab, ActionsError> {
-        #[cfg(feature = "log")]
-        {
-            log::debug!("Creating Octocrab instance");
-            log::debug!("URL: {}", self.get_api_url());
+        octocrab_client(self, true)
+    }
+
+    /// Get an Octocrab instance retrying transient errors
+    ///
+    /// Same as [ActionTrait::octocrab], requests failing with `502`, `503`, `504`, `429` or a
+    /// secondary rate limit are retried using the default [retry::RetryPolicy] (honouring the
+    /// `Retry-After` and `x-ratelimit-reset` headers).
+    #[cfg(feature = "octocrab")]
+    fn octocrab_retrying(&self) -> Result<octocrab::Octocrab, ActionsError> {
+        // Octocrab retries server errors immediately, the policy is used instead
+        Ok(retry::wrap(
+            octocrab_client(self, false)?,
+            retry::RetryPolicy::default(),
+        ))
+    }
+
+    /// Run a GraphQL query using the GraphQL URL of the GitHub instance
+    ///
+    /// Uses the token of [ActionTrait::get_token] (masked in the logs) and deserialises the
+    /// `data` of the response, the `errors` are returned as [ActionsError::GraphQLError] (see
+    /// [graphql]).
+    #[cfg(feature = "octocrab")]
+    fn graphql<T>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<T, ActionsError>> + Send>>
+    where
+        T: serde::de::DeserializeOwned + Send + 'static,
+    {
+        let token = self.get_token().ok();
+        if let Some(token) = &token {
+            Self::add_mask(token);
         }
+        let url = self.get_graphql_url();
+        let query = query.to_string();
+        Box::pin(async move { graphql::query(&url, token.as_deref(), &query, variables).await })
+    }
 
-        match self.get_token() {
-            Ok(token) => Ok(octocrab::Octocrab::builder()
-                .base_uri(self.get_api_url())
-                .map_err(|e| ActionsError::OctocrabError(e.to_string()))?
-                .add_header(
-                    http::header::ACCEPT,
-                    "application/vnd.github.v3+json".to_string(),
-                )
-                .personal_token(token)
-                .build()
-                .map_err(|e| ActionsError::OctocrabError(e.to_string()))?),
-            Err(_) => {
-                #[cfg(feature = "log")]
-                log::warn!("No GitHub Token provided");
-
-                Ok(octocrab::Octocrab::builder()
-                    .base_uri(self.get_api_url())
-                    .map_err(|e| ActionsError::OctocrabError(e.to_string()))?
-                    .add_header(
-                        http::header::ACCEPT,
-                        "application/vnd.github.v3+json".to_string(),
-                    )
-                    .build()
-                    .map_err(|e| ActionsError::OctocrabError(e.to_string()))?)
+    /// Check for a newer release of the running Action and notify the user
+    ///
+    /// Emits a single `::notice::` when the ref used by the workflow is behind the latest
+    /// release (see [updates] for details). This never fails the Action.
+    #[cfg(feature = "octocrab")]
+    fn check_for_newer_release(
+        &self,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Option<String>> + Send>> {
+        let client = self.octocrab().ok();
+        Box::pin(async move {
+            match client {
+                Some(client) => updates::check_for_newer_release(client).await,
+                None => None,
             }
-        }
+        })
     }
 
+    /// Get the OIDC token of the job for the `audience` (e.g. `sts.amazonaws.com`)
+    ///
+    /// The token is masked in the logs. The workflow needs the `id-token: write` permission
+    /// (see [oidc] for details).
+    #[cfg(feature = "oidc")]
+    fn get_id_token(
+        &self,
+        audience: Option<&str>,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<String, ActionsError>> + Send>>
+    {
+        let audience = audience.map(String::from);
+        Box::pin(async move { oidc::get_id_token(audience.as_deref()).await })
+    }
+
+    /// Opt-in to the [SafetyPolicy] guards for the rest of the process
+    ///
+    /// This is defense-in-depth for Actions running on `pull_request_target`: when the
+    /// pull request comes from a fork, clients created by [ActionTrait::octocrab] reject
+    /// write (non-`GET`) requests and values marked with [safety::mark_untrusted] can not
+    /// be exported using `set_env` / `add_path`.
+    fn assert_safe_context(&self, policy: SafetyPolicy) {
+        safety::enable(policy);
+    }
+
+    /// Get the server, API and GraphQL URLs of the GitHub instance (see [GitHubUrls])
+    fn github_urls(&self) -> GitHubUrls {
+        GitHubUrls::resolve(
+            Self::get_env("GITHUB_SERVER_URL").ok().as_deref(),
+            Self::get_env("GITHUB_API_URL").ok().as_deref(),
+            Self::get_env("GITHUB_GRAPHQL_URL").ok().as_deref(),
+        )
+    }
     /// GetHub Server URL (default: https://github.com)
     fn get_server_url(&self) -> String {
-        Self::get_input("GITHUB_SERVER_URL").unwrap_or_else(|_| "https://github.com".into())
+        self.github_urls().server_url
     }
-    /// GitHub API URL (default: https://api.github.com)
+    /// GitHub API URL (default: https://api.github.com, `<server>/api/v3` on GitHub Enterprise Server)
     fn get_api_url(&self) -> String {
-        Self::get_input("GITHUB_API_URL").unwrap_or_else(|_| "https://api.github.com".into())
+        self.github_urls().api_url
     }
-    /// GitHub GraphQL URL (default: https://api.github.com/graphql)
+    /// GitHub GraphQL URL (default: https://api.github.com/graphql, `<server>/api/graphql` on GitHub Enterprise Server)
     fn get_graphql_url(&self) -> String {
-        Self::get_input("GITHUB_GRAPHQL_URL")
-            .unwrap_or_else(|_| "https://api.github.com/graphql".into())
+        self.github_urls().graphql_url
     }
 
     /// Get the GitHub Token
+    ///
+    /// The first non-empty value of (in order of precedence):
+    ///
+    /// 1. Environment Variable: `GITHUB_TOKEN`
+    /// 2. Environment Variable: `ACTIONS_RUNTIME_TOKEN`
+    /// 3. Actions Input: `token` (`INPUT_TOKEN`)
+    /// 4. Actions Input: `github_token` (`INPUT_GITHUB_TOKEN`)
+    /// 5. Actions Input: `github-token` (`INPUT_GITHUB-TOKEN`)
     fn get_token(&self) -> Result<String, ActionsError> {
-        Self::get_input("GITHUB_TOKEN")
+        const TOKEN_VARS: [&str; 2] = ["GITHUB_TOKEN", "ACTIONS_RUNTIME_TOKEN"];
+        const TOKEN_INPUTS: [&str; 3] = ["INPUT_TOKEN", "INPUT_GITHUB_TOKEN", "INPUT_GITHUB-TOKEN"];
+
+        TOKEN_VARS
+            .iter()
+            .find_map(|key| env::var(key).filter(|token| !token.is_empty()))
+            .or_else(|| {
+                TOKEN_INPUTS
+                    .iter()
+                    .find_map(|key| dev_inputs::input_var(key).filter(|token| !token.is_empty()))
+            })
+            .ok_or_else(|| ActionsError::InputError("GITHUB_TOKEN".to_string()))
     }
     /// Get the GitHub SHA
     fn get_sha(&self) -> Result<String, ActionsError> {
-        Self::get_input("GITHUB_SHA")
+        Self::get_env("GITHUB_SHA")
     }
     /// Get the GitHub Ref (full)
     fn get_ref(&self) -> Result<String, ActionsError> {
-        Self::get_input("GITHUB_REF")
+        Self::get_env("GITHUB_REF")
     }
     /// Get the GitHub Ref Type
     fn get_ref_type(&self) -> Result<String, ActionsError> {
-        Self::get_input("GITHUB_REF_TYPE")
+        Self::get_env("GITHUB_REF_TYPE")
     }
     /// Get the GitHub Ref Name
     fn get_ref_name(&self) -> Result<String, ActionsError> {
-        Self::get_input("GITHUB_REF_NAME")
+        Self::get_env("GITHUB_REF_NAME")
     }
 
     /// Get the GitHub Workflow Event Name
     fn get_event_name(&self) -> Result<String, ActionsError> {
-        Self::get_input("GITHUB_EVENT_NAME")
+        Self::get_env("GITHUB_EVENT_NAME")
+    }
+
+    /// Get the GitHub context of the workflow run (parsed from the environment)
+    fn context(&self) -> Result<GitHubContext, ActionsError> {
+        GitHubContext::from_env()
+    }
+
+    /// Get the webhook event payload (`GITHUB_EVENT_PATH`) as JSON
+    fn get_event_payload(&self) -> Result<serde_json::Value, ActionsError> {
+        event::get_event_payload()
+    }
+    /// Get the webhook event payload (`GITHUB_EVENT_PATH`) as a custom type
+    fn get_event<T: serde::de::DeserializeOwned>(&self) -> Result<T, ActionsError> {
+        event::get_event()
+    }
+
+    /// Get the unique ID of the workflow run (`GITHUB_RUN_ID`)
+    fn get_run_id(&self) -> Result<u64, ActionsError> {
+        Self::get_env("GITHUB_RUN_ID")?
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| ActionsError::InputTypeError("GITHUB_RUN_ID".into(), "u64".into()))
+    }
+    /// Get the number of the workflow run (`GITHUB_RUN_NUMBER`)
+    fn get_run_number(&self) -> Result<u64, ActionsError> {
+        Self::get_env("GITHUB_RUN_NUMBER")?
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| ActionsError::InputTypeError("GITHUB_RUN_NUMBER".into(), "u64".into()))
+    }
+    /// Get the attempt of the workflow run, starting at `1` (`GITHUB_RUN_ATTEMPT`)
+    fn get_run_attempt(&self) -> Result<u32, ActionsError> {
+        Self::get_env("GITHUB_RUN_ATTEMPT")?
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| ActionsError::InputTypeError("GITHUB_RUN_ATTEMPT".into(), "u32".into()))
+    }
+    /// Get the URL of the workflow run (`{server_url}/{repository}/actions/runs/{run_id}`)
+    fn get_run_url(&self) -> Result<String, ActionsError> {
+        Ok(format!(
+            "{}/{}/actions/runs/{}",
+            self.get_server_url(),
+            self.get_repository()?,
+            self.get_run_id()?
+        ))
+    }
+    /// Get the ID of the current job (`GITHUB_JOB`)
+    fn get_job(&self) -> Result<String, ActionsError> {
+        Self::get_env("GITHUB_JOB")
+    }
+    /// Get the name of the workflow (`GITHUB_WORKFLOW`)
+    fn get_workflow(&self) -> Result<String, ActionsError> {
+        Self::get_env("GITHUB_WORKFLOW")
+    }
+    /// Get the name of the current step running the Action (`GITHUB_ACTION`)
+    fn get_action(&self) -> Result<String, ActionsError> {
+        Self::get_env("GITHUB_ACTION")
     }
 
     /// Get the full GitHub Repository (owner/repo)
     fn get_repository(&self) -> Result<String, ActionsError> {
-        Self::get_input("GITHUB_REPOSITORY")
+        Self::get_env("GITHUB_REPOSITORY")
     }
     /// Get the GitHub Repository owner name (org/user)
     fn get_repository_owner(&self) -> Result<String, ActionsError> {
-        Self::get_input("GITHUB_REPOSITORY_OWNER").or_else(|_| {
+        Self::get_env("GITHUB_REPOSITORY_OWNER").or_else(|_| {
             self.get_repository()
                 .map(|r| r.split('/').collect::<Vec<&str>>()[0].to_string())
         })
@@ -170,10 +592,65 @@ pub trait ActionTrait {
     }
     /// Get the GitHub Repository URL
     fn get_repository_url(&self) -> Result<String, ActionsError> {
-        Self::get_input("GITHUB_REPOSITORYURL")
+        Self::get_env("GITHUB_REPOSITORYURL")
     }
     /// Get the Action Triggering Author
     fn get_actor(&self) -> Result<String, ActionsError> {
-        Self::get_input("GITHUB_ACTOR")
+        Self::get_env("GITHUB_ACTOR")
     }
 }
+
+/// Create the Octocrab client of an Action (see [ActionTrait::octocrab])
+///
+/// `builtin_retries` enables the immediate retries of server errors done by Octocrab.
+#[cfg(feature = "octocrab")]
+fn octocrab_client<A: ActionTrait + ?Sized>(
+    action: &A,
+    builtin_retries: bool,
+) -> Result<octocrab::Octocrab, ActionsError> {
+    #[cfg(feature = "log")]
+    {
+        log::debug!("Creating Octocrab instance");
+        log::debug!("URL: {}", action.get_api_url());
+    }
+
+    let api_url = action.github_urls().api_base_uri();
+    let proxy = proxy::ProxySettings::from_env();
+    if proxy.proxy_for(&api_url).is_some() {
+        #[cfg(feature = "log")]
+        log::debug!("Using the proxy for the GitHub API");
+
+        let token = action.get_token().ok();
+        match &token {
+            Some(token) => A::add_mask(token),
+            #[cfg(feature = "log")]
+            None => log::warn!("No GitHub Token provided"),
+            #[cfg(not(feature = "log"))]
+            None => {}
+        }
+        let client = proxy::octocrab(&proxy, &api_url, token, builtin_retries)?;
+        return Ok(safety::guard_octocrab(client));
+    }
+
+    let mut builder = octocrab::Octocrab::builder();
+    if !builtin_retries {
+        builder.add_retry_config(octocrab::service::middleware::retry::RetryConfig::None);
+    }
+    let builder = builder.base_uri(api_url)?.add_header(
+        http::header::ACCEPT,
+        "application/vnd.github.v3+json".to_string(),
+    );
+    let client = match action.get_token() {
+        Ok(token) => {
+            A::add_mask(&token);
+            builder.personal_token(token).build()?
+        }
+        Err(_) => {
+            #[cfg(feature = "log")]
+            log::warn!("No GitHub Token provided");
+
+            builder.build()?
+        }
+    };
+    Ok(safety::guard_octocrab(client))
+}
@@ -1,7 +1,10 @@
 //! Logging utilities for GitHub Actions
 use env_logger::Builder;
-use std::env;
 use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::commands::escape_data;
 
 /// Initialise and create a `env_logger::Builder` which follows the
 /// GitHub Actions logging syntax.
@@ -16,36 +19,164 @@ pub fn init_logger() -> Builder {
     builder.filter(None, get_log_level());
     builder.write_style(env_logger::WriteStyle::Always);
 
+    let local = is_local_mode();
+    if local {
+        eprintln!(
+            "ghactions: running in local mode, annotations are written to stderr (set `{}=false` to disable)",
+            LOCAL_MODE_ENV
+        );
+    }
+
     // Custom Formatter for Actions
-    builder.format(|buf, record| match record.level().as_str() {
-        "DEBUG" => writeln!(buf, "::debug :: {}", record.args()),
-        "WARN" => writeln!(buf, "::warning :: {}", record.args()),
-        "ERROR" => {
-            writeln!(buf, "::error :: {}", record.args())
+    builder.format(move |buf, record| {
+        let message = record.args().to_string();
+        // New lines in the message would end the workflow command
+        let data = |message: String| match local {
+            true => message,
+            false => escape_data(&message),
+        };
+        let line = match record.level().as_str() {
+            // Annotations (e.g. `errorf!`) are already workflow commands
+            _ if message.starts_with("::") => message,
+            "DEBUG" => format!("::debug :: {}", data(message)),
+            "WARN" => format!("::warning :: {}", data(message)),
+            "ERROR" => format!("::error :: {}", data(message)),
+            _ => message,
+        };
+        // Annotations are not useful locally, render them as human-readable lines
+        if local {
+            if let Some(local_line) = local_annotation(&line) {
+                eprintln!("{}", local_line);
+                return Ok(());
+            }
         }
-        _ => writeln!(buf, "{}", record.args()),
+        writeln!(buf, "{}", line)
     });
 
     builder
 }
 
-/// Get the Log Level for the logger
-fn get_log_level() -> log::LevelFilter {
-    // DEBUG
-    match env::var("DEBUG") {
-        Ok(_value) => return log::LevelFilter::Debug,
-        Err(_err) => (),
+pub use crate::runner::{is_local_mode, LOCAL_MODE_ENV};
+
+/// Convert a workflow command annotation into a human-readable (coloured) line
+///
+/// Returns `None` if the line is not an annotation.
+///
+/// ```
+/// use ghactions_core::logging::local_annotation;
+///
+/// let line = local_annotation("::error file=src/main.rs,line=10,col=0 :: message").unwrap();
+/// assert!(line.contains("error src/main.rs:10:"));
+/// assert!(line.ends_with(" message"));
+/// ```
+pub fn local_annotation(line: &str) -> Option<String> {
+    let command = line.strip_prefix("::")?;
+    let (command, message) = command.split_once("::")?;
+    let (level, properties) = match command.trim().split_once(' ') {
+        Some((level, properties)) => (level, properties.trim()),
+        None => (command.trim(), ""),
+    };
+    let colour = match level {
+        "error" => "31",
+        "warning" => "33",
+        "notice" => "36",
+        "debug" => "90",
+        _ => return None,
+    };
+
+    let mut file = None;
+    let mut line_number = None;
+    for property in properties.split(',') {
+        match property.split_once('=') {
+            Some(("file", value)) => file = Some(value),
+            Some(("line", value)) => line_number = Some(value),
+            _ => {}
+        }
     }
-    // ACTIONS_RUNNER_DEBUG
-    match env::var("ACTIONS_RUNNER_DEBUG") {
-        Ok(_value) => return log::LevelFilter::Debug,
-        Err(_err) => (),
+    let location = match (file, line_number) {
+        (Some(file), Some(line)) => format!(" {}:{}:", file, line),
+        (Some(file), None) => format!(" {}:", file),
+        _ => ":".to_string(),
     };
 
-    log::LevelFilter::Info
+    Some(format!(
+        "\x1b[{}m{}{}\x1b[0m {}",
+        colour,
+        level,
+        location,
+        message.trim()
+    ))
 }
 
-/// Error for files (including line and column numbers)
+/// Get the Log Level for the logger (see [crate::runner::is_debug])
+fn get_log_level() -> log::LevelFilter {
+    match crate::runner::is_debug() {
+        true => log::LevelFilter::Debug,
+        false => log::LevelFilter::Info,
+    }
+}
+
+/// Open log groups (guard id and name), the last group is the innermost
+static GROUPS: Mutex<Vec<(usize, String)>> = Mutex::new(Vec::new());
+static GROUP_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Guard closing a log group when dropped (see [log_group])
+#[derive(Debug)]
+#[must_use = "the group is closed when the guard is dropped"]
+pub struct GroupGuard {
+    id: usize,
+}
+
+/// Open a log group which is closed when the returned guard is dropped
+///
+/// Preferred over [group!] / [groupend!] as the group is also closed when returning
+/// early (e.g. using `?`). When an outer guard is dropped before an inner guard, the
+/// inner groups are closed first so the commands stay balanced.
+///
+/// ```
+/// use ghactions::log_group;
+///
+/// fn build() -> Result<(), std::io::Error> {
+///     let _group = log_group("Build");
+///     // `::endgroup::` is emitted even if this fails
+///     std::fs::metadata("Cargo.toml")?;
+///     Ok(())
+/// }
+/// ```
+pub fn log_group(name: impl Into<String>) -> GroupGuard {
+    let name = name.into();
+    let id = GROUP_ID.fetch_add(1, Ordering::SeqCst);
+    // The group is the current phase of the Action (see `deadline`)
+    crate::deadline::set_phase(name.clone());
+    log::info!("::group::{}", escape_data(&name));
+    GROUPS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .push((id, name));
+    GroupGuard { id }
+}
+
+impl Drop for GroupGuard {
+    fn drop(&mut self) {
+        let mut groups = GROUPS.lock().unwrap_or_else(|err| err.into_inner());
+        // Already closed by an outer guard
+        let Some(index) = groups.iter().position(|(id, _)| *id == self.id) else {
+            return;
+        };
+        for _ in index..groups.len() {
+            log::info!("::endgroup::");
+        }
+        groups.truncate(index);
+        if let Some((_, parent)) = groups.last() {
+            crate::deadline::set_phase(parent.clone());
+        }
+    }
+}
+
+/// Error for files (including line and column numbers and an optional title)
+///
+/// Supports the same properties as [notice!] (e.g. `end_line` and `end_column` for
+/// multi-line annotations).
 ///
 /// # Examples
 ///
@@ -59,20 +190,132 @@ fn get_log_level() -> log::LevelFilter {
 ///     column: 0,
 ///     "Error checking file"
 /// );
+/// errorf!(
+///     file: "src/main.rs",
+///     line: 10,
+///     end_line: 12,
+///     title: "Invalid config",
+///     "Missing `{}` key",
+///     "name"
+/// );
 /// # }
 /// ```
 #[macro_export(local_inner_macros)]
 macro_rules! errorf {
     // errorf!(file: "./lib.rs", line: 0, column: 0, "Sample Error")
-    (file: $file:expr, line: $line:expr, column: $column:expr, $msg:tt) => {
-        ::log::log!(::log::Level::Info, "::error file={},line={},col={} :: {}", $file, $line, $column, $msg)
+    ($($arg:tt)+) => {
+        __annotation!(
+            @props $crate::commands::Annotation::new(
+                $crate::commands::AnnotationLevel::Error,
+                ::std::string::String::new()
+            ),
+            $($arg)+
+        )
+    };
+}
+
+/// Warning for files (including line and column numbers and an optional title)
+///
+/// Supports the same properties as [notice!] and is shown inline on pull request diffs.
+///
+/// # Examples
+///
+/// ```
+/// use ghactions::warningf;
+///
+/// # fn foo() {
+/// warningf!(
+///     file: "src/main.rs",
+///     line: 10,
+///     column: 4,
+///     title: "Unused import",
+///     "`{}` is never used",
+///     "HashMap"
+/// );
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+macro_rules! warningf {
+    // warningf!(file: "./lib.rs", line: 0, column: 0, title: "Lint", "Sample Warning")
+    ($($arg:tt)+) => {
+        __annotation!(
+            @props $crate::commands::Annotation::new(
+                $crate::commands::AnnotationLevel::Warning,
+                ::std::string::String::new()
+            ),
+            $($arg)+
+        )
+    };
+}
+
+/// Notice annotation with the optional `file`, `line`, `col` (or `column`), `end_line`,
+/// `end_column` and `title` properties (in this order) followed by the message.
+///
+/// When running locally the notice is logged as a plain line (see `commands::Annotation`).
+///
+/// # Examples
+///
+/// ```
+/// use ghactions::notice;
+///
+/// # fn foo() {
+/// notice!("Cache restored from `{}`", "linux-1234");
+/// notice!(
+///     file: "src/main.rs",
+///     line: 10,
+///     title: "Deprecated",
+///     "`init_legacy` is deprecated"
+/// );
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+macro_rules! notice {
+    // notice!(file: "src/main.rs", line: 1, "message")
+    ($($arg:tt)+) => {
+        __annotation!(
+            @props $crate::commands::Annotation::new(
+                $crate::commands::AnnotationLevel::Notice,
+                ::std::string::String::new()
+            ),
+            $($arg)+
+        )
+    };
+}
+
+/// Set the properties of an annotation and emit it (used by [errorf!], [warningf!] and [notice!])
+#[doc(hidden)]
+#[macro_export(local_inner_macros)]
+macro_rules! __annotation {
+    (@props $annotation:expr, file: $value:expr, $($rest:tt)+) => {
+        __annotation!(@props $annotation.file($value), $($rest)+)
+    };
+    (@props $annotation:expr, line: $value:expr, $($rest:tt)+) => {
+        __annotation!(@props $annotation.line($value), $($rest)+)
+    };
+    (@props $annotation:expr, col: $value:expr, $($rest:tt)+) => {
+        __annotation!(@props $annotation.column($value), $($rest)+)
+    };
+    (@props $annotation:expr, column: $value:expr, $($rest:tt)+) => {
+        __annotation!(@props $annotation.column($value), $($rest)+)
+    };
+    (@props $annotation:expr, end_line: $value:expr, $($rest:tt)+) => {
+        __annotation!(@props $annotation.end_line($value), $($rest)+)
+    };
+    (@props $annotation:expr, end_column: $value:expr, $($rest:tt)+) => {
+        __annotation!(@props $annotation.end_column($value), $($rest)+)
+    };
+    (@props $annotation:expr, title: $value:expr, $($rest:tt)+) => {
+        __annotation!(@props $annotation.title($value), $($rest)+)
+    };
+    (@props $annotation:expr, $($arg:tt)+) => {
+        $annotation.message(::std::format!($($arg)+)).emit()
     };
-    // errorf!("a {} event", "log")
-    ($($arg:tt)+) => (::log::log!($crate::Level::Error, $($arg)+))
 }
 
 /// Group Macros
 ///
+/// Prefer [log_group] / [group_scope!] which always close the group.
+///
 /// # Examples
 ///
 /// ```
@@ -85,9 +328,39 @@ macro_rules! errorf {
 #[macro_export(local_inner_macros)]
 macro_rules! group {
     // group!("Group name")
-    ($dst:expr $(,)?) => {
-        ::log::log!(log::Level::Info, "::group::{}", $dst)
-    };
+    ($dst:expr $(,)?) => {{
+        let name = $dst;
+        // The group is the current phase of the Action (see `deadline`)
+        $crate::deadline::set_phase(::std::format!("{}", name));
+        ::log::log!(
+            log::Level::Info,
+            "::group::{}",
+            $crate::commands::escape_data(&::std::format!("{}", name))
+        )
+    }};
+}
+
+/// Evaluate a block inside a log group and return its value (see [log_group])
+///
+/// # Examples
+///
+/// ```
+/// use ghactions::group_scope;
+///
+/// # fn foo() -> Result<(), std::io::Error> {
+/// let metadata = group_scope!("Read manifest", {
+///     std::fs::metadata("Cargo.toml")?
+/// });
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! group_scope {
+    // group_scope!("Group name", { ... })
+    ($name:expr, $body:block $(,)?) => {{
+        let _group = $crate::logging::log_group($name);
+        $body
+    }};
 }
 
 /// End Group Macros
@@ -102,6 +375,7 @@ macro_rules! group {
 /// # }
 
 /// ```
+#[allow(clippy::empty_line_after_doc_comments)]
 #[macro_export(local_inner_macros)]
 macro_rules! groupend {
     // group_end!()
@@ -124,24 +398,80 @@ macro_rules! groupend {
 #[macro_export(local_inner_macros)]
 macro_rules! setoutput {
     // setoutput!("name", "value")
-    ($($arg:tt)+) => {
-        {
-            use std::io::Write;
-            let output = ::std::format!("::set-output name={}::{}", $($arg)+);
-            #[cfg(feature = "log")]
-            {
-                ::log::log!(::log::Level::Info, "{}", output);
-            }
+    ($key:expr, $value:expr) => {{
+        let key = ::std::string::ToString::to_string(&$key);
+        let value = ::std::string::ToString::to_string(&$value);
+        let writer = $crate::env_files::OutputWriter::new();
+        ::log::log!(::log::Level::Debug, "Setting output `{}`", writer.key(&key));
 
-            let output_file = std::env::var("GITHUB_OUTPUT").unwrap_or_else(|_| "/tmp/github_actions.env".to_string());
-            // Append to the file
-            let mut file = std::fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(output_file)
-                .unwrap();
-            // Append to end of file
-            ::std::writeln!(file, "{}", output).unwrap();
-        }
+        writer.set(&key, &value).unwrap();
+    }};
+}
+
+/// Masks a value (e.g. a generated token) in the following lines of the job log.
+///
+/// # Examples
+///
+/// ```rust
+/// use ghactions::mask;
+///
+/// # fn foo() {
+/// mask!("ghs_installation_token");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! mask {
+    // mask!("value")
+    ($value:expr) => {{
+        $crate::commands::add_mask(::std::convert::AsRef::<str>::as_ref(&$value));
+    }};
+}
+
+/// Exports an environment variable which can be used in subsequent steps of the job.
+///
+/// Evaluates to the result of [crate::env_files::set_env] (e.g. an invalid name).
+///
+/// # Examples
+///
+/// ```rust
+/// use ghactions::setenv;
+///
+/// # fn foo() -> Result<(), ghactions::ActionsError> {
+/// setenv!("MY_TOOL_VERSION", "1.2.3")?;
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export(local_inner_macros)]
+macro_rules! setenv {
+    // setenv!("name", "value")
+    ($key:expr, $value:expr) => {{
+        let key = ::std::string::ToString::to_string(&$key);
+        let value = ::std::string::ToString::to_string(&$value);
+        ::log::log!(
+            ::log::Level::Debug,
+            "Setting environment variable `{}`",
+            key
+        );
+
+        $crate::env_files::set_env(&key, &value)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_annotation() {
+        assert_eq!(
+            local_annotation("::error file=src/main.rs,line=10,col=0 :: Error checking file"),
+            Some("\x1b[31merror src/main.rs:10:\x1b[0m Error checking file".to_string())
+        );
+        assert_eq!(
+            local_annotation("::warning :: Something happened"),
+            Some("\x1b[33mwarning:\x1b[0m Something happened".to_string())
+        );
+        assert_eq!(local_annotation("::group::Name"), None);
+        assert_eq!(local_annotation("Plain log line"), None);
     }
 }
@@ -0,0 +1,186 @@
+//! # OIDC
+//!
+//! Request the OpenID Connect token of the job, used to authenticate to cloud providers
+//! (AWS, Azure, Google Cloud, ...) without long-lived secrets.
+//!
+//! The runner only provides the request URL and token when the workflow (or job) has the
+//! `id-token: write` permission.
+//!
+//! https://docs.github.com/en/actions/security-for-github-actions/security-hardening-your-deployments/about-security-hardening-with-openid-connect
+use http::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
+use http_body_util::BodyExt;
+use serde::Deserialize;
+
+use crate::proxy::{http_client, ProxySettings};
+use crate::urls::encode_component;
+use crate::ActionsError;
+
+/// Environment variable containing the URL to request the OIDC token
+pub const ID_TOKEN_REQUEST_URL_ENV: &str = "ACTIONS_ID_TOKEN_REQUEST_URL";
+/// Environment variable containing the bearer token to request the OIDC token
+pub const ID_TOKEN_REQUEST_TOKEN_ENV: &str = "ACTIONS_ID_TOKEN_REQUEST_TOKEN";
+
+#[derive(Deserialize)]
+struct IdTokenResponse {
+    value: String,
+}
+
+/// Get the OIDC token of the job for the `audience` (e.g. `sts.amazonaws.com`)
+///
+/// The token is masked in the logs. Fails with [ActionsError::IdTokenUnavailable] when
+/// the workflow does not have the `id-token: write` permission.
+pub async fn get_id_token(audience: Option<&str>) -> Result<String, ActionsError> {
+    let var = |name: &'static str| {
+        std::env::var(name)
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .ok_or(ActionsError::IdTokenUnavailable(name))
+    };
+    let request_url = var(ID_TOKEN_REQUEST_URL_ENV)?;
+    let request_token = var(ID_TOKEN_REQUEST_TOKEN_ENV)?;
+
+    let token = request_id_token(&request_url, &request_token, audience).await?;
+    crate::commands::add_mask(&token);
+    Ok(token)
+}
+
+/// Request the OIDC token using the request URL and bearer token provided by the runner
+///
+/// The request is sent through the proxy of the runner (see [crate::proxy]).
+pub async fn request_id_token(
+    request_url: &str,
+    request_token: &str,
+    audience: Option<&str>,
+) -> Result<String, ActionsError> {
+    let url = id_token_url(request_url, audience);
+    let client = http_client(&ProxySettings::from_env())?;
+
+    let request = http::Request::get(url.as_str())
+        .header(AUTHORIZATION, format!("Bearer {}", request_token))
+        .header(ACCEPT, "application/json; api-version=2.0")
+        .header(USER_AGENT, "ghactions")
+        .body(String::new())
+        .map_err(|err| ActionsError::IdTokenError(err.to_string()))?;
+
+    let response = client
+        .request(request)
+        .await
+        .map_err(|err| ActionsError::IdTokenError(err.to_string()))?;
+    let status = response.status();
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .map_err(|err| ActionsError::IdTokenError(err.to_string()))?
+        .to_bytes();
+
+    if !status.is_success() {
+        return Err(ActionsError::IdTokenError(format!(
+            "HTTP {} ({})",
+            status,
+            String::from_utf8_lossy(&body).trim()
+        )));
+    }
+    let response: IdTokenResponse = serde_json::from_slice(&body)
+        .map_err(|err| ActionsError::IdTokenError(format!("invalid response: {}", err)))?;
+    Ok(response.value)
+}
+
+/// Add the (percent encoded) `audience` query parameter to the request URL
+fn id_token_url(request_url: &str, audience: Option<&str>) -> String {
+    match audience {
+        Some(audience) => {
+            let separator = match request_url.contains('?') {
+                true => '&',
+                false => '?',
+            };
+            format!(
+                "{}{}audience={}",
+                request_url,
+                separator,
+                encode_component(audience)
+            )
+        }
+        None => request_url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_id_token_url() {
+        assert_eq!(
+            id_token_url(
+                "https://token.actions/abc?api-version=2.0",
+                Some("sts.amazonaws.com")
+            ),
+            "https://token.actions/abc?api-version=2.0&audience=sts.amazonaws.com"
+        );
+        assert_eq!(
+            id_token_url(
+                "https://token.actions/abc",
+                Some("api://AzureADTokenExchange")
+            ),
+            "https://token.actions/abc?audience=api%3A%2F%2FAzureADTokenExchange"
+        );
+        assert_eq!(
+            id_token_url("https://token.actions/abc", None),
+            "https://token.actions/abc"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_id_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/token"))
+            .and(query_param("audience", "sts.amazonaws.com"))
+            .and(header("authorization", "Bearer request-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "count": 1,
+                "value": "eyJhbGciOiJSUzI1NiJ9.payload.signature",
+            })))
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/token?api-version=2.0", server.uri());
+        let token = request_id_token(&url, "request-token", Some("sts.amazonaws.com"))
+            .await
+            .unwrap();
+        assert_eq!(token, "eyJhbGciOiJSUzI1NiJ9.payload.signature");
+    }
+
+    #[tokio::test]
+    async fn test_request_id_token_forbidden() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/token"))
+            .respond_with(ResponseTemplate::new(403).set_body_string("Forbidden"))
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/token", server.uri());
+        assert_eq!(
+            request_id_token(&url, "request-token", None).await,
+            Err(ActionsError::IdTokenError(
+                "HTTP 403 Forbidden (Forbidden)".to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_id_token_unavailable() {
+        std::env::remove_var(ID_TOKEN_REQUEST_URL_ENV);
+        let err = get_id_token(None).await.unwrap_err();
+        assert_eq!(
+            err,
+            ActionsError::IdTokenUnavailable(ID_TOKEN_REQUEST_URL_ENV)
+        );
+        assert!(err.to_string().contains("id-token: write"));
+    }
+}
@@ -0,0 +1,362 @@
+//! # Reports
+//!
+//! Import annotations from report formats produced by other tools:
+//!
+//! - `cargo --message-format=json` diagnostics ([Annotations::from_cargo_json])
+//! - JUnit XML test reports ([Annotations::from_junit])
+//!
+//! File paths are rewritten relative to `GITHUB_WORKSPACE` so the annotations link to the
+//! files in the repository.
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+use quick_xml::events::{BytesStart, Event};
+use serde::Deserialize;
+
+pub use crate::commands::{Annotation, AnnotationLevel, MAX_MESSAGE_LENGTH};
+use crate::ActionsError;
+
+/// Maximum number of annotations per level GitHub displays for a step
+pub const MAX_ANNOTATIONS_PER_LEVEL: usize = 10;
+
+/// Collection of annotations
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Annotations {
+    /// Annotations
+    pub annotations: Vec<Annotation>,
+}
+
+impl Annotations {
+    /// Import the compiler diagnostics from `cargo --message-format=json` output
+    ///
+    /// Lines which are not JSON or not compiler messages are ignored. Diagnostics with
+    /// multiple primary spans create one annotation per span, diagnostics without spans
+    /// (e.g. `aborting due to previous error`) are skipped.
+    pub fn from_cargo_json(reader: impl BufRead) -> Result<Self, ActionsError> {
+        let workspace = workspace();
+        let mut annotations = Vec::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            let message = match serde_json::from_str::<CargoMessage>(&line) {
+                Ok(CargoMessage {
+                    reason,
+                    message: Some(message),
+                }) if reason == "compiler-message" => message,
+                _ => continue,
+            };
+            let level = match message.level.as_str() {
+                "error" | "error: internal compiler error" => AnnotationLevel::Error,
+                "warning" => AnnotationLevel::Warning,
+                _ => AnnotationLevel::Notice,
+            };
+
+            let mut text = message.message.clone();
+            for child in message.children.iter() {
+                if !child.message.is_empty() {
+                    text.push_str(&format!("\n{}: {}", child.level, child.message));
+                }
+            }
+
+            for span in message.spans.iter().filter(|span| span.is_primary) {
+                let single_line = span.line_start == span.line_end;
+                annotations.push(Annotation {
+                    level,
+                    title: message.code.as_ref().map(|code| code.code.clone()),
+                    file: Some(relative_to(&span.file_name, workspace.as_deref())),
+                    line: Some(span.line_start),
+                    end_line: (!single_line).then_some(span.line_end),
+                    column: Some(span.column_start),
+                    end_column: single_line.then_some(span.column_end),
+                    message: text.clone(),
+                });
+            }
+        }
+        Ok(Self { annotations })
+    }
+
+    /// Import the test failures (and errors) from a JUnit XML report
+    ///
+    /// The test name is used as the title, the `file` and `line` attributes of the test
+    /// case are used as the location when present.
+    pub fn from_junit(reader: impl BufRead) -> Result<Self, ActionsError> {
+        let workspace = workspace();
+
+        let mut annotations = Vec::new();
+        let mut reader = quick_xml::Reader::from_reader(reader);
+        let mut buffer = Vec::new();
+
+        let mut testcase: Option<Annotation> = None;
+        let mut failure: Option<Annotation> = None;
+
+        loop {
+            match reader.read_event_into(&mut buffer)? {
+                Event::Start(element) | Event::Empty(element)
+                    if element.name().as_ref() == b"testcase" =>
+                {
+                    let mut annotation = Annotation::new(AnnotationLevel::Error, "");
+                    annotation.title = attribute(&element, b"name")?;
+                    annotation.file = attribute(&element, b"file")?
+                        .map(|file| relative_to(&file, workspace.as_deref()));
+                    annotation.line =
+                        attribute(&element, b"line")?.and_then(|line| line.parse().ok());
+                    testcase = Some(annotation);
+                }
+                Event::Start(element) if is_failure(&element) => {
+                    failure = failure_annotation(&testcase, &element)?;
+                }
+                Event::Empty(element) if is_failure(&element) => {
+                    annotations.extend(failure_annotation(&testcase, &element)?);
+                }
+                Event::Text(text) => {
+                    if let Some(failure) = failure.as_mut() {
+                        let text = text.unescape()?;
+                        let text = text.trim();
+                        if !text.is_empty() {
+                            if !failure.message.is_empty() {
+                                failure.message.push_str("\n\n");
+                            }
+                            failure.message.push_str(text);
+                        }
+                    }
+                }
+                Event::CData(data) => {
+                    if let Some(failure) = failure.as_mut() {
+                        let text = String::from_utf8_lossy(&data);
+                        if !failure.message.is_empty() {
+                            failure.message.push_str("\n\n");
+                        }
+                        failure.message.push_str(text.trim());
+                    }
+                }
+                Event::End(element) if is_failure_name(element.name().as_ref()) => {
+                    annotations.extend(failure.take());
+                }
+                Event::End(element) if element.name().as_ref() == b"testcase" => {
+                    testcase = None;
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buffer.clear();
+        }
+        Ok(Self { annotations })
+    }
+
+    /// Number of annotations
+    pub fn len(&self) -> usize {
+        self.annotations.len()
+    }
+
+    /// Check if there are no annotations
+    pub fn is_empty(&self) -> bool {
+        self.annotations.is_empty()
+    }
+
+    /// Check if any of the annotations is an error
+    pub fn has_errors(&self) -> bool {
+        self.annotations
+            .iter()
+            .any(|annotation| annotation.level == AnnotationLevel::Error)
+    }
+
+    /// Render the workflow command lines
+    ///
+    /// GitHub only displays [MAX_ANNOTATIONS_PER_LEVEL] annotations per level for a step,
+    /// the remaining annotations are rendered as plain log lines followed by a count.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut counts: HashMap<AnnotationLevel, usize> = HashMap::new();
+
+        for annotation in self.annotations.iter() {
+            let count = counts.entry(annotation.level).or_default();
+            *count += 1;
+            if *count <= MAX_ANNOTATIONS_PER_LEVEL {
+                lines.push(annotation.to_string());
+                continue;
+            }
+
+            let location = match (&annotation.file, annotation.line) {
+                (Some(file), Some(line)) => format!(" {}:{}:", file.display(), line),
+                (Some(file), None) => format!(" {}:", file.display()),
+                _ => ":".to_string(),
+            };
+            lines.push(format!(
+                "{}{} {}",
+                annotation.level, location, annotation.message
+            ));
+        }
+
+        for level in [
+            AnnotationLevel::Error,
+            AnnotationLevel::Warning,
+            AnnotationLevel::Notice,
+        ] {
+            let count = counts.get(&level).copied().unwrap_or_default();
+            if count > MAX_ANNOTATIONS_PER_LEVEL {
+                lines.push(format!(
+                    "ghactions: {} more {} annotation(s) not displayed (limit of {} per step)",
+                    count - MAX_ANNOTATIONS_PER_LEVEL,
+                    level,
+                    MAX_ANNOTATIONS_PER_LEVEL
+                ));
+            }
+        }
+        lines
+    }
+
+    /// Emit the annotations using the logger (see [Annotations::lines])
+    pub fn emit(&self) {
+        for line in self.lines() {
+            log::info!("{}", line);
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<CargoDiagnostic>,
+}
+
+#[derive(Deserialize)]
+struct CargoDiagnostic {
+    message: String,
+    level: String,
+    code: Option<CargoCode>,
+    #[serde(default)]
+    spans: Vec<CargoSpan>,
+    #[serde(default)]
+    children: Vec<CargoDiagnostic>,
+}
+
+#[derive(Deserialize)]
+struct CargoCode {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct CargoSpan {
+    file_name: String,
+    line_start: usize,
+    line_end: usize,
+    column_start: usize,
+    column_end: usize,
+    is_primary: bool,
+}
+
+fn is_failure_name(name: &[u8]) -> bool {
+    name == b"failure" || name == b"error"
+}
+
+fn is_failure(element: &BytesStart) -> bool {
+    is_failure_name(element.name().as_ref())
+}
+
+/// Create the annotation of a failure inside a test case
+fn failure_annotation(
+    testcase: &Option<Annotation>,
+    element: &BytesStart,
+) -> Result<Option<Annotation>, ActionsError> {
+    Ok(testcase.as_ref().map(|testcase| Annotation {
+        message: attribute(element, b"message")
+            .ok()
+            .flatten()
+            .unwrap_or_default(),
+        ..testcase.clone()
+    }))
+}
+
+/// Get the (unescaped) value of an XML attribute
+fn attribute(element: &BytesStart, name: &[u8]) -> Result<Option<String>, ActionsError> {
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(quick_xml::Error::from)?;
+        if attribute.key.as_ref() == name {
+            let value = attribute.unescape_value()?;
+            return Ok(Some(value.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// Get the workspace (`GITHUB_WORKSPACE`)
+fn workspace() -> Option<PathBuf> {
+    std::env::var_os("GITHUB_WORKSPACE")
+        .filter(|workspace| !workspace.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Make an absolute path inside the workspace relative to the workspace
+fn relative_to(path: &str, workspace: Option<&Path>) -> PathBuf {
+    let path = Path::new(path);
+    workspace
+        .and_then(|workspace| path.strip_prefix(workspace).ok())
+        .unwrap_or(path)
+        .to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_to() {
+        let workspace = Path::new("/home/runner/work/repo/repo");
+        assert_eq!(
+            relative_to("/home/runner/work/repo/repo/src/main.rs", Some(workspace)),
+            PathBuf::from("src/main.rs")
+        );
+        assert_eq!(
+            relative_to("src/main.rs", Some(workspace)),
+            PathBuf::from("src/main.rs")
+        );
+        assert_eq!(
+            relative_to("/rustc/library/core/src/lib.rs", Some(workspace)),
+            PathBuf::from("/rustc/library/core/src/lib.rs")
+        );
+    }
+
+    #[test]
+    fn test_annotation_display() {
+        let mut annotation = Annotation::new(AnnotationLevel::Warning, "100% done\nnext");
+        assert_eq!(annotation.to_string(), "::warning::100%25 done%0Anext");
+
+        annotation.file = Some(PathBuf::from("src/main.rs"));
+        annotation.line = Some(10);
+        annotation.title = Some("tests::a, b".to_string());
+        assert_eq!(
+            annotation.to_string(),
+            "::warning file=src/main.rs,line=10,title=tests%3A%3Aa%2C b::100%25 done%0Anext"
+        );
+
+        annotation.message = "x".repeat(MAX_MESSAGE_LENGTH + 10);
+        assert!(annotation
+            .to_string()
+            .ends_with(&format!("::{}...", "x".repeat(MAX_MESSAGE_LENGTH))));
+    }
+
+    #[test]
+    fn test_lines_budget() {
+        let mut annotations = Annotations::default();
+        for index in 0..12 {
+            annotations.annotations.push(Annotation::new(
+                AnnotationLevel::Error,
+                format!("error {}", index),
+            ));
+        }
+        annotations
+            .annotations
+            .push(Annotation::new(AnnotationLevel::Warning, "warning"));
+
+        let lines = annotations.lines();
+        assert_eq!(lines.len(), 14);
+        assert_eq!(lines[9], "::error::error 9");
+        assert_eq!(lines[10], "error: error 10");
+        assert_eq!(lines[12], "::warning::warning");
+        assert_eq!(
+            lines[13],
+            "ghactions: 2 more error annotation(s) not displayed (limit of 10 per step)"
+        );
+    }
+}
@@ -6,10 +6,12 @@ use std::{
     path::{Component, PathBuf},
 };
 
+use serde::Serialize;
+
 use crate::ActionsError;
 
 /// RepositoryReference is a struct that holds the owner, name, path and reference of a repository
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct RepositoryReference {
     /// Repository owner
     pub owner: String,
@@ -167,6 +169,18 @@ mod tests {
         );
     }
     #[test]
+    fn test_hash_dedup() {
+        let references: std::collections::HashSet<RepositoryReference> = [
+            "geekmasher/ghactions@main",
+            "geekmasher/ghactions@main",
+            "geekmasher/ghactions@v1",
+        ]
+        .iter()
+        .map(|reference| RepositoryReference::parse(reference).unwrap())
+        .collect();
+        assert_eq!(references.len(), 2);
+    }
+    #[test]
     fn test_owner_repo_path_traversal() {
         let repo_ref = RepositoryReference::parse("geekmasher/ghactions/../test@main");
         assert!(repo_ref.is_err());
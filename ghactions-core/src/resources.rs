@@ -0,0 +1,249 @@
+//! # Resources
+//!
+//! Locate data files (templates, scripts, ...) shipped alongside the `action.yml` file and
+//! resolve paths inside the workspace (`GITHUB_WORKSPACE`).
+use std::path::{Component, Path, PathBuf};
+
+use crate::ActionsError;
+
+/// Action file names used to detect the Action directory
+const ACTION_FILES: [&str; 2] = ["action.yml", "action.yaml"];
+
+/// Get the directory of the Action
+///
+/// Resolution order:
+///
+/// 1. `GITHUB_ACTION_PATH` (set by the runner for composite Action steps)
+/// 2. The directory of the Action binary (if it contains an `action.yml` file)
+/// 3. The current working directory
+pub fn action_path() -> Result<PathBuf, ActionsError> {
+    if let Some(path) = std::env::var_os("GITHUB_ACTION_PATH")
+        .map(PathBuf::from)
+        .filter(|path| path.is_dir())
+    {
+        return Ok(path);
+    }
+
+    if let Some(path) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        .filter(|dir| ACTION_FILES.iter().any(|file| dir.join(file).is_file()))
+    {
+        return Ok(path);
+    }
+
+    Ok(std::env::current_dir()?)
+}
+
+/// Get the path of a resource relative to the Action directory (see [action_path])
+///
+/// Absolute paths and paths (or symlinks) escaping the Action directory are rejected and
+/// the resource has to exist.
+pub fn action_resource(relative: impl AsRef<Path>) -> Result<PathBuf, ActionsError> {
+    resolve_resource(&action_path()?, relative.as_ref())
+}
+
+fn resolve_resource(root: &Path, relative: &Path) -> Result<PathBuf, ActionsError> {
+    let escaping = relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir));
+    if escaping {
+        return Err(ActionsError::FileError {
+            path: relative.to_path_buf(),
+            kind: std::io::ErrorKind::PermissionDenied,
+            message: "resources must be relative to the Action directory".to_string(),
+        });
+    }
+
+    let path = root.join(relative);
+    let file_error = |err: std::io::Error| ActionsError::FileError {
+        path: path.clone(),
+        kind: err.kind(),
+        message: err.to_string(),
+    };
+    let resolved = std::fs::canonicalize(&path).map_err(file_error)?;
+    let root = std::fs::canonicalize(root).map_err(file_error)?;
+
+    if !resolved.starts_with(&root) {
+        return Err(ActionsError::FileError {
+            path,
+            kind: std::io::ErrorKind::PermissionDenied,
+            message: format!(
+                "resource resolves outside of the Action directory (`{}`)",
+                resolved.display()
+            ),
+        });
+    }
+    Ok(path)
+}
+
+/// Get the workspace of the job (`GITHUB_WORKSPACE`)
+pub fn get_workspace() -> Result<PathBuf, ActionsError> {
+    std::env::var_os("GITHUB_WORKSPACE")
+        .filter(|workspace| !workspace.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| ActionsError::InputError("GITHUB_WORKSPACE".to_string()))
+}
+
+/// Resolve a path (e.g. from an input) inside the workspace (see [get_workspace])
+///
+/// Relative paths are joined to the workspace and paths (or symlinks) escaping the
+/// workspace are rejected. Existing paths are canonicalised, the path does not have to
+/// exist (e.g. an output file).
+pub fn resolve_workspace_path(path: impl AsRef<Path>) -> Result<PathBuf, ActionsError> {
+    resolve_within(&get_workspace()?, path.as_ref())
+}
+
+fn resolve_within(root: &Path, path: &Path) -> Result<PathBuf, ActionsError> {
+    let escaping = |resolved: &Path| ActionsError::FileError {
+        path: path.to_path_buf(),
+        kind: std::io::ErrorKind::PermissionDenied,
+        message: format!(
+            "path resolves outside of the workspace (`{}`)",
+            resolved.display()
+        ),
+    };
+
+    let root = normalize(root).ok_or_else(|| escaping(root))?;
+    let joined = root.join(path);
+    let normalized = normalize(&joined).ok_or_else(|| escaping(&joined))?;
+    if !normalized.starts_with(&root) {
+        return Err(escaping(&normalized));
+    }
+
+    // Symlinks are only resolved when the path exists
+    if normalized.exists() {
+        let file_error = |err: std::io::Error| ActionsError::FileError {
+            path: normalized.clone(),
+            kind: err.kind(),
+            message: err.to_string(),
+        };
+        let resolved = std::fs::canonicalize(&normalized).map_err(file_error)?;
+        let root = std::fs::canonicalize(&root).map_err(file_error)?;
+        if !resolved.starts_with(&root) {
+            return Err(escaping(&resolved));
+        }
+        return Ok(resolved);
+    }
+    Ok(normalized)
+}
+
+/// Lexically normalize a path (`None` if it goes above the root)
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) {
+                    return None;
+                }
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    Some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_resource() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir(root.path().join("templates")).unwrap();
+        std::fs::write(root.path().join("templates/summary.md"), "# Summary").unwrap();
+
+        assert_eq!(
+            resolve_resource(root.path(), Path::new("templates/summary.md")),
+            Ok(root.path().join("templates/summary.md"))
+        );
+
+        // Missing
+        assert!(matches!(
+            resolve_resource(root.path(), Path::new("missing.json")),
+            Err(ActionsError::FileError {
+                kind: std::io::ErrorKind::NotFound,
+                ..
+            })
+        ));
+
+        // Traversal
+        for path in ["../secret", "templates/../../secret", "/etc/passwd"] {
+            assert!(matches!(
+                resolve_resource(root.path(), Path::new(path)),
+                Err(ActionsError::FileError {
+                    kind: std::io::ErrorKind::PermissionDenied,
+                    ..
+                })
+            ));
+        }
+
+        // Symlink escaping the Action directory
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret"), "secret").unwrap();
+        std::os::unix::fs::symlink(outside.path().join("secret"), root.path().join("link"))
+            .unwrap();
+        assert!(matches!(
+            resolve_resource(root.path(), Path::new("link")),
+            Err(ActionsError::FileError {
+                kind: std::io::ErrorKind::PermissionDenied,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_resolve_within() {
+        let root = tempfile::tempdir().unwrap();
+        let canonical = std::fs::canonicalize(root.path()).unwrap();
+        std::fs::create_dir(root.path().join("src")).unwrap();
+        std::fs::write(root.path().join("src/lib.rs"), "").unwrap();
+
+        // Existing paths are canonicalised
+        assert_eq!(
+            resolve_within(root.path(), Path::new("./src/../src/lib.rs")),
+            Ok(canonical.join("src/lib.rs"))
+        );
+        assert_eq!(
+            resolve_within(root.path(), &root.path().join("src")),
+            Ok(canonical.join("src"))
+        );
+        // Missing paths are normalized
+        assert_eq!(
+            resolve_within(root.path(), Path::new("target/report.json")),
+            Ok(root.path().join("target/report.json"))
+        );
+
+        // Traversal
+        for path in ["../../etc/passwd", "src/../../secret", "/etc/passwd", ".."] {
+            assert!(
+                matches!(
+                    resolve_within(root.path(), Path::new(path)),
+                    Err(ActionsError::FileError {
+                        kind: std::io::ErrorKind::PermissionDenied,
+                        ..
+                    })
+                ),
+                "{} is not rejected",
+                path
+            );
+        }
+
+        // Symlink escaping the workspace
+        let outside = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.path().join("link")).unwrap();
+        assert!(matches!(
+            resolve_within(root.path(), Path::new("link")),
+            Err(ActionsError::FileError {
+                kind: std::io::ErrorKind::PermissionDenied,
+                ..
+            })
+        ));
+    }
+}
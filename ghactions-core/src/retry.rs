@@ -0,0 +1,511 @@
+//! # Retry
+//!
+//! Retry GitHub API requests failing with a transient error (`502`, `503`, `504`, `429` and
+//! secondary rate limits) using an exponential backoff with jitter.
+//!
+//! [with_retry] retries an operation (e.g. an Octocrab call) and
+//! [crate::ActionTrait::octocrab_retrying] creates a client retrying each request. The delay
+//! requested by the server (`Retry-After` or `x-ratelimit-reset`) is used over the backoff
+//! when present.
+//!
+//! ```no_run
+//! use ghactions_core::retry::{with_retry, RetryPolicy};
+//!
+//! # async fn run(octocrab: octocrab::Octocrab) -> Result<(), octocrab::Error> {
+//! let repo = with_retry(RetryPolicy::default(), || async {
+//!     octocrab.repos("42ByteLabs", "ghactions").get().await
+//! })
+//! .await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use http::{HeaderMap, StatusCode};
+
+use crate::ActionsError;
+
+/// Retry policy (number of attempts, backoff and total deadline)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts (including the first one)
+    pub max_attempts: u32,
+    /// Delay before the first retry (doubled for each retry)
+    pub initial_delay: Duration,
+    /// Maximum delay between two attempts
+    pub max_delay: Duration,
+    /// Maximum total time spent retrying (no retry is started past the deadline)
+    pub deadline: Option<Duration>,
+    /// Randomise the delays (between half and all of the backoff)
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            deadline: Some(Duration::from_secs(300)),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Get the backoff delay after a number of failed attempts (starting at 1)
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ghactions_core::retry::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy { jitter: false, ..Default::default() };
+    /// assert_eq!(policy.backoff(1), Duration::from_secs(1));
+    /// assert_eq!(policy.backoff(3), Duration::from_secs(4));
+    /// assert_eq!(policy.backoff(10), Duration::from_secs(60));
+    /// ```
+    pub fn backoff(&self, failures: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+        let delay = self
+            .initial_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+        match self.jitter {
+            true => delay / 2 + delay.mul_f64(random_fraction() / 2.0),
+            false => delay,
+        }
+    }
+
+    /// Get the delay before the next attempt (`None` if the operation is not retried)
+    ///
+    /// `elapsed` is the time spent since the first attempt and `requested` the delay
+    /// requested by the server.
+    fn next_delay(
+        &self,
+        failures: u32,
+        elapsed: Duration,
+        requested: Option<Duration>,
+    ) -> Option<Duration> {
+        if failures >= self.max_attempts {
+            return None;
+        }
+        let delay = requested.unwrap_or_else(|| self.backoff(failures));
+        match self.deadline {
+            Some(deadline) if elapsed + delay > deadline => None,
+            _ => Some(delay),
+        }
+    }
+}
+
+/// Error of an operation which can be retried
+pub trait RetryableError {
+    /// If the error is transient (the operation can be retried)
+    fn is_retryable(&self) -> bool;
+
+    /// Delay requested by the server before retrying
+    fn retry_after(&self) -> Option<Duration> {
+        None
+    }
+}
+
+impl RetryableError for octocrab::Error {
+    fn is_retryable(&self) -> bool {
+        match self {
+            octocrab::Error::GitHub { source, .. } => {
+                retryable_status(source.status_code, &source.message)
+            }
+            // Errors blocked by the safety policy are not transient
+            octocrab::Error::Service { source, .. } => !source.is::<ActionsError>(),
+            octocrab::Error::Hyper { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+impl RetryableError for ActionsError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            ActionsError::OctocrabError(err) => err.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+/// Run an operation, retrying it while it fails with a transient error
+///
+/// Octocrab errors do not include the response headers, the delays requested by the server
+/// are only honoured by the clients created using [crate::ActionTrait::octocrab_retrying].
+/// The last error is returned when the attempts or the deadline of the policy are exhausted.
+pub async fn with_retry<T, E, F, Fut>(policy: RetryPolicy, mut operation: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: RetryableError + std::fmt::Display,
+{
+    let start = tokio::time::Instant::now();
+    let mut failures = 0;
+    loop {
+        let err = match operation().await {
+            Ok(value) => return Ok(value),
+            Err(err) if err.is_retryable() => err,
+            Err(err) => return Err(err),
+        };
+        failures += 1;
+
+        match policy.next_delay(failures, start.elapsed(), err.retry_after()) {
+            Some(delay) => {
+                #[cfg(feature = "log")]
+                log::warn!(
+                    "Attempt {} failed, retrying in {:?}: {}",
+                    failures,
+                    delay,
+                    err
+                );
+                tokio::time::sleep(delay).await;
+            }
+            None => return Err(err),
+        }
+    }
+}
+
+/// If a response status (and GitHub error message) is a transient error
+fn retryable_status(status: StatusCode, message: &str) -> bool {
+    match status {
+        StatusCode::TOO_MANY_REQUESTS
+        | StatusCode::BAD_GATEWAY
+        | StatusCode::SERVICE_UNAVAILABLE
+        | StatusCode::GATEWAY_TIMEOUT => true,
+        // Secondary rate limits (other `403`s are permission errors)
+        StatusCode::FORBIDDEN => message.to_lowercase().contains("rate limit"),
+        _ => false,
+    }
+}
+
+/// Get the delay requested using the `Retry-After` or `x-ratelimit-reset` headers
+///
+/// `Retry-After` is in seconds and `x-ratelimit-reset` is a UTC epoch (in seconds), used when
+/// `x-ratelimit-remaining` is `0`.
+fn requested_delay(headers: &HeaderMap, now: SystemTime) -> Option<Duration> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+    };
+    if let Some(seconds) = header("retry-after") {
+        return Some(Duration::from_secs(seconds));
+    }
+    if header("x-ratelimit-remaining") == Some(0) {
+        let reset = UNIX_EPOCH + Duration::from_secs(header("x-ratelimit-reset")?);
+        return Some(reset.duration_since(now).unwrap_or_default());
+    }
+    None
+}
+
+/// Random number in `[0, 1)` (used for the jitter, not cryptographically secure)
+fn random_fraction() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+pub(crate) use self::service::wrap;
+
+mod service {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use std::time::SystemTime;
+
+    use bytes::Bytes;
+    use http::{Request, Response};
+    use http_body_util::combinators::BoxBody;
+    use tower::Service;
+
+    use super::{requested_delay, retryable_status, RetryPolicy, RetryableError};
+
+    /// Service sending requests using an Octocrab client, retrying the transient errors
+    #[derive(Clone)]
+    struct RetryService {
+        client: octocrab::Octocrab,
+        policy: RetryPolicy,
+    }
+
+    impl Service<Request<String>> for RetryService {
+        type Response = Response<BoxBody<Bytes, octocrab::Error>>;
+        type Error = octocrab::Error;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: Request<String>) -> Self::Future {
+            let client = self.client.clone();
+            let policy = self.policy.clone();
+            Box::pin(async move {
+                let start = tokio::time::Instant::now();
+                let mut failures = 0;
+                loop {
+                    let mut attempt = Request::new(request.body().clone());
+                    *attempt.method_mut() = request.method().clone();
+                    *attempt.uri_mut() = request.uri().clone();
+                    *attempt.headers_mut() = request.headers().clone();
+
+                    let delay = match client.execute(attempt).await {
+                        Ok(response) => {
+                            let requested = requested_delay(response.headers(), SystemTime::now());
+                            // `403`s requesting a delay are rate limits (the message of
+                            // secondary rate limits is in the body)
+                            let retryable = retryable_status(response.status(), "")
+                                || (response.status() == http::StatusCode::FORBIDDEN
+                                    && requested.is_some());
+                            if !retryable {
+                                return Ok(response);
+                            }
+                            failures += 1;
+                            match policy.next_delay(failures, start.elapsed(), requested) {
+                                Some(delay) => delay,
+                                None => return Ok(response),
+                            }
+                        }
+                        Err(err) if err.is_retryable() => {
+                            failures += 1;
+                            match policy.next_delay(failures, start.elapsed(), None) {
+                                Some(delay) => delay,
+                                None => return Err(err),
+                            }
+                        }
+                        Err(err) => return Err(err),
+                    };
+
+                    #[cfg(feature = "log")]
+                    log::warn!(
+                        "`{} {}` failed (attempt {}), retrying in {:?}",
+                        request.method(),
+                        request.uri().path(),
+                        failures,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+            })
+        }
+    }
+
+    /// Wrap an Octocrab client so transient errors are retried using `policy`
+    pub(crate) fn wrap(client: octocrab::Octocrab, policy: RetryPolicy) -> octocrab::Octocrab {
+        octocrab::OctocrabBuilder::new_empty()
+            .with_service(RetryService { client, policy })
+            .with_auth(octocrab::AuthState::None)
+            .build()
+            .unwrap_or_else(|never| match never {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            jitter: false,
+            ..Default::default()
+        }
+    }
+
+    async fn flaky_server(retry_after: Option<&str>) -> MockServer {
+        let server = MockServer::start().await;
+        let mut unavailable =
+            ResponseTemplate::new(503).set_body_json(json!({ "message": "Service Unavailable" }));
+        if let Some(retry_after) = retry_after {
+            unavailable = unavailable.insert_header("retry-after", retry_after);
+        }
+        Mock::given(method("GET"))
+            .and(path("/repos/octo/repo"))
+            .respond_with(unavailable)
+            .up_to_n_times(2)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/octo/repo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": 1 })))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn client(server: &MockServer) -> octocrab::Octocrab {
+        let mut builder = octocrab::Octocrab::builder();
+        builder.add_retry_config(octocrab::service::middleware::retry::RetryConfig::None);
+        builder.base_uri(server.uri()).unwrap().build().unwrap()
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = policy();
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(7), Duration::from_secs(60));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(60));
+
+        let jitter = RetryPolicy::default();
+        for _ in 0..100 {
+            let delay = jitter.backoff(3);
+            assert!(delay >= Duration::from_secs(2) && delay <= Duration::from_secs(4));
+        }
+
+        // Attempts and deadline
+        assert_eq!(policy.next_delay(4, Duration::ZERO, None), None);
+        assert_eq!(
+            policy.next_delay(1, Duration::ZERO, Some(Duration::from_secs(30))),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            policy.next_delay(1, Duration::from_secs(280), Some(Duration::from_secs(30))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_requested_delay() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let headers = |values: &[(&'static str, &'static str)]| {
+            let mut headers = HeaderMap::new();
+            for (name, value) in values {
+                headers.insert(*name, value.parse().unwrap());
+            }
+            headers
+        };
+
+        assert_eq!(
+            requested_delay(&headers(&[("retry-after", "7")]), now),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(
+            requested_delay(
+                &headers(&[
+                    ("x-ratelimit-remaining", "0"),
+                    ("x-ratelimit-reset", "1700000042")
+                ]),
+                now
+            ),
+            Some(Duration::from_secs(42))
+        );
+        // The limit is not exhausted
+        assert_eq!(
+            requested_delay(
+                &headers(&[
+                    ("x-ratelimit-remaining", "10"),
+                    ("x-ratelimit-reset", "1700000042")
+                ]),
+                now
+            ),
+            None
+        );
+        assert_eq!(
+            requested_delay(&headers(&[("retry-after", "soon")]), now),
+            None
+        );
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        assert!(retryable_status(StatusCode::SERVICE_UNAVAILABLE, ""));
+        assert!(retryable_status(
+            StatusCode::FORBIDDEN,
+            "You have exceeded a secondary rate limit"
+        ));
+        assert!(!retryable_status(
+            StatusCode::FORBIDDEN,
+            "Resource not accessible"
+        ));
+        assert!(!retryable_status(StatusCode::NOT_FOUND, ""));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_with_retry() {
+        let server = flaky_server(None).await;
+        let client = client(&server);
+
+        let start = tokio::time::Instant::now();
+        let mut attempts = 0;
+        let repo: serde_json::Value = with_retry(policy(), || {
+            attempts += 1;
+            client.get("/repos/octo/repo", None::<&()>)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(repo["id"], 1);
+        assert_eq!(attempts, 3);
+        // Backoff of 1s then 2s
+        assert_eq!(start.elapsed().as_secs(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_with_retry_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(404).set_body_json(json!({ "message": "Not Found" })),
+            )
+            .mount(&server)
+            .await;
+        let client = client(&server);
+
+        // Errors which are not transient are not retried
+        let mut attempts = 0;
+        let result: Result<serde_json::Value, _> = with_retry(policy(), || {
+            attempts += 1;
+            client.get("/repos/octo/repo", None::<&()>)
+        })
+        .await;
+        assert!(!result.unwrap_err().is_retryable());
+        assert_eq!(attempts, 1);
+
+        // The attempts are limited
+        let server = flaky_server(None).await;
+        let client = self::client(&server);
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            ..policy()
+        };
+        let mut attempts = 0;
+        let result: Result<serde_json::Value, ActionsError> = with_retry(policy, || {
+            attempts += 1;
+            let client = client.clone();
+            async move { Ok(client.get("/repos/octo/repo", None::<&()>).await?) }
+        })
+        .await;
+        assert!(result.unwrap_err().is_retryable());
+        assert_eq!(attempts, 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retrying_client() {
+        let server = flaky_server(Some("5")).await;
+        let client = wrap(client(&server), policy());
+
+        let start = tokio::time::Instant::now();
+        let repo: serde_json::Value = client.get("/repos/octo/repo", None::<&()>).await.unwrap();
+
+        assert_eq!(repo["id"], 1);
+        assert_eq!(server.received_requests().await.unwrap().len(), 3);
+        // `Retry-After` is used over the backoff
+        assert_eq!(start.elapsed().as_secs(), 10);
+    }
+}
@@ -0,0 +1,193 @@
+//! # Runner
+//!
+//! Runner version detection, capability probes, debug mode and local mode.
+use crate::ActionsError;
+
+/// First runner version which supports the `GITHUB_OUTPUT` / `GITHUB_STATE` files
+const OUTPUT_FILES_VERSION: (u64, u64, u64) = (2, 297, 0);
+
+/// Get the version of the runner executing the Action
+///
+/// Uses `RUNNER_VERSION` or `ACTIONS_RUNNER_VERSION`, returns `None` if unknown.
+pub fn runner_version() -> Option<String> {
+    ["RUNNER_VERSION", "ACTIONS_RUNNER_VERSION"]
+        .iter()
+        .find_map(|name| crate::env::var(name))
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty())
+}
+
+/// Parse a (`major.minor.patch`) version, missing components default to `0`
+///
+/// ```
+/// use ghactions_core::runner::parse_version;
+///
+/// assert_eq!(parse_version("2.300.1"), Some((2, 300, 1)));
+/// assert_eq!(parse_version("v2.311"), Some((2, 311, 0)));
+/// assert_eq!(parse_version("latest"), None);
+/// ```
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.trim().trim_start_matches('v');
+    // Ignore pre-release / build metadata
+    let version = version.split(['-', '+']).next()?;
+
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = match parts.next() {
+        Some(minor) => minor.parse().ok()?,
+        None => 0,
+    };
+    let patch = match parts.next() {
+        Some(patch) => patch.parse().ok()?,
+        None => 0,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((major, minor, patch))
+}
+
+/// Check the runner version is at least the required version
+///
+/// Versions which can't be parsed are treated as unknown and pass the check.
+pub fn check_runner_version(found: &str, required: &str) -> Result<(), ActionsError> {
+    match (parse_version(found), parse_version(required)) {
+        (Some(found_version), Some(required_version)) if found_version < required_version => {
+            Err(ActionsError::RunnerVersionError {
+                found: found.to_string(),
+                required: required.to_string(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Check if the runner supports the output files (`GITHUB_OUTPUT`)
+///
+/// Older runners only support the deprecated `::set-output` workflow command.
+/// If the runner version is unknown (e.g. running locally) the output files are used.
+pub fn runner_supports_output_files() -> bool {
+    if crate::env::var("GITHUB_OUTPUT").is_some_and(|path| !path.is_empty()) {
+        return true;
+    }
+    match runner_version().as_deref().and_then(parse_version) {
+        Some(version) => version >= OUTPUT_FILES_VERSION,
+        None => true,
+    }
+}
+
+/// Check if debug logging is enabled (e.g. the job was re-run with debug logging)
+///
+/// Debug logging is enabled when `RUNNER_DEBUG` is `1` (set by the runner). When
+/// `RUNNER_DEBUG` is not set, `DEBUG` is used (any value except empty, `0` or `false`).
+pub fn is_debug() -> bool {
+    match crate::env::var("RUNNER_DEBUG") {
+        Some(value) => value.trim() == "1",
+        None => crate::env::var("DEBUG")
+            .is_some_and(|value| !matches!(value.trim(), "" | "0" | "false")),
+    }
+}
+
+/// Environment variable to enable / disable local mode
+pub const LOCAL_MODE_ENV: &str = "GHACTIONS_LOCAL_MODE";
+
+/// Check if the Action is running locally (not in GitHub Actions)
+///
+/// Local mode is detected when `GITHUB_ACTIONS` is not `true` and can be
+/// forced on or off using the `GHACTIONS_LOCAL_MODE` environment variable.
+pub fn is_local_mode() -> bool {
+    match crate::env::var(LOCAL_MODE_ENV).as_deref() {
+        Some("false") | Some("0") => false,
+        Some("true") | Some("1") => true,
+        _ => crate::env::var("GITHUB_ACTIONS").as_deref() != Some("true"),
+    }
+}
+
+/// Check if running in CI (`CI` is `true`, set by GitHub Actions and most CI services)
+pub fn is_ci() -> bool {
+    crate::env::var("CI").is_some_and(|value| value.trim().eq_ignore_ascii_case("true"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_debug() {
+        std::env::remove_var("DEBUG");
+        std::env::remove_var("RUNNER_DEBUG");
+        assert!(!is_debug());
+
+        std::env::set_var("RUNNER_DEBUG", "1");
+        assert!(is_debug());
+        std::env::set_var("RUNNER_DEBUG", "0");
+        assert!(!is_debug());
+
+        // `RUNNER_DEBUG` takes precedence over `DEBUG`
+        std::env::set_var("DEBUG", "true");
+        assert!(!is_debug());
+        std::env::remove_var("RUNNER_DEBUG");
+        assert!(is_debug());
+        std::env::set_var("DEBUG", "0");
+        assert!(!is_debug());
+        std::env::remove_var("DEBUG");
+    }
+
+    #[test]
+    fn test_local_mode_detection() {
+        let env = |vars: &[(&str, &str)]| -> std::collections::HashMap<String, String> {
+            vars.iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect()
+        };
+        let local = |vars: &[(&str, &str)]| crate::env::with_env(env(vars), is_local_mode);
+
+        assert!(!local(&[("GITHUB_ACTIONS", "true")]));
+        assert!(local(&[]));
+        // Switched on / off explicitly
+        assert!(!local(&[(LOCAL_MODE_ENV, "false")]));
+        assert!(local(&[("GITHUB_ACTIONS", "true"), (LOCAL_MODE_ENV, "1")]));
+    }
+
+    #[test]
+    fn test_is_ci() {
+        let env = |value: &str| std::collections::HashMap::from([("CI".to_string(), value.into())]);
+        assert!(crate::env::with_env(env("true"), is_ci));
+        assert!(crate::env::with_env(env("TRUE"), is_ci));
+        assert!(!crate::env::with_env(env("false"), is_ci));
+        assert!(!crate::env::with_env(env(""), is_ci));
+    }
+
+    #[test]
+    fn test_check_runner_version() {
+        assert!(check_runner_version("2.311.0", "2.300.0").is_ok());
+        assert!(check_runner_version("2.300.0", "2.300.0").is_ok());
+        assert_eq!(
+            check_runner_version("2.299.1", "2.300.0"),
+            Err(ActionsError::RunnerVersionError {
+                found: "2.299.1".to_string(),
+                required: "2.300.0".to_string()
+            })
+        );
+        // Unknown versions are not rejected
+        assert!(check_runner_version("dev", "2.300.0").is_ok());
+    }
+
+    #[test]
+    fn test_runner_supports_output_files() {
+        let env = |vars: &[(&str, &str)]| -> std::collections::HashMap<String, String> {
+            vars.iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect()
+        };
+        let supported =
+            |vars: &[(&str, &str)]| crate::env::with_env(env(vars), runner_supports_output_files);
+        assert!(supported(&[("GITHUB_OUTPUT", "/tmp/output")]));
+        assert!(supported(&[
+            ("GITHUB_OUTPUT", "/tmp/output"),
+            ("RUNNER_VERSION", "2.290.0")
+        ]));
+        assert!(!supported(&[("RUNNER_VERSION", "2.290.0")]));
+        assert!(supported(&[]));
+    }
+}
@@ -0,0 +1,354 @@
+//! # Safety
+//!
+//! Opt-in, defense-in-depth guards for Actions running in untrusted contexts.
+//!
+//! Workflows triggered by `pull_request_target` run with a write token and secrets but
+//! can be fed data from a fork. Calling [crate::ActionTrait::assert_safe_context] with a
+//! [SafetyPolicy] enables the following guards for the rest of the process:
+//!
+//! - API clients created by [crate::ActionTrait::octocrab] reject any non-`GET` request
+//!   when the event is a pull request from a fork (note: this includes GraphQL queries)
+//! - Values marked with [mark_untrusted] can not be exported with `set_env` / `add_path`
+//!
+//! These guards do not replace reviewing how fork-controlled data is used.
+use std::sync::{Mutex, RwLock};
+
+use crate::ActionsError;
+
+/// Safety Policy used by [crate::ActionTrait::assert_safe_context]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SafetyPolicy {
+    /// Forbid mutating API requests (non-`GET`) when running for a pull request from a fork
+    pub forbid_fork_writes: bool,
+    /// Refuse to export values marked as untrusted using `set_env` / `add_path`
+    pub forbid_untrusted_commands: bool,
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        Self {
+            forbid_fork_writes: true,
+            forbid_untrusted_commands: true,
+        }
+    }
+}
+
+/// Active policy and if the workflow was triggered by a fork pull request
+#[derive(Debug, Clone)]
+struct SafetyContext {
+    policy: SafetyPolicy,
+    fork_pull_request: bool,
+}
+
+static CONTEXT: RwLock<Option<SafetyContext>> = RwLock::new(None);
+static UNTRUSTED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Enable the safety policy for the rest of the process
+pub(crate) fn enable(policy: SafetyPolicy) {
+    let fork_pull_request = is_fork_pull_request();
+
+    #[cfg(feature = "log")]
+    if fork_pull_request && policy.forbid_fork_writes {
+        log::warn!("Running for a pull request from a fork, API write operations are disabled");
+    }
+
+    *CONTEXT.write().unwrap_or_else(|err| err.into_inner()) = Some(SafetyContext {
+        policy,
+        fork_pull_request,
+    });
+}
+
+/// Check if mutating API requests are forbidden by the active safety policy
+pub fn writes_forbidden() -> bool {
+    CONTEXT
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .as_ref()
+        .is_some_and(|context| context.policy.forbid_fork_writes && context.fork_pull_request)
+}
+
+/// Mark a value as untrusted (e.g. a pull request title or branch name from a fork)
+///
+/// The value is returned so it can be used inline.
+///
+/// ```
+/// use ghactions_core::safety::mark_untrusted;
+///
+/// let title = mark_untrusted("Fix typo");
+/// assert_eq!(title, "Fix typo");
+/// ```
+pub fn mark_untrusted(value: impl Into<String>) -> String {
+    let value = value.into();
+    if !value.is_empty() {
+        UNTRUSTED
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(value.clone());
+    }
+    value
+}
+
+/// Check a value can be used with a workflow command (`set_env` / `add_path`)
+///
+/// Fails if the active safety policy forbids untrusted values and the value contains
+/// a value marked with [mark_untrusted].
+pub fn check_untrusted(command: &str, value: &str) -> Result<(), ActionsError> {
+    let forbidden = CONTEXT
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .as_ref()
+        .is_some_and(|context| context.policy.forbid_untrusted_commands);
+    if !forbidden {
+        return Ok(());
+    }
+
+    let untrusted = UNTRUSTED.lock().unwrap_or_else(|err| err.into_inner());
+    if untrusted
+        .iter()
+        .any(|marked| value.contains(marked.as_str()))
+    {
+        return Err(ActionsError::UnsafeContext(format!(
+            "`{}` was called with a value marked as untrusted",
+            command
+        )));
+    }
+    Ok(())
+}
+
+/// Check if the workflow was triggered by `pull_request_target` for a pull request from a fork
+///
+/// Uses `GITHUB_EVENT_NAME` and the event payload at `GITHUB_EVENT_PATH`. If the payload
+/// can not be read the pull request is treated as coming from a fork.
+pub fn is_fork_pull_request() -> bool {
+    if std::env::var("GITHUB_EVENT_NAME").as_deref() != Ok("pull_request_target") {
+        return false;
+    }
+    let payload = std::env::var("GITHUB_EVENT_PATH")
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+
+    match payload {
+        Some(payload) => fork_pull_request(&payload),
+        None => true,
+    }
+}
+
+/// Check if the pull request in an event payload comes from a fork
+fn fork_pull_request(payload: &serde_json::Value) -> bool {
+    let pull_request = &payload["pull_request"];
+    if pull_request["head"]["repo"]["fork"].as_bool() == Some(true) {
+        return true;
+    }
+    let head = pull_request["head"]["repo"]["full_name"].as_str();
+    let base = pull_request["base"]["repo"]["full_name"].as_str();
+    match (head, base) {
+        (Some(head), Some(base)) => head != base,
+        // Deleted forks have no head repository
+        _ => true,
+    }
+}
+
+#[cfg(feature = "octocrab")]
+pub use self::readonly::{ReadOnly, ReadOnlyLayer};
+
+#[cfg(feature = "octocrab")]
+mod readonly {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use bytes::Bytes;
+    use http::{Method, Request, Response};
+    use http_body_util::combinators::BoxBody;
+    use tower::{BoxError, Layer, Service};
+
+    use crate::ActionsError;
+
+    /// Layer rejecting all mutating (non-`GET`) requests
+    #[derive(Debug, Clone, Default)]
+    pub struct ReadOnlyLayer;
+
+    impl<S> Layer<S> for ReadOnlyLayer {
+        type Service = ReadOnly<S>;
+
+        fn layer(&self, inner: S) -> Self::Service {
+            ReadOnly { inner }
+        }
+    }
+
+    /// Service rejecting all mutating (non-`GET`) requests (see [ReadOnlyLayer])
+    #[derive(Debug, Clone)]
+    pub struct ReadOnly<S> {
+        inner: S,
+    }
+
+    impl<S, B> Service<Request<B>> for ReadOnly<S>
+    where
+        S: Service<Request<B>>,
+        S::Error: Into<BoxError>,
+        S::Future: Send + 'static,
+    {
+        type Response = S::Response;
+        type Error = BoxError;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            self.inner.poll_ready(cx).map_err(Into::into)
+        }
+
+        fn call(&mut self, request: Request<B>) -> Self::Future {
+            let method = request.method().clone();
+            if !matches!(method, Method::GET | Method::HEAD | Method::OPTIONS) {
+                let err = ActionsError::UnsafeContext(format!(
+                    "`{} {}` is a write operation and is blocked for pull requests from forks",
+                    method,
+                    request.uri().path()
+                ));
+                return Box::pin(async move { Err(err.into()) });
+            }
+
+            let future = self.inner.call(request);
+            Box::pin(async move { future.await.map_err(Into::into) })
+        }
+    }
+
+    /// Service sending requests using an existing Octocrab client
+    #[derive(Clone)]
+    struct OctocrabService(octocrab::Octocrab);
+
+    impl Service<Request<String>> for OctocrabService {
+        type Response = Response<BoxBody<Bytes, octocrab::Error>>;
+        type Error = octocrab::Error;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: Request<String>) -> Self::Future {
+            let client = self.0.clone();
+            Box::pin(async move { client.execute(request).await })
+        }
+    }
+
+    /// Wrap an Octocrab client so only read (`GET`) requests are allowed
+    pub(crate) fn wrap(client: octocrab::Octocrab) -> octocrab::Octocrab {
+        octocrab::OctocrabBuilder::new_empty()
+            .with_service(OctocrabService(client))
+            .with_layer(&ReadOnlyLayer)
+            .with_auth(octocrab::AuthState::None)
+            .build()
+            .unwrap_or_else(|never| match never {})
+    }
+}
+
+/// Apply the active safety policy to an Octocrab client
+#[cfg(feature = "octocrab")]
+pub(crate) fn guard_octocrab(client: octocrab::Octocrab) -> octocrab::Octocrab {
+    if writes_forbidden() {
+        readonly::wrap(client)
+    } else {
+        client
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_fork_pull_request_payload() {
+        let same_repo = json!({
+            "pull_request": {
+                "head": { "repo": { "full_name": "octo/repo", "fork": false } },
+                "base": { "repo": { "full_name": "octo/repo" } }
+            }
+        });
+        assert!(!fork_pull_request(&same_repo));
+
+        let fork = json!({
+            "pull_request": {
+                "head": { "repo": { "full_name": "evil/repo", "fork": true } },
+                "base": { "repo": { "full_name": "octo/repo" } }
+            }
+        });
+        assert!(fork_pull_request(&fork));
+
+        let deleted_fork = json!({
+            "pull_request": {
+                "head": { "repo": null },
+                "base": { "repo": { "full_name": "octo/repo" } }
+            }
+        });
+        assert!(fork_pull_request(&deleted_fork));
+    }
+
+    #[cfg(feature = "octocrab")]
+    #[tokio::test]
+    async fn test_fork_pull_request_guards() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/octo/repo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let event = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            event.path(),
+            json!({
+                "pull_request": {
+                    "head": { "repo": { "full_name": "evil/repo", "fork": true } },
+                    "base": { "repo": { "full_name": "octo/repo" } }
+                }
+            })
+            .to_string(),
+        )
+        .unwrap();
+        std::env::set_var("GITHUB_EVENT_NAME", "pull_request_target");
+        std::env::set_var("GITHUB_EVENT_PATH", event.path());
+
+        enable(SafetyPolicy::default());
+        assert!(writes_forbidden());
+
+        let client = guard_octocrab(
+            octocrab::Octocrab::builder()
+                .base_uri(server.uri())
+                .unwrap()
+                .build()
+                .unwrap(),
+        );
+
+        let response: Result<serde_json::Value, _> =
+            client.get("/repos/octo/repo", None::<&()>).await;
+        assert!(response.is_ok());
+
+        let response: Result<serde_json::Value, _> = client
+            .post(
+                "/repos/octo/repo/issues",
+                Some(&json!({ "title": "pwned" })),
+            )
+            .await;
+        let err = response.unwrap_err();
+        assert!(format!("{:?}", err).contains("blocked for pull requests from forks"));
+
+        // Untrusted values can not be exported
+        let branch = mark_untrusted("evil-branch\nPATH=/tmp");
+        assert!(check_untrusted("set_env", &format!("BRANCH={}", branch)).is_err());
+        assert!(check_untrusted("set_env", "BRANCH=main").is_ok());
+
+        *CONTEXT.write().unwrap() = None;
+        std::env::remove_var("GITHUB_EVENT_NAME");
+        std::env::remove_var("GITHUB_EVENT_PATH");
+    }
+}
@@ -0,0 +1,568 @@
+//! # Job Summary
+//!
+//! Build a Markdown job summary and write it to the `GITHUB_STEP_SUMMARY` file.
+//!
+//! Besides Markdown blocks, the Summary supports collapsible sections, Mermaid diagrams,
+//! GitHub alerts, badges and templates (see [Summary::from_template]).
+//!
+//! ```
+//! use ghactions_core::summary::Summary;
+//!
+//! let mut summary = Summary::new();
+//! summary
+//!     .heading("Results", 2)
+//!     .table(&["Crate", "Tests"], &[vec!["ghactions", "42"]]);
+//!
+//! assert_eq!(
+//!     summary.as_str(),
+//!     "## Results\n\n| Crate | Tests |\n| --- | --- |\n| ghactions | 42 |\n\n"
+//! );
+//! ```
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::ActionsError;
+
+/// Summary file used when running locally (`GITHUB_STEP_SUMMARY` is not set)
+pub const LOCAL_SUMMARY_PATH: &str = "./ghactions-summary.md";
+
+/// Environment variable overriding the local summary file (see [LOCAL_SUMMARY_PATH])
+pub const LOCAL_SUMMARY_ENV: &str = "GHACTIONS_SUMMARY_PATH";
+
+/// Set once the path of the local summary file has been printed
+static LOCAL_SUMMARY_NOTICE: AtomicBool = AtomicBool::new(false);
+
+/// Maximum size of the job summary of a step (larger summaries are not shown by GitHub)
+pub const SUMMARY_SIZE_LIMIT: usize = 1024 * 1024;
+
+/// Kind of a GitHub alert (highlighted blockquote)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertKind {
+    /// Useful information
+    Note,
+    /// Helpful advice
+    Tip,
+    /// Key information
+    Important,
+    /// Urgent information that needs attention
+    Warning,
+    /// Risks or negative outcomes
+    Caution,
+}
+
+impl AlertKind {
+    /// Get the marker of the alert (e.g. `WARNING`)
+    pub fn marker(&self) -> &'static str {
+        match self {
+            AlertKind::Note => "NOTE",
+            AlertKind::Tip => "TIP",
+            AlertKind::Important => "IMPORTANT",
+            AlertKind::Warning => "WARNING",
+            AlertKind::Caution => "CAUTION",
+        }
+    }
+}
+
+/// Markdown job summary buffered until [Summary::write] or [Summary::overwrite] is called
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Summary {
+    buffer: String,
+}
+
+impl Summary {
+    /// Create an empty Summary
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a Summary from a template file
+    ///
+    /// The `{{ name }}` placeholders are replaced with the values of the variables, a
+    /// placeholder without a value is an error. Templates shipped with the Action can be
+    /// located using [crate::resources::action_resource].
+    pub fn from_template<K, V>(
+        path: impl AsRef<Path>,
+        vars: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<Self, ActionsError>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let path = path.as_ref();
+        let template = std::fs::read_to_string(path).map_err(|err| ActionsError::FileError {
+            path: path.to_path_buf(),
+            kind: err.kind(),
+            message: err.to_string(),
+        })?;
+        let vars: HashMap<String, String> = vars
+            .into_iter()
+            .map(|(name, value)| (name.into(), value.into()))
+            .collect();
+
+        let markdown = render_template(&template, &vars).map_err(|name| {
+            ActionsError::SummaryTemplateError {
+                path: path.to_path_buf(),
+                name,
+            }
+        })?;
+        let mut summary = Self::new();
+        summary.add_raw(markdown);
+        Ok(summary)
+    }
+
+    /// Get the buffered Markdown
+    pub fn as_str(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Get the size of the buffered Markdown (in bytes)
+    pub fn size(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Check if the buffered Markdown is over [SUMMARY_SIZE_LIMIT]
+    pub fn is_over_limit(&self) -> bool {
+        self.size() > SUMMARY_SIZE_LIMIT
+    }
+
+    /// Check if nothing has been added to the Summary
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Clear the buffered Markdown
+    pub fn clear(&mut self) -> &mut Self {
+        self.buffer.clear();
+        self
+    }
+
+    /// Add raw Markdown (without a trailing blank line)
+    pub fn raw(&mut self, markdown: impl AsRef<str>) -> &mut Self {
+        self.buffer.push_str(markdown.as_ref());
+        self
+    }
+
+    /// Add raw Markdown or HTML as a block (followed by a blank line)
+    ///
+    /// The content is not escaped.
+    pub fn add_raw(&mut self, content: impl AsRef<str>) -> &mut Self {
+        self.block(content.as_ref().trim_end_matches('\n'))
+    }
+
+    /// Add a heading (the level is clamped between `1` and `6`)
+    pub fn heading(&mut self, text: impl AsRef<str>, level: usize) -> &mut Self {
+        let level = level.clamp(1, 6);
+        self.block(format!("{} {}", "#".repeat(level), text.as_ref()))
+    }
+
+    /// Add a paragraph of text
+    pub fn text(&mut self, text: impl AsRef<str>) -> &mut Self {
+        self.block(text.as_ref())
+    }
+
+    /// Add a fenced code block (the language can be empty)
+    pub fn code_block(&mut self, lang: impl AsRef<str>, body: impl AsRef<str>) -> &mut Self {
+        let body = body.as_ref().trim_end_matches('\n');
+        // The fence has to be longer than any backtick run in the body
+        let mut fence = String::from("```");
+        while body.contains(&fence) {
+            fence.push('`');
+        }
+        self.block(format!("{}{}\n{}\n{}", fence, lang.as_ref(), body, fence))
+    }
+
+    /// Add a [Mermaid](https://mermaid.js.org) diagram
+    pub fn mermaid(&mut self, diagram: impl AsRef<str>) -> &mut Self {
+        self.code_block("mermaid", diagram)
+    }
+
+    /// Add a collapsible section (the body is Markdown and hidden until expanded)
+    ///
+    /// The summary line is escaped, a nested [Summary] can be used as the body.
+    pub fn details(&mut self, summary: impl AsRef<str>, body: impl AsRef<str>) -> &mut Self {
+        self.block(format!(
+            "<details>\n<summary>{}</summary>\n\n{}\n\n</details>",
+            escape_html(summary.as_ref()),
+            body.as_ref().trim_end_matches('\n')
+        ))
+    }
+
+    /// Add a GitHub alert (e.g. `> [!WARNING]`)
+    pub fn alert(&mut self, kind: AlertKind, text: impl AsRef<str>) -> &mut Self {
+        let mut lines = vec![format!("> [!{}]", kind.marker())];
+        lines.extend(text.as_ref().trim_end_matches('\n').lines().map(
+            |line| match line.is_empty() {
+                true => ">".to_string(),
+                false => format!("> {}", line),
+            },
+        ));
+        self.block(lines.join("\n"))
+    }
+
+    /// Add a badge (a [Shields.io](https://shields.io) static badge image)
+    ///
+    /// The color is a named color (e.g. `green`) or a hex color without the `#`.
+    pub fn badge(
+        &mut self,
+        label: impl AsRef<str>,
+        message: impl AsRef<str>,
+        color: impl AsRef<str>,
+    ) -> &mut Self {
+        let (label, message) = (label.as_ref(), message.as_ref());
+        let alt = format!("{}: {}", label, message)
+            .replace('[', "\\[")
+            .replace(']', "\\]");
+        self.block(format!(
+            "![{}](https://img.shields.io/badge/{}-{}-{})",
+            alt,
+            badge_component(label),
+            badge_component(message),
+            crate::urls::encode_component(color.as_ref())
+        ))
+    }
+
+    /// Add an unordered list
+    pub fn list<I: AsRef<str>>(&mut self, items: &[I]) -> &mut Self {
+        let list = items
+            .iter()
+            .map(|item| format!("- {}", item.as_ref()))
+            .collect::<Vec<String>>()
+            .join("\n");
+        self.block(list)
+    }
+
+    /// Add a link (as its own paragraph)
+    pub fn link(&mut self, text: impl AsRef<str>, href: impl AsRef<str>) -> &mut Self {
+        self.block(format!("[{}]({})", text.as_ref(), href.as_ref()))
+    }
+
+    /// Add a table
+    ///
+    /// Pipes in the cells are escaped and new lines are replaced with `<br>`. Rows with
+    /// fewer cells than headers are padded with empty cells.
+    pub fn table<H: AsRef<str>, C: AsRef<str>>(
+        &mut self,
+        headers: &[H],
+        rows: &[Vec<C>],
+    ) -> &mut Self {
+        let row = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+
+        let mut lines = vec![
+            row(headers.iter().map(|header| cell(header.as_ref())).collect()),
+            row(vec!["---".to_string(); headers.len()]),
+        ];
+        for cells in rows {
+            let mut cells: Vec<String> = cells.iter().map(|value| cell(value.as_ref())).collect();
+            if cells.len() < headers.len() {
+                cells.resize(headers.len(), String::new());
+            }
+            lines.push(row(cells));
+        }
+        self.block(lines.join("\n"))
+    }
+
+    /// Append the Summary to the job summary and clear the buffer
+    pub fn write(&mut self) -> Result<(), ActionsError> {
+        self.flush(false)
+    }
+
+    /// Replace the job summary (of the current step) with the Summary and clear the buffer
+    pub fn overwrite(&mut self) -> Result<(), ActionsError> {
+        self.flush(true)
+    }
+
+    fn flush(&mut self, overwrite: bool) -> Result<(), ActionsError> {
+        let (path, local) = summary_path()?;
+
+        #[cfg(feature = "log")]
+        {
+            let existing = match overwrite {
+                true => 0,
+                false => std::fs::metadata(&path).map_or(0, |meta| meta.len() as usize),
+            };
+            if existing + self.size() > SUMMARY_SIZE_LIMIT {
+                log::warn!(
+                    "Job summary is over the {} KiB limit ({} KiB) and will not be shown",
+                    SUMMARY_SIZE_LIMIT / 1024,
+                    (existing + self.size()) / 1024
+                );
+            }
+        }
+        std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(!overwrite)
+            .truncate(overwrite)
+            .open(&path)
+            .and_then(|mut file| file.write_all(self.buffer.as_bytes()))
+            .map_err(|err| ActionsError::FileError {
+                path: path.clone(),
+                kind: err.kind(),
+                message: err.to_string(),
+            })?;
+        self.buffer.clear();
+
+        if local && !LOCAL_SUMMARY_NOTICE.swap(true, Ordering::Relaxed) {
+            eprintln!(
+                "ghactions: running in local mode, the job summary is written to `{}` (set `{}=false` to disable)",
+                path.display(),
+                crate::runner::LOCAL_MODE_ENV
+            );
+        }
+        Ok(())
+    }
+
+    fn block(&mut self, markdown: impl AsRef<str>) -> &mut Self {
+        self.buffer.push_str(markdown.as_ref());
+        self.buffer.push_str("\n\n");
+        self
+    }
+}
+
+impl std::fmt::Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.buffer)
+    }
+}
+
+/// Escape a table cell
+fn cell(value: &str) -> String {
+    value
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+/// Escape text used inside HTML tags
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Escape a label or message of a badge (dashes and underscores are doubled)
+fn badge_component(value: &str) -> String {
+    crate::urls::encode_component(&value.replace('-', "--").replace('_', "__"))
+}
+
+/// Replace the `{{ name }}` placeholders, returns the name of an undefined variable
+///
+/// An unterminated `{{` is kept as is.
+fn render_template(template: &str, vars: &HashMap<String, String>) -> Result<String, String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + end].trim();
+        let value = vars.get(name).ok_or_else(|| name.to_string())?;
+        rendered.push_str(&rest[..start]);
+        rendered.push_str(value);
+        rest = &rest[start + end + 2..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+/// Get the path of the job summary file (and if it is the local summary file)
+///
+/// Falls back to `GHACTIONS_SUMMARY_PATH` or [LOCAL_SUMMARY_PATH] when running locally
+/// (see [crate::runner::is_local_mode]).
+fn summary_path() -> Result<(PathBuf, bool), ActionsError> {
+    if let Some(path) = crate::env_files::get_step_summary_path() {
+        return Ok((path, false));
+    }
+    if crate::runner::is_local_mode() {
+        let path = crate::env::var(LOCAL_SUMMARY_ENV)
+            .filter(|path| !path.is_empty())
+            .unwrap_or_else(|| LOCAL_SUMMARY_PATH.to_string());
+        return Ok((PathBuf::from(path), true));
+    }
+    Err(ActionsError::SummaryUnavailable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> HashMap<String, String> {
+        vars.iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_write_local() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("summary.md");
+        let path_env = path.display().to_string();
+
+        // Local mode (`GITHUB_ACTIONS` and `GITHUB_STEP_SUMMARY` are not set)
+        crate::env::with_env(env(&[(LOCAL_SUMMARY_ENV, &path_env)]), || {
+            let mut summary = Summary::new();
+            summary.heading("Local", 1).write().unwrap();
+            summary.text("Appended").write().unwrap();
+        });
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# Local\n\nAppended\n\n"
+        );
+
+        // Default local summary file
+        crate::env::with_env(env(&[]), || {
+            Summary::new().text("Default").write().unwrap();
+        });
+        assert_eq!(
+            std::fs::read_to_string(LOCAL_SUMMARY_PATH).unwrap(),
+            "Default\n\n"
+        );
+        std::fs::remove_file(LOCAL_SUMMARY_PATH).unwrap();
+
+        // In GitHub Actions (or with local mode switched off) the summary file is required
+        for vars in [
+            [
+                ("GITHUB_ACTIONS", "true"),
+                (LOCAL_SUMMARY_ENV, path_env.as_str()),
+            ],
+            [
+                (crate::runner::LOCAL_MODE_ENV, "false"),
+                (LOCAL_SUMMARY_ENV, path_env.as_str()),
+            ],
+        ] {
+            let result = crate::env::with_env(env(&vars), || Summary::new().text("CI").write());
+            assert_eq!(result, Err(ActionsError::SummaryUnavailable));
+        }
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# Local\n\nAppended\n\n"
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        let mut summary = Summary::new();
+        summary.table(
+            &["Name", "Status"],
+            &[
+                vec!["build", "passed"],
+                vec!["a | b", "line\nbreak"],
+                vec!["lint"],
+            ],
+        );
+        assert_eq!(
+            summary.as_str(),
+            "| Name | Status |\n\
+             | --- | --- |\n\
+             | build | passed |\n\
+             | a \\| b | line<br>break |\n\
+             | lint |  |\n\n"
+        );
+    }
+
+    #[test]
+    fn test_blocks() {
+        let mut summary = Summary::new();
+        summary
+            .heading("Report", 1)
+            .text("All checks passed")
+            .list(&["first", "second"])
+            .link("Docs", "https://docs.rs/ghactions")
+            .code_block("rust", "let fence = \"```\";\n");
+        assert_eq!(
+            summary.to_string(),
+            "# Report\n\n\
+             All checks passed\n\n\
+             - first\n- second\n\n\
+             [Docs](https://docs.rs/ghactions)\n\n\
+             ````rust\nlet fence = \"```\";\n````\n\n"
+        );
+    }
+
+    #[test]
+    fn test_extended_blocks() {
+        let mut details = Summary::new();
+        details.list(&["unit", "e2e"]);
+
+        let mut summary = Summary::new();
+        summary
+            .details("Suites <2>", details.as_str())
+            .mermaid("graph LR\n  build --> test\n")
+            .alert(AlertKind::Warning, "Deprecated input\n\nUse `token`")
+            .badge("build-status", "passing_now", "green")
+            .add_raw("<p align=\"center\">Done</p>\n");
+        assert_eq!(
+            summary.as_str(),
+            "<details>\n<summary>Suites &lt;2&gt;</summary>\n\n- unit\n- e2e\n\n</details>\n\n\
+             ```mermaid\ngraph LR\n  build --> test\n```\n\n\
+             > [!WARNING]\n> Deprecated input\n>\n> Use `token`\n\n\
+             ![build-status: passing_now](https://img.shields.io/badge/build--status-passing__now-green)\n\n\
+             <p align=\"center\">Done</p>\n\n"
+        );
+    }
+
+    #[test]
+    fn test_size() {
+        let mut summary = Summary::new();
+        summary.text("Done");
+        assert_eq!(summary.size(), 6);
+        assert!(!summary.is_over_limit());
+
+        summary.raw("a".repeat(SUMMARY_SIZE_LIMIT));
+        assert!(summary.is_over_limit());
+    }
+
+    #[test]
+    fn test_render_template() {
+        let vars = HashMap::from([
+            ("name".to_string(), "ghactions".to_string()),
+            ("count".to_string(), "42".to_string()),
+        ]);
+        let cases = [
+            ("# {{name}}", Ok("# ghactions")),
+            ("{{ name }}: {{count}} {{count}}", Ok("ghactions: 42 42")),
+            ("no placeholders", Ok("no placeholders")),
+            ("unterminated {{ name", Ok("unterminated {{ name")),
+            ("{{ missing }}", Err("missing")),
+        ];
+        for (template, expected) in cases {
+            assert_eq!(
+                render_template(template, &vars),
+                expected.map(String::from).map_err(String::from),
+                "template: {}",
+                template
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_template() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("summary.md");
+        std::fs::write(&path, "## {{ title }}\n\nTests: {{passed}}\n").unwrap();
+
+        let mut summary =
+            Summary::from_template(&path, [("title", "Results"), ("passed", "10")]).unwrap();
+        summary.text("Done");
+        assert_eq!(summary.as_str(), "## Results\n\nTests: 10\n\nDone\n\n");
+
+        assert_eq!(
+            Summary::from_template(&path, [("title", "Results")]),
+            Err(ActionsError::SummaryTemplateError {
+                path: path.clone(),
+                name: "passed".to_string()
+            })
+        );
+        assert!(Summary::from_template(dir.path().join("missing.md"), [("a", "b")]).is_err());
+    }
+
+    #[test]
+    fn test_heading_level() {
+        let mut summary = Summary::new();
+        summary.heading("Zero", 0).heading("Seven", 7);
+        assert_eq!(summary.as_str(), "# Zero\n\n###### Seven\n\n");
+    }
+}
@@ -0,0 +1,234 @@
+//! # Testing
+//!
+//! Test helpers for Actions: set the inputs and the workflow environment variables, and read
+//! back the outputs, exported variables and job summary written by the Action.
+//!
+//! [TestEnv] changes the process environment under a global lock (so tests using it do not
+//! race) and restores the variables it changed when dropped (including the variables
+//! exported to `GITHUB_ENV` and the `PATH`). The environment files (`GITHUB_OUTPUT`, `GITHUB_ENV`,
+//! `GITHUB_PATH`, `GITHUB_STATE` and `GITHUB_STEP_SUMMARY`) are created in a temporary
+//! directory.
+//!
+//! ```
+//! use ghactions_core::testing::TestEnv;
+//! use ghactions_core::env_files::write_outputs;
+//!
+//! let env = TestEnv::new()
+//!     .input("repository", "42ByteLabs/ghactions")
+//!     .github_env("GITHUB_SHA", "ffac537e6cbbf934b08745a378932722df287a53");
+//! assert_eq!(std::env::var("INPUT_REPOSITORY").unwrap(), "42ByteLabs/ghactions");
+//!
+//! write_outputs(&[("version", "1.0.0"), ("notes", "first\nsecond")]).unwrap();
+//! assert_eq!(env.outputs()["version"], "1.0.0");
+//! assert_eq!(env.outputs()["notes"], "first\nsecond");
+//! ```
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use indexmap::IndexMap;
+
+use crate::env_files::{parse_env_file, reset_paths_for_testing};
+use crate::inputs::input_env_name;
+
+/// Serialises the tests changing the process environment (see [TestEnv::new])
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Environment files created by [TestEnv] (environment variable and file name)
+const ENV_FILES: [(&str, &str); 5] = [
+    ("GITHUB_OUTPUT", "output.env"),
+    ("GITHUB_ENV", "env.env"),
+    ("GITHUB_PATH", "path.env"),
+    ("GITHUB_STATE", "state.env"),
+    ("GITHUB_STEP_SUMMARY", "summary.md"),
+];
+
+/// Environment of an Action under test, restored when dropped
+///
+/// Only one [TestEnv] exists at a time: creating a second one on the same thread (before
+/// dropping the first one) deadlocks.
+#[derive(Debug)]
+pub struct TestEnv {
+    /// Process environment before the test
+    saved: Vec<(OsString, OsString)>,
+    /// Variables changed by the test environment (restored on drop)
+    changed: Vec<String>,
+    dir: tempfile::TempDir,
+    // Dropped last so the environment is restored while holding the lock
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl TestEnv {
+    /// Lock the environment and create the environment files
+    ///
+    /// Panics if the temporary directory can not be created.
+    pub fn new() -> Self {
+        // A test panicking while holding the lock does not affect the other tests
+        let lock = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        let saved = std::env::vars_os().collect();
+        let dir = tempfile::tempdir().expect("failed to create the test environment directory");
+
+        for (name, file) in ENV_FILES {
+            let path = dir.path().join(file);
+            std::fs::write(&path, "").expect("failed to create the environment file");
+            std::env::set_var(name, path);
+        }
+        reset_paths_for_testing();
+
+        let mut changed: Vec<String> = ENV_FILES.iter().map(|(name, _)| name.to_string()).collect();
+        // Updated by `add_path`
+        changed.push("PATH".to_string());
+        Self {
+            saved,
+            changed,
+            dir,
+            _lock: lock,
+        }
+    }
+
+    /// Set an input (using the environment variable the runner sets, e.g. `INPUT_REPO`)
+    pub fn input(self, name: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.github_env(input_env_name(name.as_ref()), value)
+    }
+
+    /// Set an environment variable (e.g. `GITHUB_SHA` or `GITHUB_EVENT_NAME`)
+    pub fn github_env(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+        self.changed.push(key.as_ref().to_string());
+        std::env::set_var(key.as_ref(), value.as_ref());
+        reset_paths_for_testing();
+        self
+    }
+
+    /// Remove an environment variable
+    pub fn remove(mut self, key: impl AsRef<str>) -> Self {
+        self.changed.push(key.as_ref().to_string());
+        std::env::remove_var(key.as_ref());
+        reset_paths_for_testing();
+        self
+    }
+
+    /// Get the temporary directory of the environment files (e.g. to create a workspace)
+    pub fn dir(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Get the outputs written to `GITHUB_OUTPUT` (the last value of each output)
+    pub fn outputs(&self) -> IndexMap<String, String> {
+        self.records("GITHUB_OUTPUT")
+    }
+
+    /// Get the variables exported to `GITHUB_ENV` (the last value of each variable)
+    pub fn exported_env(&self) -> IndexMap<String, String> {
+        self.records("GITHUB_ENV")
+    }
+
+    /// Get the values saved to `GITHUB_STATE` (the last value of each key)
+    pub fn state(&self) -> IndexMap<String, String> {
+        self.records("GITHUB_STATE")
+    }
+
+    /// Get the directories added to `GITHUB_PATH` (in the order they were added)
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.read("GITHUB_PATH")
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(PathBuf::from)
+            .collect()
+    }
+
+    /// Get the job summary written to `GITHUB_STEP_SUMMARY`
+    pub fn summary(&self) -> String {
+        self.read("GITHUB_STEP_SUMMARY")
+    }
+
+    fn records(&self, name: &str) -> IndexMap<String, String> {
+        parse_env_file(&self.read(name)).into_iter().collect()
+    }
+
+    fn read(&self, name: &str) -> String {
+        let file = ENV_FILES
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, file)| self.dir.path().join(file))
+            .expect("unknown environment file");
+        std::fs::read_to_string(file).unwrap_or_default()
+    }
+}
+
+impl Default for TestEnv {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TestEnv {
+    fn drop(&mut self) {
+        // `set_env` also sets the exported variables for the process
+        let exported = self.exported_env().into_keys();
+        for key in self.changed.drain(..).chain(exported) {
+            match self.saved.iter().find(|(saved, _)| *saved == *key) {
+                Some((_, value)) => std::env::set_var(&key, value),
+                None => std::env::remove_var(&key),
+            }
+        }
+        reset_paths_for_testing();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_restored() {
+        std::env::set_var("GHACTIONS_TESTING_KEPT", "before");
+        std::env::remove_var("INPUT_TESTING-NAME");
+        let path = std::env::var_os("PATH");
+
+        let env = TestEnv::new()
+            .input("testing-name", "value")
+            .github_env("GHACTIONS_TESTING_KEPT", "during")
+            .github_env("GHACTIONS_TESTING_ADDED", "added");
+        crate::env_files::set_env("GHACTIONS_TESTING_EXPORTED", "exported").unwrap();
+        crate::env_files::add_path(env.dir().join("bin")).unwrap();
+
+        assert_eq!(std::env::var("INPUT_TESTING-NAME").unwrap(), "value");
+        assert_eq!(
+            env.exported_env(),
+            IndexMap::from([(
+                "GHACTIONS_TESTING_EXPORTED".to_string(),
+                "exported".to_string()
+            )])
+        );
+        assert_eq!(env.paths(), vec![env.dir().join("bin")]);
+        let dir = env.dir().to_path_buf();
+        drop(env);
+
+        assert!(!dir.exists());
+        assert_eq!(std::env::var("GHACTIONS_TESTING_KEPT").unwrap(), "before");
+        assert!(std::env::var("GHACTIONS_TESTING_ADDED").is_err());
+        assert!(std::env::var("GHACTIONS_TESTING_EXPORTED").is_err());
+        assert!(std::env::var("INPUT_TESTING-NAME").is_err());
+        assert_eq!(std::env::var_os("PATH"), path);
+        std::env::remove_var("GHACTIONS_TESTING_KEPT");
+    }
+
+    #[test]
+    fn test_outputs_and_summary() {
+        let env = TestEnv::new();
+        crate::env_files::write_outputs(&[("count", "1"), ("notes", "a\nb"), ("count", "2")])
+            .unwrap();
+        crate::env_files::save_state("pid", "42").unwrap();
+        crate::Summary::new().heading("Report", 2).write().unwrap();
+
+        assert_eq!(
+            env.outputs().into_iter().collect::<Vec<_>>(),
+            vec![
+                ("count".to_string(), "2".to_string()),
+                ("notes".to_string(), "a\nb".to_string()),
+            ]
+        );
+        assert_eq!(env.state()["pid"], "42");
+        assert_eq!(env.summary(), "## Report\n\n");
+    }
+}
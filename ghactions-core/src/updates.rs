@@ -0,0 +1,200 @@
+//! # Updates
+//!
+//! Notify the users of an Action when the Action version they use is behind the latest release.
+//!
+//! The check is opt-in (see [ActionTrait::check_for_newer_release](crate::ActionTrait)) and
+//! is disabled by setting [UPDATE_CHECK_DISABLE_ENV].
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::runner::parse_version;
+use crate::ActionsError;
+
+/// Environment variable disabling the update check (any non-empty value)
+pub const UPDATE_CHECK_DISABLE_ENV: &str = "GHACTIONS_DISABLE_UPDATE_CHECK";
+
+/// Result of the update check (the check only runs once per process)
+static LATEST: Mutex<Option<Option<String>>> = Mutex::new(None);
+static NOTIFIED: AtomicBool = AtomicBool::new(false);
+
+/// Check if the update check is disabled using [UPDATE_CHECK_DISABLE_ENV]
+pub fn update_check_disabled() -> bool {
+    std::env::var(UPDATE_CHECK_DISABLE_ENV).is_ok_and(|value| !value.trim().is_empty())
+}
+
+/// Check if the `latest` release is newer than the `current` ref of the Action
+///
+/// Only the parts of the version used by the ref are compared so major-only refs
+/// (e.g. `v2`) are up to date with all `v2.x.x` releases. Refs which are not versions
+/// (branches or commit SHAs) are never behind.
+///
+/// ```
+/// use ghactions_core::updates::is_behind;
+///
+/// assert!(is_behind("v1.2.0", "v1.3.0"));
+/// assert!(is_behind("v1", "v2.0.1"));
+/// assert!(!is_behind("v2", "v2.4.0"));
+/// assert!(!is_behind("main", "v2.4.0"));
+/// ```
+pub fn is_behind(current: &str, latest: &str) -> bool {
+    let (Some(current_version), Some(latest_version)) =
+        (parse_version(current), parse_version(latest))
+    else {
+        return false;
+    };
+    let precision = current
+        .trim()
+        .split(['-', '+'])
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .count();
+    let truncate = |(major, minor, patch): (u64, u64, u64)| match precision {
+        1 => (major, 0, 0),
+        2 => (major, minor, 0),
+        _ => (major, minor, patch),
+    };
+    truncate(latest_version) > truncate(current_version)
+}
+
+/// Get the latest release of `repository` if it is newer than the `reference` of the Action
+pub async fn newer_release(
+    client: &octocrab::Octocrab,
+    repository: &str,
+    reference: &str,
+) -> Result<Option<String>, ActionsError> {
+    let (owner, repo) = repository
+        .split_once('/')
+        .ok_or_else(|| ActionsError::RepositoryReferenceError(repository.to_string()))?;
+    let release = client.repos(owner, repo).releases().get_latest().await?;
+
+    match is_behind(reference, &release.tag_name) {
+        true => Ok(Some(release.tag_name)),
+        false => Ok(None),
+    }
+}
+
+/// Check for a newer release of the running Action and notify the user (once per process)
+///
+/// The Action is read from `GITHUB_ACTION_REPOSITORY` and `GITHUB_ACTION_REF`. Errors
+/// (e.g. network errors) are logged at the debug level and never fail the Action.
+pub async fn check_for_newer_release(client: octocrab::Octocrab) -> Option<String> {
+    if update_check_disabled() {
+        return None;
+    }
+    if let Some(latest) = LATEST.lock().unwrap_or_else(|err| err.into_inner()).clone() {
+        return latest;
+    }
+
+    let repository = std::env::var("GITHUB_ACTION_REPOSITORY").unwrap_or_default();
+    let reference = std::env::var("GITHUB_ACTION_REF").unwrap_or_default();
+    let latest = if repository.is_empty() || reference.is_empty() {
+        None
+    } else {
+        match newer_release(&client, &repository, &reference).await {
+            Ok(latest) => latest,
+            Err(_err) => {
+                #[cfg(feature = "log")]
+                log::debug!("Failed to check for a newer release: {}", _err);
+                None
+            }
+        }
+    };
+    *LATEST.lock().unwrap_or_else(|err| err.into_inner()) = Some(latest.clone());
+
+    if let Some(ref latest) = latest {
+        if !NOTIFIED.swap(true, Ordering::SeqCst) {
+            let message = format!(
+                "::notice::A newer version of `{}` is available ({} -> {}), consider updating the Action",
+                repository, reference, latest
+            );
+            #[cfg(feature = "log")]
+            log::info!("{}", message);
+            #[cfg(not(feature = "log"))]
+            println!("{}", message);
+        }
+    }
+    latest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_is_behind() {
+        assert!(is_behind("v1.0.0", "v1.0.1"));
+        assert!(is_behind("1.2", "v1.3.0"));
+        assert!(!is_behind("v1.2", "v1.2.9"));
+        assert!(!is_behind("v2.0.0", "v1.9.0"));
+        assert!(!is_behind("v2.0.0", "v2.0.0"));
+        assert!(!is_behind("0123456789abcdef", "v2.0.0"));
+        assert!(!is_behind("v2", "latest"));
+    }
+
+    #[test]
+    fn test_update_check_disabled() {
+        std::env::set_var(UPDATE_CHECK_DISABLE_ENV, "true");
+        assert!(update_check_disabled());
+        std::env::set_var(UPDATE_CHECK_DISABLE_ENV, "");
+        assert!(!update_check_disabled());
+        std::env::remove_var(UPDATE_CHECK_DISABLE_ENV);
+    }
+
+    async fn server(status: u16, tag: &str) -> (MockServer, octocrab::Octocrab) {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/octo/action/releases/latest"))
+            .respond_with(ResponseTemplate::new(status).set_body_json(json!({
+                "url": "https://api.github.com/repos/octo/action/releases/1",
+                "html_url": "https://github.com/octo/action/releases/v2.1.0",
+                "assets_url": "https://api.github.com/repos/octo/action/releases/1/assets",
+                "upload_url": "https://uploads.github.com/repos/octo/action/releases/1/assets",
+                "id": 1,
+                "node_id": "RE_1",
+                "tag_name": tag,
+                "target_commitish": "main",
+                "draft": false,
+                "prerelease": false,
+                "assets": [],
+            })))
+            .mount(&server)
+            .await;
+        let client = octocrab::Octocrab::builder()
+            .base_uri(server.uri())
+            .unwrap()
+            .build()
+            .unwrap();
+        (server, client)
+    }
+
+    #[tokio::test]
+    async fn test_newer_release_available() {
+        let (_server, client) = server(200, "v2.1.0").await;
+        let latest = newer_release(&client, "octo/action", "v1").await.unwrap();
+        assert_eq!(latest, Some("v2.1.0".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_newer_release_up_to_date() {
+        let (_server, client) = server(200, "v2.1.0").await;
+        let latest = newer_release(&client, "octo/action", "v2").await.unwrap();
+        assert_eq!(latest, None);
+    }
+
+    #[tokio::test]
+    async fn test_newer_release_non_semver_ref() {
+        let (_server, client) = server(200, "v2.1.0").await;
+        let latest = newer_release(&client, "octo/action", "main").await.unwrap();
+        assert_eq!(latest, None);
+    }
+
+    #[tokio::test]
+    async fn test_newer_release_api_failure() {
+        let (_server, client) = server(500, "v2.1.0").await;
+        assert!(newer_release(&client, "octo/action", "v1").await.is_err());
+    }
+}
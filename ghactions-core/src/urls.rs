@@ -0,0 +1,267 @@
+//! # URLs
+//!
+//! Resolve the server, API and GraphQL URLs of the GitHub instance running the workflow:
+//!
+//! - github.com (`https://api.github.com` and `https://api.github.com/graphql`)
+//! - GitHub Enterprise Server (`https://ghe.example.com/api/v3` and
+//!   `https://ghe.example.com/api/graphql`)
+//! - GitHub Enterprise Cloud with data residency (`https://api.octo.ghe.com` and
+//!   `https://api.octo.ghe.com/graphql`)
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+/// Default GitHub Server URL
+pub(crate) const DEFAULT_SERVER_URL: &str = "https://github.com";
+/// Default GitHub API URL
+pub(crate) const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Server, API and GraphQL URLs of the GitHub instance
+///
+/// URLs are normalised without a trailing slash, missing URLs are derived from the server
+/// URL.
+///
+/// ```
+/// use ghactions_core::urls::GitHubUrls;
+///
+/// let urls = GitHubUrls::resolve(Some("https://ghe.example.com/"), None, None);
+/// assert_eq!(urls.api_url, "https://ghe.example.com/api/v3");
+/// assert_eq!(urls.graphql_url, "https://ghe.example.com/api/graphql");
+/// assert_eq!(urls.api_base_uri(), "https://ghe.example.com/api/v3/");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GitHubUrls {
+    /// Server URL (`GITHUB_SERVER_URL`)
+    pub server_url: String,
+    /// API URL (`GITHUB_API_URL`)
+    pub api_url: String,
+    /// GraphQL URL (`GITHUB_GRAPHQL_URL`)
+    pub graphql_url: String,
+}
+
+impl Default for GitHubUrls {
+    fn default() -> Self {
+        Self::resolve(None, None, None)
+    }
+}
+
+impl GitHubUrls {
+    /// Resolve the URLs from the environment of the process
+    pub fn from_env() -> Self {
+        Self::from_vars(std::env::vars())
+    }
+
+    /// Resolve the URLs from environment variables (name and value)
+    ///
+    /// Uses `GITHUB_SERVER_URL`, `GITHUB_API_URL` and `GITHUB_GRAPHQL_URL` (empty values are
+    /// treated as missing).
+    pub fn from_vars<K, V>(vars: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let vars: HashMap<String, String> = vars
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.into()))
+            .collect();
+        let var = |name: &str| vars.get(name).map(String::as_str);
+
+        Self::resolve(
+            var("GITHUB_SERVER_URL"),
+            var("GITHUB_API_URL"),
+            var("GITHUB_GRAPHQL_URL"),
+        )
+    }
+
+    /// Resolve the URLs, deriving the missing URLs from the server URL
+    ///
+    /// An API URL which is the GitHub Enterprise Server URL (without `/api/v3`) gets the
+    /// `/api/v3` suffix.
+    pub fn resolve(
+        server_url: Option<&str>,
+        api_url: Option<&str>,
+        graphql_url: Option<&str>,
+    ) -> Self {
+        let server_url = normalize(server_url).unwrap_or_else(|| DEFAULT_SERVER_URL.to_string());
+        let api_url = match normalize(api_url) {
+            Some(api_url) if api_url == server_url && !is_github_com(&server_url) => {
+                format!("{}/api/v3", api_url)
+            }
+            Some(api_url) => api_url,
+            None => api_url_for(&server_url),
+        };
+        let graphql_url = normalize(graphql_url).unwrap_or_else(|| graphql_url_for(&api_url));
+
+        Self {
+            server_url,
+            api_url,
+            graphql_url,
+        }
+    }
+
+    /// API URL with a trailing slash (base URI of the API clients, so paths are joined
+    /// after `/api/v3` on GitHub Enterprise Server)
+    pub fn api_base_uri(&self) -> String {
+        format!("{}/", self.api_url)
+    }
+}
+
+/// Percent encode a query parameter value (only unreserved characters are kept)
+pub(crate) fn encode_component(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Percent decode a URL component (invalid escapes are kept as is)
+#[cfg(feature = "http-client")]
+pub(crate) fn decode_component(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escape = bytes
+            .get(index + 1..index + 3)
+            .filter(|hex| bytes[index] == b'%' && hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escape {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Trim the URL and remove the trailing slashes (`None` if the URL is empty)
+fn normalize(url: Option<&str>) -> Option<String> {
+    url.map(|url| url.trim().trim_end_matches('/'))
+        .filter(|url| !url.is_empty())
+        .map(String::from)
+}
+
+/// Split the scheme and the host of a URL
+fn split_host(url: &str) -> (&str, &str) {
+    let (scheme, rest) = url.split_once("://").unwrap_or(("https", url));
+    (scheme, rest.split('/').next().unwrap_or_default())
+}
+
+fn is_github_com(server_url: &str) -> bool {
+    let (_, host) = split_host(server_url);
+    host.eq_ignore_ascii_case("github.com") || host.eq_ignore_ascii_case("www.github.com")
+}
+
+/// Derive the API URL from the server URL
+fn api_url_for(server_url: &str) -> String {
+    let (scheme, host) = split_host(server_url);
+    let is_root = server_url.ends_with(host);
+
+    if is_github_com(server_url) {
+        DEFAULT_API_URL.to_string()
+    } else if is_root && host.to_lowercase().ends_with(".ghe.com") {
+        format!("{}://api.{}", scheme, host)
+    } else {
+        format!("{}/api/v3", server_url)
+    }
+}
+
+/// Derive the GraphQL URL from the API URL
+fn graphql_url_for(api_url: &str) -> String {
+    match api_url.strip_suffix("/api/v3") {
+        Some(server_url) => format!("{}/api/graphql", server_url),
+        None => format!("{}/graphql", api_url),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_github_com() {
+        let expected = GitHubUrls {
+            server_url: "https://github.com".to_string(),
+            api_url: "https://api.github.com".to_string(),
+            graphql_url: "https://api.github.com/graphql".to_string(),
+        };
+        assert_eq!(GitHubUrls::default(), expected);
+        assert_eq!(
+            GitHubUrls::from_vars([
+                ("GITHUB_SERVER_URL", "https://github.com/"),
+                ("GITHUB_API_URL", "https://api.github.com/"),
+                ("GITHUB_GRAPHQL_URL", ""),
+            ]),
+            expected
+        );
+        assert_eq!(expected.api_base_uri(), "https://api.github.com/");
+    }
+
+    #[test]
+    fn test_enterprise_server() {
+        let expected = GitHubUrls {
+            server_url: "https://ghe.example.com".to_string(),
+            api_url: "https://ghe.example.com/api/v3".to_string(),
+            graphql_url: "https://ghe.example.com/api/graphql".to_string(),
+        };
+        // Set by the runner
+        assert_eq!(
+            GitHubUrls::from_vars([
+                ("GITHUB_SERVER_URL", "https://ghe.example.com"),
+                ("GITHUB_API_URL", "https://ghe.example.com/api/v3"),
+                ("GITHUB_GRAPHQL_URL", "https://ghe.example.com/api/graphql"),
+            ]),
+            expected
+        );
+        // Derived from the server URL
+        assert_eq!(
+            GitHubUrls::resolve(Some("https://ghe.example.com/"), None, None),
+            expected
+        );
+        // API URL without the `/api/v3` suffix or with a trailing slash
+        assert_eq!(
+            GitHubUrls::resolve(
+                Some("https://ghe.example.com"),
+                Some("https://ghe.example.com/"),
+                None
+            ),
+            expected
+        );
+        assert_eq!(
+            GitHubUrls::resolve(None, Some("https://ghe.example.com/api/v3/"), None).graphql_url,
+            expected.graphql_url
+        );
+        assert_eq!(expected.api_base_uri(), "https://ghe.example.com/api/v3/");
+    }
+
+    #[test]
+    fn test_ghe_com() {
+        let expected = GitHubUrls {
+            server_url: "https://octo.ghe.com".to_string(),
+            api_url: "https://api.octo.ghe.com".to_string(),
+            graphql_url: "https://api.octo.ghe.com/graphql".to_string(),
+        };
+        assert_eq!(
+            GitHubUrls::resolve(Some("https://octo.ghe.com"), None, None),
+            expected
+        );
+        assert_eq!(
+            GitHubUrls::from_vars([
+                ("GITHUB_SERVER_URL", "https://octo.ghe.com"),
+                ("GITHUB_API_URL", "https://api.octo.ghe.com/"),
+            ]),
+            expected
+        );
+    }
+}
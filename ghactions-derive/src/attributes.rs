@@ -1,3 +1,4 @@
+use ghactions_core::actions::branding;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{
@@ -43,6 +44,98 @@ pub(crate) enum ActionsAttributeKeys {
     Separator,
     /// Entrypoint
     Entrypoint,
+    /// Verify the Action file on init (in debug mode or CI)
+    VerifyOnInit,
+    /// Supported workflow events
+    Events,
+    /// What to do when the event is not supported (`error` or `skip`)
+    EventsMode,
+    /// Regex pattern the input value must match
+    Pattern,
+    /// Comma separated list of allowed input values
+    AllowedValues,
+    /// Compare allowed values case insensitively
+    IgnoreCase,
+    /// Deprecation message for the input
+    Deprecated,
+    /// Allow writing the Action file through a symlink
+    FollowSymlinks,
+    /// Presence based boolean flag
+    Flag,
+    /// Lenient boolean parsing (`1` / `0`, `yes` / `no` and `on` / `off`)
+    Lenient,
+    /// Trim surrounding whitespace from the input value
+    Trim,
+    /// Split the input value into lines
+    Multiline,
+    /// Environment variable (`KEY=value`) for the generated run step
+    RunStepEnv,
+    /// Name of the generated run step
+    RunStepName,
+    /// Name of the generated compile / install step
+    CompileStepName,
+    /// Step ID composite output values are read from
+    OutputStep,
+    /// Allow input values to contain `${{ }}` expressions
+    AllowExpressions,
+    /// Always write the output (even when empty)
+    Always,
+    /// Write the output value serialized as JSON
+    Json,
+    /// Minimum runner version the Action supports
+    MinRunnerVersion,
+    /// Mask the input value in the workflow logs
+    Secret,
+    /// User authored composite Action (keeps the existing `runs` section)
+    Composite,
+    /// Install the prebuilt binary from the releases (optionally using a custom script)
+    Installer,
+    /// Prefix of the output names written by the Action
+    OutputPrefix,
+    /// Generate the Dockerfile of a container Action
+    GenerateDockerfile,
+    /// Rust version of the generated Dockerfile builder image
+    RustVersion,
+    /// Base image of the generated Dockerfile
+    BaseImage,
+    /// Argument passed to the container
+    DockerArgs,
+    /// Environment variable (`KEY=value`) passed to the container
+    DockerEnv,
+    /// Pass the inputs to the container as arguments
+    DockerPassInputs,
+    /// Branding icon (Feather icon name)
+    BrandingIcon,
+    /// Branding background colour
+    BrandingColor,
+    /// How the Action file is generated (`write` or `check`)
+    Generate,
+    /// Input used as the deadline (total run time) of the Action
+    DeadlineInput,
+    /// Local inputs file loaded when not running in GitHub Actions
+    DevInputs,
+    /// Dotenv file loaded by `init` (or `false` to disable dotenv)
+    Dotenv,
+    /// Flatten the inputs of an input group into the Action
+    Flatten,
+    /// Field which is not an input or output (`#[action(skip)]`)
+    Skip,
+    /// Script run before the Action
+    PreEntrypoint,
+    /// Condition for running the pre entrypoint
+    PreIf,
+    /// Script run after the Action (cleanup)
+    PostEntrypoint,
+    /// Condition for running the post entrypoint
+    PostIf,
+    /// Node.js version of a JavaScript Action (e.g. `20`)
+    Node,
+    /// Main script of a JavaScript Action
+    Main,
+    /// Other name the input is read from (e.g. the old name of a renamed input)
+    Alias,
+    /// Add the input aliases to the Action file as deprecated inputs
+    DeprecateAliases,
 }
 
 #[derive(Debug, Clone)]
@@ -62,6 +155,11 @@ pub(crate) enum ActionsAttributeValue {
 }
 
 impl Parse for ActionsAttribute {
+    #[allow(
+        irrefutable_let_patterns,
+        clippy::to_string_in_format_args,
+        clippy::unnecessary_fallible_conversions
+    )]
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let name: Ident = input.parse()?;
         let name_str = name.to_string();
@@ -81,6 +179,52 @@ impl Parse for ActionsAttribute {
             "image" => Some(ActionsAttributeKeys::Image),
             "entrypoint" => Some(ActionsAttributeKeys::Entrypoint),
             "separator" | "split" => Some(ActionsAttributeKeys::Separator),
+            "verify_on_init" => Some(ActionsAttributeKeys::VerifyOnInit),
+            "events" => Some(ActionsAttributeKeys::Events),
+            "events_mode" => Some(ActionsAttributeKeys::EventsMode),
+            "pattern" => Some(ActionsAttributeKeys::Pattern),
+            "allowed_values" => Some(ActionsAttributeKeys::AllowedValues),
+            "ignore_case" => Some(ActionsAttributeKeys::IgnoreCase),
+            "deprecated" | "deprecation_message" => Some(ActionsAttributeKeys::Deprecated),
+            "follow_symlinks" => Some(ActionsAttributeKeys::FollowSymlinks),
+            "flag" => Some(ActionsAttributeKeys::Flag),
+            "lenient" => Some(ActionsAttributeKeys::Lenient),
+            "trim" => Some(ActionsAttributeKeys::Trim),
+            "multiline" => Some(ActionsAttributeKeys::Multiline),
+            "run_step_env" => Some(ActionsAttributeKeys::RunStepEnv),
+            "run_step_name" => Some(ActionsAttributeKeys::RunStepName),
+            "compile_step_name" => Some(ActionsAttributeKeys::CompileStepName),
+            "output_step" | "step" => Some(ActionsAttributeKeys::OutputStep),
+            "allow_expressions" => Some(ActionsAttributeKeys::AllowExpressions),
+            "always" => Some(ActionsAttributeKeys::Always),
+            "json" => Some(ActionsAttributeKeys::Json),
+            "min_runner_version" => Some(ActionsAttributeKeys::MinRunnerVersion),
+            "secret" => Some(ActionsAttributeKeys::Secret),
+            "composite" => Some(ActionsAttributeKeys::Composite),
+            "installer" => Some(ActionsAttributeKeys::Installer),
+            "output_prefix" => Some(ActionsAttributeKeys::OutputPrefix),
+            "generate_dockerfile" => Some(ActionsAttributeKeys::GenerateDockerfile),
+            "rust_version" => Some(ActionsAttributeKeys::RustVersion),
+            "base_image" => Some(ActionsAttributeKeys::BaseImage),
+            "docker_args" | "docker_arg" => Some(ActionsAttributeKeys::DockerArgs),
+            "docker_env" => Some(ActionsAttributeKeys::DockerEnv),
+            "docker_pass_inputs" => Some(ActionsAttributeKeys::DockerPassInputs),
+            "icon" => Some(ActionsAttributeKeys::BrandingIcon),
+            "color" | "colour" => Some(ActionsAttributeKeys::BrandingColor),
+            "generate" => Some(ActionsAttributeKeys::Generate),
+            "deadline_input" => Some(ActionsAttributeKeys::DeadlineInput),
+            "dev_inputs" => Some(ActionsAttributeKeys::DevInputs),
+            "dotenv" => Some(ActionsAttributeKeys::Dotenv),
+            "flatten" => Some(ActionsAttributeKeys::Flatten),
+            "skip" => Some(ActionsAttributeKeys::Skip),
+            "pre_entrypoint" => Some(ActionsAttributeKeys::PreEntrypoint),
+            "pre_if" => Some(ActionsAttributeKeys::PreIf),
+            "post_entrypoint" => Some(ActionsAttributeKeys::PostEntrypoint),
+            "post_if" => Some(ActionsAttributeKeys::PostIf),
+            "node" => Some(ActionsAttributeKeys::Node),
+            "main" => Some(ActionsAttributeKeys::Main),
+            "alias" => Some(ActionsAttributeKeys::Alias),
+            "deprecate_aliases" => Some(ActionsAttributeKeys::DeprecateAliases),
             _ => {
                 return Err(syn::Error::new(
                     name.span(),
@@ -151,27 +295,35 @@ impl ActionsAttribute {
             // TODO: This could be nicer
             if attribute.path().is_ident("action") {
                 name = String::from("action");
-                for attr in attribute
-                    .parse_args_with(Punctuated::<ActionsAttribute, Token![,]>::parse_terminated)?
-                {
+                let attrs = attribute
+                    .parse_args_with(Punctuated::<ActionsAttribute, Token![,]>::parse_terminated)?;
+                let generate_dockerfile = attrs.iter().any(|attr| {
+                    attr.key == Some(ActionsAttributeKeys::GenerateDockerfile)
+                        && !matches!(attr.value, Some(ActionsAttributeValue::Bool(false)))
+                });
+                for attr in attrs {
+                    // The Dockerfile is generated so it does not need to exist yet
+                    if generate_dockerfile
+                        && attr.key == Some(ActionsAttributeKeys::Image)
+                        && matches!(attr.value, Some(ActionsAttributeValue::Path(_)))
+                    {
+                        parsed.push(attr);
+                        continue;
+                    }
                     // Validate the attribute before adding it to the parsed list
                     attr.validate()?;
                     parsed.push(attr);
                 }
             } else if attribute.path().is_ident("input") {
                 name = String::from("input");
-                for attr in attribute
-                    .parse_args_with(Punctuated::<ActionsAttribute, Token![,]>::parse_terminated)?
-                {
+                for attr in Self::parse_args(attribute)? {
                     // Validate the attribute before adding it to the parsed list
                     attr.validate()?;
                     parsed.push(attr);
                 }
             } else if attribute.path().is_ident("output") {
                 name = String::from("output");
-                for attr in attribute
-                    .parse_args_with(Punctuated::<ActionsAttribute, Token![,]>::parse_terminated)?
-                {
+                for attr in Self::parse_args(attribute)? {
                     // Validate the attribute before adding it to the parsed list
                     attr.validate()?;
                     parsed.push(attr);
@@ -183,7 +335,18 @@ impl ActionsAttribute {
         Ok((name, parsed))
     }
 
-    #[allow(irrefutable_let_patterns)]
+    /// Parse the arguments of an attribute (`#[input]` has no arguments)
+    fn parse_args(
+        attribute: &Attribute,
+    ) -> Result<Punctuated<ActionsAttribute, Token![,]>, syn::Error> {
+        match attribute.meta {
+            syn::Meta::Path(_) => Ok(Punctuated::new()),
+            _ => attribute
+                .parse_args_with(Punctuated::<ActionsAttribute, Token![,]>::parse_terminated),
+        }
+    }
+
+    #[allow(irrefutable_let_patterns, clippy::needless_return)]
     pub(crate) fn validate(&self) -> Result<(), syn::Error> {
         match self.key {
             Some(ActionsAttributeKeys::Path) => {
@@ -307,6 +470,336 @@ impl ActionsAttribute {
                     ));
                 }
             }
+            Some(ActionsAttributeKeys::Events) => {
+                if let Some(ActionsAttributeValue::String(value)) = &self.value {
+                    if value.split(',').any(|event| event.trim().is_empty()) {
+                        Err(syn::Error::new(
+                            self.value_span.unwrap(),
+                            "Events attribute must be a comma separated list of event names",
+                        ))
+                    } else {
+                        Ok(())
+                    }
+                } else {
+                    Err(syn::Error::new(
+                        self.span.span(),
+                        "Events attribute must have a string value",
+                    ))
+                }
+            }
+            Some(ActionsAttributeKeys::EventsMode) => match &self.value {
+                Some(ActionsAttributeValue::String(value))
+                    if value == "error" || value == "skip" =>
+                {
+                    Ok(())
+                }
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Events mode attribute must be `error` or `skip`",
+                )),
+            },
+            Some(ActionsAttributeKeys::Pattern) => {
+                if let Some(ActionsAttributeValue::String(value)) = &self.value {
+                    regex::Regex::new(value).map(|_| ()).map_err(|err| {
+                        syn::Error::new(
+                            self.value_span.unwrap(),
+                            format!("Pattern attribute must be a valid regex: {}", err),
+                        )
+                    })
+                } else {
+                    Err(syn::Error::new(
+                        self.span.span(),
+                        "Pattern attribute must have a string value",
+                    ))
+                }
+            }
+            Some(ActionsAttributeKeys::AllowedValues) => {
+                if let Some(ActionsAttributeValue::String(value)) = &self.value {
+                    if value.split(',').any(|allowed| allowed.trim().is_empty()) {
+                        Err(syn::Error::new(
+                            self.value_span.unwrap(),
+                            "Allowed values attribute must be a comma separated list of values",
+                        ))
+                    } else {
+                        Ok(())
+                    }
+                } else {
+                    Err(syn::Error::new(
+                        self.span.span(),
+                        "Allowed values attribute must have a string value",
+                    ))
+                }
+            }
+            Some(ActionsAttributeKeys::IgnoreCase) => match self.value {
+                None | Some(ActionsAttributeValue::Bool(_)) => Ok(()),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Ignore case attribute must have a boolean value",
+                )),
+            },
+            Some(ActionsAttributeKeys::Secret) => match self.value {
+                None | Some(ActionsAttributeValue::Bool(_)) => Ok(()),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Secret attribute must have a boolean value",
+                )),
+            },
+            Some(ActionsAttributeKeys::Installer) => match &self.value {
+                None | Some(ActionsAttributeValue::Bool(_)) => Ok(()),
+                Some(ActionsAttributeValue::Path(path)) if path.exists() => Ok(()),
+                Some(ActionsAttributeValue::Path(_)) => Err(syn::Error::new(
+                    self.value_span.unwrap(),
+                    "Installer attribute must have a valid path value (file not found)",
+                )),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Installer attribute must be a flag or a script path (e.g. `./installer.sh`)",
+                )),
+            },
+            Some(ActionsAttributeKeys::GenerateDockerfile) => match self.value {
+                None | Some(ActionsAttributeValue::Bool(_)) => Ok(()),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Generate Dockerfile attribute must have a boolean value",
+                )),
+            },
+            Some(ActionsAttributeKeys::RustVersion) | Some(ActionsAttributeKeys::BaseImage) => {
+                match &self.value {
+                    Some(ActionsAttributeValue::String(value))
+                        if !value.is_empty() && !value.contains(char::is_whitespace) =>
+                    {
+                        Ok(())
+                    }
+                    _ => Err(syn::Error::new(
+                        self.span.span(),
+                        format!(
+                            "`{}` attribute must be an image tag / name without whitespace",
+                            self.span
+                        ),
+                    )),
+                }
+            }
+            Some(ActionsAttributeKeys::Composite) => match self.value {
+                None | Some(ActionsAttributeValue::Bool(_)) => Ok(()),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Composite attribute must have a boolean value",
+                )),
+            },
+            Some(ActionsAttributeKeys::Deprecated) => {
+                if let Some(ActionsAttributeValue::String(_)) = &self.value {
+                    Ok(())
+                } else {
+                    Err(syn::Error::new(
+                        self.span.span(),
+                        "Deprecated attribute must have a string value",
+                    ))
+                }
+            }
+            Some(ActionsAttributeKeys::Generate) => match &self.value {
+                Some(ActionsAttributeValue::String(value))
+                    if value == "write" || value == "check" =>
+                {
+                    Ok(())
+                }
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Generate attribute must be `write` or `check`",
+                )),
+            },
+            Some(ActionsAttributeKeys::DockerArgs) => match &self.value {
+                Some(ActionsAttributeValue::String(_)) => Ok(()),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Docker args attribute must have a string value",
+                )),
+            },
+            Some(ActionsAttributeKeys::DockerPassInputs) => match &self.value {
+                Some(ActionsAttributeValue::String(value)) if value == "args" => Ok(()),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Docker pass inputs attribute only supports `args`",
+                )),
+            },
+            Some(ActionsAttributeKeys::RunStepEnv) | Some(ActionsAttributeKeys::DockerEnv) => {
+                match &self.value {
+                    Some(ActionsAttributeValue::String(value))
+                        if value
+                            .split_once('=')
+                            .is_some_and(|(key, _)| !key.trim().is_empty()) =>
+                    {
+                        Ok(())
+                    }
+                    _ => Err(syn::Error::new(
+                        self.span.span(),
+                        format!(
+                            "`{}` attribute must have a `KEY=value` string value",
+                            self.span
+                        ),
+                    )),
+                }
+            }
+            Some(ActionsAttributeKeys::RunStepName)
+            | Some(ActionsAttributeKeys::CompileStepName) => match &self.value {
+                Some(ActionsAttributeValue::String(value)) if !value.is_empty() => Ok(()),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Step name attribute must have a non-empty string value",
+                )),
+            },
+            Some(ActionsAttributeKeys::OutputStep) => match &self.value {
+                Some(ActionsAttributeValue::String(value))
+                    if ghactions_core::actions::names::is_valid_name(value) =>
+                {
+                    Ok(())
+                }
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Output step attribute must be a valid step ID (e.g. `run-tool`)",
+                )),
+            },
+            Some(ActionsAttributeKeys::OutputPrefix) => match &self.value {
+                Some(ActionsAttributeValue::String(value))
+                    if ghactions_core::actions::names::is_valid_name(value) =>
+                {
+                    Ok(())
+                }
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Output prefix attribute must be a valid output name prefix (e.g. `plan-`)",
+                )),
+            },
+            Some(ActionsAttributeKeys::BrandingIcon) => match &self.value {
+                Some(ActionsAttributeValue::String(value)) if branding::is_valid_icon(value) => {
+                    Ok(())
+                }
+                Some(ActionsAttributeValue::String(value)) => Err(syn::Error::new(
+                    self.value_span.unwrap(),
+                    format!(
+                        "Icon `{}` is not supported by GitHub (suggestion: `{}`)",
+                        value,
+                        branding::closest(value, &branding::ALLOWED_ICONS).unwrap_or_default()
+                    ),
+                )),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Icon attribute must have a string value",
+                )),
+            },
+            Some(ActionsAttributeKeys::BrandingColor) => match &self.value {
+                Some(ActionsAttributeValue::String(value)) if branding::is_valid_colour(value) => {
+                    Ok(())
+                }
+                Some(ActionsAttributeValue::String(value)) => Err(syn::Error::new(
+                    self.value_span.unwrap(),
+                    format!(
+                        "Colour `{}` is not supported by GitHub (suggestion: `{}`, allowed: {})",
+                        value,
+                        branding::closest(value, &branding::ALLOWED_COLOURS).unwrap_or_default(),
+                        branding::ALLOWED_COLOURS.join(", ")
+                    ),
+                )),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Colour attribute must have a string value",
+                )),
+            },
+            Some(ActionsAttributeKeys::MinRunnerVersion) => match &self.value {
+                Some(ActionsAttributeValue::String(value))
+                    if ghactions_core::runner::parse_version(value).is_some() =>
+                {
+                    Ok(())
+                }
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Min runner version attribute must be a version (e.g. `2.300.0`)",
+                )),
+            },
+            Some(ActionsAttributeKeys::PreEntrypoint)
+            | Some(ActionsAttributeKeys::PostEntrypoint) => match &self.value {
+                Some(ActionsAttributeValue::Path(_)) => Ok(()),
+                Some(ActionsAttributeValue::String(value)) if !value.is_empty() => Ok(()),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Pre / post entrypoint attributes must be a script path (e.g. `./cleanup.sh`)",
+                )),
+            },
+            Some(ActionsAttributeKeys::Alias) => match &self.value {
+                Some(ActionsAttributeValue::String(value))
+                    if ghactions_core::actions::names::is_valid_name(value) =>
+                {
+                    Ok(())
+                }
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Alias attribute must be a valid input name (e.g. `gh-token`)",
+                )),
+            },
+            Some(ActionsAttributeKeys::DeprecateAliases) => match self.value {
+                None | Some(ActionsAttributeValue::Bool(_)) => Ok(()),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Deprecate aliases attribute must have a boolean value",
+                )),
+            },
+            Some(ActionsAttributeKeys::Node) => match &self.value {
+                Some(ActionsAttributeValue::Int(version)) if *version > 0 => Ok(()),
+                Some(ActionsAttributeValue::String(version))
+                    if !version.is_empty() && version.chars().all(|c| c.is_ascii_digit()) =>
+                {
+                    Ok(())
+                }
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Node attribute must be a Node.js major version (e.g. `20`)",
+                )),
+            },
+            Some(ActionsAttributeKeys::Main) => match &self.value {
+                Some(ActionsAttributeValue::Path(_)) => Ok(()),
+                Some(ActionsAttributeValue::String(value)) if !value.is_empty() => Ok(()),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Main attribute must be a script path (e.g. `./dist/index.js`)",
+                )),
+            },
+            Some(ActionsAttributeKeys::PreIf) | Some(ActionsAttributeKeys::PostIf) => {
+                match &self.value {
+                    Some(ActionsAttributeValue::String(value)) if !value.is_empty() => Ok(()),
+                    _ => Err(syn::Error::new(
+                        self.span.span(),
+                        "Pre / post if attributes must be a condition (e.g. `always()`)",
+                    )),
+                }
+            }
+            Some(ActionsAttributeKeys::DeadlineInput) => match &self.value {
+                Some(ActionsAttributeValue::String(value))
+                    if ghactions_core::actions::names::is_valid_name(value) =>
+                {
+                    Ok(())
+                }
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Deadline input attribute must be the name of an input (e.g. `timeout`)",
+                )),
+            },
+            Some(ActionsAttributeKeys::DevInputs) => match &self.value {
+                Some(ActionsAttributeValue::Path(_)) => Ok(()),
+                Some(ActionsAttributeValue::String(value)) if !value.is_empty() => Ok(()),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Dev inputs attribute must be a file path (e.g. `./inputs.local.yml`)",
+                )),
+            },
+            Some(ActionsAttributeKeys::Dotenv) => match &self.value {
+                Some(ActionsAttributeValue::Path(_)) | Some(ActionsAttributeValue::Bool(_)) => {
+                    Ok(())
+                }
+                Some(ActionsAttributeValue::String(value)) if !value.is_empty() => Ok(()),
+                _ => Err(syn::Error::new(
+                    self.span.span(),
+                    "Dotenv attribute must be a file path (e.g. `.env.local`) or `false`",
+                )),
+            },
             _ => Ok(()),
         }
     }
@@ -0,0 +1,182 @@
+//! Input groups (`#[derive(ActionInputs)]`) flattened into Actions using `#[input(flatten)]`
+//!
+//! A derive macro only sees the struct it is applied to, so the input group derive defines
+//! a (crate visible) macro which passes the group struct back to the Action derive:
+//!
+//! 1. `#[derive(Actions)]` finds the first unresolved `#[input(flatten)]` field and calls
+//!    the macro of its group (`__ghactions_inputs_{Group}!`) with the Action struct
+//! 2. The group macro calls `__derive_flattened!` with the Action struct and the groups
+//! 3. This repeats until all the groups are resolved and the Action is derived
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{spanned::Spanned, Data, DataStruct, DeriveInput, Fields};
+
+use super::{generate_inputs, name_span, parse_input};
+use crate::attributes::{ActionsAttribute, ActionsAttributeKeys, ActionsAttributeValue};
+use ghactions_core::{ActionInput, ActionYML};
+
+/// Action struct and the input groups resolved so far
+pub(crate) struct Flattened {
+    pub(crate) action: DeriveInput,
+    pub(crate) groups: Vec<DeriveInput>,
+}
+
+impl Parse for Flattened {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let action = input.parse()?;
+        let mut groups = Vec::new();
+        while !input.is_empty() {
+            groups.push(input.parse()?);
+        }
+        Ok(Self { action, groups })
+    }
+}
+
+/// Check if the input attributes contain `flatten`
+pub(crate) fn is_flatten(attributes: &[ActionsAttribute]) -> bool {
+    attributes.iter().any(|attr| {
+        attr.key == Some(ActionsAttributeKeys::Flatten)
+            && !matches!(attr.value, Some(ActionsAttributeValue::Bool(false)))
+    })
+}
+
+/// Fields using `#[input(flatten)]` (in declaration order)
+pub(crate) fn flatten_fields(fields: &syn::FieldsNamed) -> Result<Vec<&syn::Field>, syn::Error> {
+    let mut flatten = Vec::new();
+    for field in fields.named.iter() {
+        let (name, attributes) = ActionsAttribute::parse_all(&field.attrs)?;
+        if name == "input" && is_flatten(&attributes) {
+            flatten.push(field);
+        }
+    }
+    Ok(flatten)
+}
+
+/// Name of the macro defined by the input group derive
+fn group_macro(ident: &syn::Ident) -> syn::Ident {
+    format_ident!("__ghactions_inputs_{}", ident)
+}
+
+/// Call the macro of the input group of `field` to resolve it
+pub(crate) fn resolve_group(
+    ast: &DeriveInput,
+    groups: &[DeriveInput],
+    field: &syn::Field,
+) -> Result<TokenStream, syn::Error> {
+    let mut path = match &field.ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.clone(),
+        ty => {
+            return Err(syn::Error::new(
+                ty.span(),
+                "Flattened inputs must be a struct deriving `ActionInputs`",
+            ))
+        }
+    };
+    let segment = path.segments.last_mut().unwrap();
+    segment.ident = group_macro(&segment.ident);
+    segment.arguments = syn::PathArguments::None;
+
+    Ok(quote! {
+        #path! { #ast #(#groups)* }
+    })
+}
+
+/// Parse the inputs of an input group flattened into the `field` of an Action
+pub(crate) fn parse_group(
+    group: &DeriveInput,
+    field: &syn::Ident,
+) -> Result<Vec<(ActionInput, Span)>, syn::Error> {
+    let mut inputs = Vec::new();
+    for group_field in group_fields(group)?.named.iter() {
+        let (name, attributes) = ActionsAttribute::parse_all(&group_field.attrs)?;
+        if name == "input" {
+            let mut input = parse_input(group_field, &attributes)?;
+            input.group = Some(field.to_string());
+            let span = name_span(&attributes, group_field.ident.as_ref().unwrap());
+            inputs.push((input, span));
+        }
+    }
+    Ok(inputs)
+}
+
+fn group_fields(group: &DeriveInput) -> Result<&syn::FieldsNamed, syn::Error> {
+    match group.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(ref fields),
+            ..
+        }) => Ok(fields),
+        _ => Err(syn::Error::new(
+            group.span(),
+            "Only structs with named fields are supported",
+        )),
+    }
+}
+
+/// Derive an input group (`#[derive(ActionInputs)]`)
+pub(crate) fn derive_input_group(ast: &DeriveInput) -> Result<TokenStream, syn::Error> {
+    let ident = &ast.ident;
+    let fields = group_fields(ast)?;
+
+    let mut action = ActionYML::default();
+    for field in fields.named.iter() {
+        let field_name = field.ident.as_ref().unwrap();
+        let (name, attributes) = ActionsAttribute::parse_all(&field.attrs)?;
+        match name.as_str() {
+            "input" if is_flatten(&attributes) => {
+                return Err(syn::Error::new(
+                    field_name.span(),
+                    "Input groups can not be nested",
+                ))
+            }
+            "input" => {
+                let input = parse_input(field, &attributes)?;
+                if action.inputs.contains_key(&input.action_name) {
+                    return Err(syn::Error::new(
+                        name_span(&attributes, field_name),
+                        format!("Input `{}` is defined more than once", input.action_name),
+                    ));
+                }
+                action.inputs.insert(input.action_name.clone(), input);
+            }
+            "output" => {
+                return Err(syn::Error::new(
+                    field_name.span(),
+                    "Input groups only support inputs",
+                ))
+            }
+            _ => {}
+        }
+    }
+
+    let (inputstream, selfstream) = generate_inputs(ident, fields, &action, &quote! { __A })?;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let group_macro = group_macro(ident);
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::ghactions::InputGroup for #ident #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn from_inputs<__A: ::ghactions::ActionTrait>(
+                errors: &mut Vec<::ghactions::ActionsError>,
+            ) -> Self {
+                #inputstream
+
+                Self {
+                    #selfstream
+                }
+            }
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #group_macro {
+            ($($tokens:tt)*) => {
+                ::ghactions::__derive_flattened! { $($tokens)* #ast }
+            };
+        }
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #group_macro;
+    })
+}
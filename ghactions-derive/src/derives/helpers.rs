@@ -1,37 +1,134 @@
 use ghactions_core::ActionYML;
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{quote, ToTokens};
+
+use super::supported_events;
+use crate::attributes::ActionsAttribute;
 
 pub(crate) fn generate_helpers(
     ident: &syn::Ident,
     fields: &syn::FieldsNamed,
-    _generics: &syn::Generics,
+    generics: &syn::Generics,
+    attributes: &[ActionsAttribute],
     action: &ActionYML,
 ) -> Result<TokenStream, syn::Error> {
     let mut tokens = TokenStream::new();
 
     // Generate the `set_{}` functions
     let mut set_functions = TokenStream::new();
-    let outputs: Vec<String> = action.outputs.keys().cloned().collect();
-
     for field in fields.named.iter() {
         let field_name = field.ident.as_ref().unwrap();
 
-        if outputs.contains(&field_name.to_string()) {
+        // Outputs are keyed by the Action output name (which can be renamed)
+        let output = action
+            .outputs
+            .iter()
+            .find(|(_, output)| output.field_name == *field_name.to_string());
+
+        if let Some((output_name, output)) = output {
             let func_name = format!("set_{}", field_name);
             let func = syn::Ident::new(&func_name, Span::call_site());
+            let field_type = &field.ty;
+
+            // JSON outputs can fail to serialize so the setter returns the error
+            if output.json {
+                set_functions.extend(quote! {
+                    /// Set the output value and write it (serialized as JSON) to the Actions
+                    /// output file
+                    pub fn #func(&mut self, value: #field_type) -> Result<(), ::ghactions::ActionsError> {
+                        self.#field_name = value;
+                        <Self as ::ghactions::ActionTrait>::set_output_json(#output_name, &self.#field_name)
+                    }
+                });
+                continue;
+            }
+
+            // String outputs accept anything that converts into a String, all other
+            // types are written using `Display`
+            let (value, assign) = match field_type.to_token_stream().to_string().as_str() {
+                "String" => (quote! { value: impl Into<String> }, quote! { value.into() }),
+                _ => (quote! { value: #field_type }, quote! { value }),
+            };
 
             set_functions.extend(quote! {
-                pub fn #func(&self, value: impl Into<String>) {
-                    ::ghactions::setoutput!(stringify!(#field_name), value.into());
+                /// Set the output value and write it to the Actions output file
+                pub fn #func(&mut self, #value) -> Result<(), ::ghactions::ActionsError> {
+                    self.#field_name = #assign;
+                    <Self as ::ghactions::ActionTrait>::set_output(
+                        #output_name,
+                        self.#field_name.to_string().as_str(),
+                    )
                 }
             });
         }
     }
 
+    // Generate the `write_outputs` function (skipping empty outputs unless `always` is set)
+    let mut output_values = TokenStream::new();
+    for (output_name, output) in action.outputs.iter() {
+        let field_name = syn::Ident::new(&output.field_name, Span::call_site());
+        let always = output.always;
+        let value = match output.json {
+            true => quote! { ::ghactions::to_json_output(&self.#field_name)? },
+            false => quote! { self.#field_name.to_string() },
+        };
+        output_values.extend(quote! {
+            let value = #value;
+            if #always || !value.is_empty() {
+                outputs.push((#output_name, value));
+            }
+        });
+    }
+    set_functions.extend(quote! {
+        /// Write all the output values to the Actions output file at once
+        pub fn write_outputs(&self) -> Result<(), ::ghactions::ActionsError> {
+            let mut outputs: Vec<(&str, String)> = Vec::new();
+            #output_values
+            ::ghactions::write_outputs(&outputs)
+        }
+    });
+
+    // Generate the event guard functions
+    let events = supported_events(attributes);
+    if !events.is_empty() {
+        set_functions.extend(quote! {
+            /// Workflow events this Action supports
+            pub fn supported_events() -> &'static [&'static str] {
+                &[#(#events),*]
+            }
+
+            /// Check if the Action should be skipped as the current workflow event
+            /// is not supported
+            ///
+            /// With `events_mode = "skip"`, `init()` returns `ActionsError::SkippedEvent`
+            /// on these events, so a `main` which does not use `ghactions::run` checks
+            /// this before `init()` to exit successfully.
+            pub fn should_skip() -> bool {
+                match ::ghactions::env::var("GITHUB_EVENT_NAME") {
+                    Some(event) => !Self::supported_events().contains(&event.as_str()),
+                    None => false,
+                }
+            }
+        });
+    }
+
+    // Embed the expected Action file so it can be verified at runtime
+    let expected = action
+        .to_yaml()
+        .map_err(|err| syn::Error::new(ident.span(), err.to_string()))?;
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     tokens.extend(quote! {
-        impl #ident {
+        impl #impl_generics #ident #ty_generics #where_clause {
             #set_functions
+
+            /// Verify the Action file at the provided path matches the inputs and outputs
+            /// this Action expects
+            pub fn verify_action_file(
+                path: impl AsRef<::std::path::Path>,
+            ) -> Result<(), Vec<::ghactions::SchemaMismatch>> {
+                ::ghactions::ActionYML::verify_file(#expected, path)
+            }
         }
     });
 
@@ -1,131 +1,259 @@
 use helpers::generate_helpers;
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::{spanned::Spanned, Data, DataStruct, DeriveInput, Fields};
 
+pub(crate) mod flatten;
 mod helpers;
 
 use crate::attributes::{ActionsAttribute, ActionsAttributeKeys, ActionsAttributeValue};
 use ghactions_core::{
-    actions::models::{ActionOutput, ActionRunUsing},
+    actions::dockerfile::Dockerfile,
+    actions::models::{ActionBranding, ActionOutput, ActionRunUsing},
+    actions::names::{is_valid_name, sanitize_name},
+    env_files::OUTPUT_PREFIX_ENV,
     ActionInput, ActionYML,
 };
 
-pub(crate) fn derive_parser(ast: &DeriveInput) -> Result<TokenStream, syn::Error> {
+/// Parse the Action struct, `groups` are the input groups flattened into the Action
+/// (in field order) resolved so far
+#[allow(clippy::needless_borrow, clippy::redundant_pattern_matching)]
+pub(crate) fn derive_parser(
+    ast: &DeriveInput,
+    groups: &[DeriveInput],
+) -> Result<TokenStream, syn::Error> {
     let name = &ast.ident;
     let (_, attributes) = ActionsAttribute::parse_all(&ast.attrs)?;
 
     let mut action = load_actionyaml(&attributes)?;
+    // The `description` attribute wins over the doc comment
+    if action.description.is_none() {
+        action.description = doc_comment(&ast.attrs);
+    }
 
     match ast.data {
         Data::Struct(DataStruct {
             fields: Fields::Named(ref fields),
             ..
         }) => {
+            // Input groups are defined outside of the struct so they are resolved one at a time
+            if let Some(field) = flatten::flatten_fields(fields)?.get(groups.len()) {
+                return flatten::resolve_group(ast, groups, field);
+            }
+            let mut groups = groups.iter();
+            let mut spans = std::collections::HashMap::new();
+
             for field in fields.named.iter() {
                 let field_name = field.ident.as_ref().unwrap();
-                let field_type = &field.ty;
 
                 let (name, field_attributes) = ActionsAttribute::parse_all(&field.attrs)?;
+                skipped_field(field, &name, &field_attributes)?;
 
                 match name.as_str() {
+                    "input" if flatten::is_flatten(&field_attributes) => {
+                        if field_attributes.len() > 1 {
+                            return Err(syn::Error::new(
+                                field_name.span(),
+                                "Flatten attribute can not be used with other input attributes",
+                            ));
+                        }
+                        let group = groups.next().expect("input groups are resolved");
+                        for (input, span) in flatten::parse_group(group, field_name)? {
+                            insert_input(&mut action, &mut spans, input, span)?;
+                        }
+                    }
                     "input" => {
-                        let mut input = ActionInput::default();
-
-                        input.field_name = field_name.to_string();
-                        input.r#type = field_type.to_token_stream().to_string();
+                        let input = parse_input(field, &field_attributes)?;
+                        let span = name_span(&field_attributes, field_name);
+                        insert_input(&mut action, &mut spans, input, span)?;
+                    }
+                    "output" => {
+                        let mut output = ActionOutput {
+                            field_name: field_name.to_string(),
+                            ..Default::default()
+                        };
+                        let mut output_name = field_name.to_string();
+                        let mut output_step = action.output_value_step_id.clone();
 
                         field_attributes.iter().for_each(|attr| match attr {
                             ActionsAttribute {
-                                key: Some(ActionsAttributeKeys::Name),
-                                value: Some(ActionsAttributeValue::String(name)),
+                                key: Some(ActionsAttributeKeys::Description),
+                                value: Some(ActionsAttributeValue::String(description)),
                                 ..
                             } => {
-                                input.action_name = name.to_string();
+                                output.description = Some(description.clone());
                             }
                             ActionsAttribute {
-                                key: Some(ActionsAttributeKeys::Description),
-                                value: Some(ActionsAttributeValue::String(description)),
+                                key: Some(ActionsAttributeKeys::Name),
+                                value: Some(ActionsAttributeValue::String(name)),
                                 ..
                             } => {
-                                input.description = Some(description.clone());
+                                output_name = name.clone();
                             }
                             ActionsAttribute {
-                                key: Some(ActionsAttributeKeys::Required),
-                                value: Some(ActionsAttributeValue::Bool(required)),
+                                key: Some(ActionsAttributeKeys::OutputStep),
+                                value: Some(ActionsAttributeValue::String(step)),
                                 ..
                             } => {
-                                input.required = Some(*required);
+                                output_step = Some(step.clone());
                             }
                             ActionsAttribute {
-                                key: Some(ActionsAttributeKeys::Default),
-                                value: Some(ActionsAttributeValue::String(default)),
+                                key: Some(ActionsAttributeKeys::Always),
+                                value,
                                 ..
                             } => {
-                                input.default = Some(default.clone());
+                                output.always =
+                                    !matches!(value, Some(ActionsAttributeValue::Bool(false)));
                             }
                             ActionsAttribute {
-                                key: Some(ActionsAttributeKeys::Separator),
-                                value: Some(ActionsAttributeValue::String(separator)),
+                                key: Some(ActionsAttributeKeys::Json),
+                                value,
                                 ..
                             } => {
-                                input.separator = Some(separator.clone());
+                                output.json =
+                                    !matches!(value, Some(ActionsAttributeValue::Bool(false)));
                             }
                             _ => {}
                         });
-
-                        // If the name is empty, use the field name
-                        if input.action_name.is_empty() {
-                            input.action_name = field_name.to_string();
+                        if output.description.is_none() {
+                            output.description = doc_comment(&field.attrs);
                         }
 
-                        // Needs to be the Action name as that is the name
-                        // that will be used in the action.yml file
-                        action.inputs.insert(input.action_name.to_string(), input);
-                    }
-                    "output" => {
-                        let mut output = ActionOutput::default();
+                        validate_name(
+                            "output",
+                            &output_name,
+                            name_span(&field_attributes, field_name),
+                        )?;
+
                         // Step ID is required for composite action outputs
-                        if let Some(ref step_id) = action.output_value_step_id {
+                        // (container actions don't have steps)
+                        if output_step.is_some() && action.runs.steps.is_none() {
+                            return Err(syn::Error::new(
+                                field_name.span(),
+                                "Output step attribute is only supported for composite Actions",
+                            ));
+                        }
+                        if let Some(ref step_id) = output_step {
                             output.value = Some(format!(
-                                "${{{{ steps.{}.outputs.{} }}}}",
+                                "${{{{ steps.{}.outputs.{}{} }}}}",
                                 step_id,
-                                field_name.to_string()
+                                action.output_prefix.as_deref().unwrap_or_default(),
+                                output_name
                             ));
                         }
 
-                        match field_attributes
-                            .iter()
-                            .find(|attr| attr.key == Some(ActionsAttributeKeys::Description))
-                        {
-                            Some(ActionsAttribute {
-                                value: Some(ActionsAttributeValue::String(description)),
-                                ..
-                            }) => {
-                                output.description = Some(description.clone());
-                            }
-                            _ => {}
-                        }
-
-                        action.outputs.insert(field_name.to_string(), output);
+                        action.outputs.insert(output_name, output);
                     }
                     _ => {}
                 }
             }
 
-            let mut tokens = generate_traits(name, &fields, &ast.generics, &action)?;
+            // Pass the inputs to the container as arguments (in declaration order)
+            if attributes
+                .iter()
+                .any(|attr| attr.key == Some(ActionsAttributeKeys::DockerPassInputs))
+            {
+                let inputs: Vec<String> = action
+                    .inputs
+                    .keys()
+                    .map(|name| format!("${{{{ inputs.{} }}}}", name))
+                    .collect();
+                action
+                    .runs
+                    .args
+                    .get_or_insert_with(Default::default)
+                    .extend(inputs);
+            }
+
+            // Document the supported events in the Action description
+            let events = supported_events(&attributes);
+            if !events.is_empty() {
+                let description = action.description.clone().unwrap_or_default();
+                action.description = Some(
+                    format!("{} (Supported events: {})", description, events.join(", "))
+                        .trim_start()
+                        .to_string(),
+                );
+            }
+
+            let mut tokens = generate_traits(name, &fields, &ast.generics, &attributes, &action)?;
+
+            // Aliases are only in the Action file when deprecated (they are read by `init()`)
+            action.inputs = action
+                .inputs
+                .into_iter()
+                .flat_map(|(name, input)| {
+                    let aliases = match input.deprecate_aliases {
+                        true => input.aliases.clone(),
+                        false => Vec::new(),
+                    };
+                    let message = format!("Use `{}` instead", name);
+                    let description = input.description.clone();
+                    let deprecated = aliases.into_iter().map(move |alias| {
+                        let input = ActionInput {
+                            action_name: alias.clone(),
+                            description: description.clone(),
+                            deprecation_message: Some(message.clone()),
+                            ..Default::default()
+                        };
+                        (alias, input)
+                    });
+                    std::iter::once((name, input)).chain(deprecated)
+                })
+                .collect();
+
+            tokens.extend(generate_helpers(
+                name,
+                &fields,
+                &ast.generics,
+                &attributes,
+                &action,
+            )?);
 
-            tokens.extend(generate_helpers(name, &fields, &ast.generics, &action)?);
+            let dockerfile = dockerfile_options(&attributes, &action)?;
 
             // Generate the action.yml file if the feature is enabled
+            // (or check it is up to date when `GHACTIONS_CHECK` is set, e.g. in CI)
             #[cfg(feature = "generate")]
             {
+                let check = check_mode(&attributes);
                 if let Some(_) = &action.path {
-                    action
-                        .write()
-                        .map_err(|e| syn::Error::new(ast.span(), e.to_string()))?;
+                    if check {
+                        action.check().map_err(|e| {
+                            syn::Error::new(
+                                ast.span(),
+                                format!(
+                                    "{}\nBuild with `GHACTIONS_GENERATE=write` to update the Action file",
+                                    e
+                                ),
+                            )
+                        })?;
+                    } else {
+                        action
+                            .write()
+                            .map_err(|e| syn::Error::new(ast.span(), e.to_string()))?;
+                    }
+                }
+                if let Some((path, dockerfile)) = dockerfile {
+                    if check {
+                        if std::fs::read_to_string(&path).ok() != Some(dockerfile.render()) {
+                            return Err(syn::Error::new(
+                                ast.span(),
+                                format!(
+                                    "Dockerfile `{}` is out of date\nBuild with `GHACTIONS_GENERATE=write` to update the Dockerfile",
+                                    path.display()
+                                ),
+                            ));
+                        }
+                    } else {
+                        dockerfile
+                            .write(&path)
+                            .map_err(|e| syn::Error::new(ast.span(), e.to_string()))?;
+                    }
                 }
             }
+            #[cfg(not(feature = "generate"))]
+            let _ = dockerfile;
 
             Ok(tokens)
         }
@@ -136,88 +264,616 @@ pub(crate) fn derive_parser(ast: &DeriveInput) -> Result<TokenStream, syn::Error
     }
 }
 
+/// Get the doc comment (`///`) of a struct or field
+///
+/// Lines are trimmed and joined using a space, paragraphs (separated by an empty
+/// line) are joined using a newline.
+fn doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
+    let mut paragraphs = vec![String::new()];
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("doc")) {
+        let syn::Meta::NameValue(syn::MetaNameValue {
+            value:
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(ref doc),
+                    ..
+                }),
+            ..
+        }) = attr.meta
+        else {
+            continue;
+        };
+        // Empty doc lines (`///`) are empty strings so `lines()` would skip them
+        for line in doc.value().split('\n').map(|line| line.trim()) {
+            let paragraph = paragraphs.last_mut().unwrap();
+            if line.is_empty() {
+                if !paragraph.is_empty() {
+                    paragraphs.push(String::new());
+                }
+            } else {
+                if !paragraph.is_empty() {
+                    paragraph.push(' ');
+                }
+                paragraph.push_str(line);
+            }
+        }
+    }
+    let doc = paragraphs
+        .into_iter()
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect::<Vec<String>>()
+        .join("\n");
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// Check `#[action(skip)]` is only used by itself on fields
+///
+/// Skipped fields (and fields without attributes) are initialised using `Default`.
+fn skipped_field(
+    field: &syn::Field,
+    name: &str,
+    field_attributes: &[ActionsAttribute],
+) -> Result<(), syn::Error> {
+    let skip = field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("action"));
+    let Some(skip) = skip else {
+        return Ok(());
+    };
+    if name != "action"
+        || field_attributes
+            .iter()
+            .any(|attr| attr.key != Some(ActionsAttributeKeys::Skip))
+    {
+        return Err(syn::Error::new(
+            skip.meta.span(),
+            "Fields only support `#[action(skip)]` which can not be used with `#[input]` or `#[output]`",
+        ));
+    }
+    Ok(())
+}
+
+/// Add an input to the Action, names have to be unique (including flattened inputs)
+fn insert_input(
+    action: &mut ActionYML,
+    spans: &mut std::collections::HashMap<String, proc_macro2::Span>,
+    input: ActionInput,
+    span: proc_macro2::Span,
+) -> Result<(), syn::Error> {
+    // Aliases share the names of the inputs
+    for name in std::iter::once(&input.action_name).chain(input.aliases.iter()) {
+        if let Some(previous) = spans.insert(name.clone(), span) {
+            let mut err =
+                syn::Error::new(span, format!("Input `{}` is defined more than once", name));
+            err.combine(syn::Error::new(
+                previous,
+                format!("Input `{}` is first defined here", name),
+            ));
+            return Err(err);
+        }
+    }
+    // Needs to be the Action name as that is the name
+    // that will be used in the action.yml file
+    action.inputs.insert(input.action_name.to_string(), input);
+    Ok(())
+}
+
+/// Parse an input field (and its `#[input]` attributes)
+pub(crate) fn parse_input(
+    field: &syn::Field,
+    field_attributes: &[ActionsAttribute],
+) -> Result<ActionInput, syn::Error> {
+    let field_name = field.ident.as_ref().unwrap();
+    let field_type = &field.ty;
+
+    // Values are trimmed unless `trim = false` is set
+    let mut input = ActionInput {
+        field_name: field_name.to_string(),
+        r#type: field_type.to_token_stream().to_string(),
+        trim: true,
+        ..Default::default()
+    };
+
+    field_attributes.iter().for_each(|attr| match attr {
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::Name),
+            value: Some(ActionsAttributeValue::String(name)),
+            ..
+        } => {
+            input.action_name = name.to_string();
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::Description),
+            value: Some(ActionsAttributeValue::String(description)),
+            ..
+        } => {
+            input.description = Some(description.clone());
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::Required),
+            value: Some(ActionsAttributeValue::Bool(required)),
+            ..
+        } => {
+            input.required = Some(*required);
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::Default),
+            value: Some(ActionsAttributeValue::String(default)),
+            ..
+        } => {
+            input.default = Some(default.clone());
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::Default),
+            value: Some(ActionsAttributeValue::Path(default)),
+            ..
+        } => {
+            // Path-like defaults (e.g. `./src`) are still plain strings
+            input.default = Some(default.display().to_string());
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::Deprecated),
+            value: Some(ActionsAttributeValue::String(message)),
+            ..
+        } => {
+            input.deprecation_message = Some(message.clone());
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::Pattern),
+            value: Some(ActionsAttributeValue::String(pattern)),
+            ..
+        } => {
+            input.pattern = Some(pattern.clone());
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::AllowedValues),
+            value: Some(ActionsAttributeValue::String(allowed)),
+            ..
+        } => {
+            input.allowed_values = Some(
+                allowed
+                    .split(',')
+                    .map(|value| value.trim().to_string())
+                    .collect(),
+            );
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::IgnoreCase),
+            value,
+            ..
+        } => {
+            input.ignore_case = match value {
+                Some(ActionsAttributeValue::Bool(ignore)) => Some(*ignore),
+                _ => Some(true),
+            };
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::Flag),
+            value,
+            ..
+        } => {
+            input.flag = !matches!(value, Some(ActionsAttributeValue::Bool(false)));
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::Lenient),
+            value,
+            ..
+        } => {
+            input.lenient = !matches!(value, Some(ActionsAttributeValue::Bool(false)));
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::Trim),
+            value,
+            ..
+        } => {
+            input.trim = !matches!(value, Some(ActionsAttributeValue::Bool(false)));
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::Multiline),
+            value,
+            ..
+        } => {
+            input.multiline = !matches!(value, Some(ActionsAttributeValue::Bool(false)));
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::AllowExpressions),
+            value,
+            ..
+        } => {
+            input.allow_expressions = !matches!(value, Some(ActionsAttributeValue::Bool(false)));
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::Secret),
+            value,
+            ..
+        } => {
+            input.secret = !matches!(value, Some(ActionsAttributeValue::Bool(false)));
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::Separator),
+            value: Some(ActionsAttributeValue::String(separator)),
+            ..
+        } => {
+            input.separator = Some(separator.clone());
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::Alias),
+            value: Some(ActionsAttributeValue::String(alias)),
+            ..
+        } => {
+            input.aliases.push(alias.clone());
+        }
+        ActionsAttribute {
+            key: Some(ActionsAttributeKeys::DeprecateAliases),
+            value,
+            ..
+        } => {
+            input.deprecate_aliases = !matches!(value, Some(ActionsAttributeValue::Bool(false)));
+        }
+        _ => {}
+    });
+    if input.description.is_none() {
+        input.description = doc_comment(&field.attrs);
+    }
+
+    if input.lenient && input.r#type != "bool" {
+        return Err(syn::Error::new_spanned(
+            field_type,
+            "Lenient attribute is only supported for `bool` inputs",
+        ));
+    }
+
+    // Flags are `false` unless present
+    if input.flag {
+        if input.r#type != "bool" {
+            return Err(syn::Error::new_spanned(
+                field_type,
+                "Flag attribute is only supported for `bool` inputs",
+            ));
+        }
+        input.description = Some(
+            format!(
+                "{} (flag: empty or `true` to enable)",
+                input.description.clone().unwrap_or_default()
+            )
+            .trim_start()
+            .to_string(),
+        );
+        input.default = Some("false".to_string());
+    }
+
+    // Document the allowed values in the description
+    if let Some(ref allowed) = input.allowed_values {
+        input.description = Some(
+            format!(
+                "{} (allowed values: {})",
+                input.description.clone().unwrap_or_default(),
+                allowed.join(", ")
+            )
+            .trim_start()
+            .to_string(),
+        );
+    }
+
+    // If the name is empty, use the field name
+    if input.action_name.is_empty() {
+        input.action_name = field_name.to_string();
+    }
+    validate_name(
+        "input",
+        &input.action_name,
+        name_span(field_attributes, field_name),
+    )?;
+
+    Ok(input)
+}
+
+/// Parse the `default` attribute of an input into a value used at runtime
+///
+/// Defaults containing `${{ ... }}` expressions are only evaluated by GitHub so are
+/// not used at runtime. The default is parsed at compile time so invalid values are
+/// reported as errors.
+fn runtime_default(
+    fields: &syn::FieldsNamed,
+    input: &ActionInput,
+    action_name: &str,
+    span: proc_macro2::Span,
+) -> Result<Option<TokenStream>, syn::Error> {
+    let default = match input.default {
+        Some(ref default) if !input.flag && !default.contains("${{") => default,
+        _ => return Ok(None),
+    };
+    let invalid = || {
+        syn::Error::new(
+            span,
+            format!(
+                "Default value `{}` is not a valid `{}` for input {}",
+                default, input.r#type, action_name
+            ),
+        )
+    };
+
+    let value = match input.r#type.as_str() {
+        "String" | "&str" => {
+            let value = if input.trim { default.trim() } else { default };
+            quote! { #value.to_string() }
+        }
+        "bool" => {
+            let value = match input.lenient {
+                true => ghactions_core::inputs::parse_bool_lenient(default),
+                false => ghactions_core::inputs::parse_bool(default),
+            }
+            .ok_or_else(invalid)?;
+            quote! { #value }
+        }
+        "i32" | "i64" | "u32" | "u64" => {
+            let value = default.trim();
+            let valid = match input.r#type.as_str() {
+                "i32" => value.parse::<i32>().is_ok(),
+                "i64" => value.parse::<i64>().is_ok(),
+                "u32" => value.parse::<u32>().is_ok(),
+                _ => value.parse::<u64>().is_ok(),
+            };
+            if !valid {
+                return Err(invalid());
+            }
+            let value = syn::LitInt::new(value, span);
+            quote! { #value }
+        }
+        "Duration" | "std :: time :: Duration" => {
+            let millis = ghactions_core::deadline::parse_duration(default)
+                .ok_or_else(invalid)?
+                .as_millis() as u64;
+            quote! { ::std::time::Duration::from_millis(#millis) }
+        }
+        "Vec < String >" => {
+            let values: Vec<String> = if input.multiline {
+                default
+                    .lines()
+                    .map(|line| line.trim())
+                    .filter(|line| !line.is_empty())
+                    .map(|line| line.to_string())
+                    .collect()
+            } else {
+                let separator = input.separator.clone().unwrap_or_else(|| ",".to_string());
+                default
+                    .split(separator.as_str())
+                    .map(|value| {
+                        if input.trim {
+                            value.trim().to_string()
+                        } else {
+                            value.to_string()
+                        }
+                    })
+                    .collect()
+            };
+            quote! { vec![#(#values.to_string()),*] }
+        }
+        // Custom types are parsed at runtime
+        _ => {
+            let ty = field_type(fields, &input.field_name)?;
+            // Only the type is spanned, `errors` is defined at the call site
+            let parse = quote_spanned! { ty.span() =>
+                <#ty as ::ghactions::FromInput>::from_input(#default)
+            };
+            quote! {
+                match #parse {
+                    Ok(value) => value,
+                    Err(err) => {
+                        errors.push(::ghactions::ActionsError::InputParseError(
+                            #action_name.to_string(),
+                            err,
+                        ));
+                        Default::default()
+                    }
+                }
+            }
+        }
+    };
+    Ok(Some(value))
+}
+
+/// Find a named field
+fn find_field<'a>(fields: &'a syn::FieldsNamed, name: &str) -> Result<&'a syn::Field, syn::Error> {
+    fields
+        .named
+        .iter()
+        .find(|field| field.ident.as_ref().is_some_and(|ident| ident == name))
+        .ok_or_else(|| syn::Error::new(fields.span(), format!("Unknown field `{}`", name)))
+}
+
+/// Find the type of a named field
+fn field_type<'a>(fields: &'a syn::FieldsNamed, name: &str) -> Result<&'a syn::Type, syn::Error> {
+    find_field(fields, name).map(|field| &field.ty)
+}
+
 pub(crate) fn generate_traits(
     ident: &syn::Ident,
-    _fields: &syn::FieldsNamed,
+    fields: &syn::FieldsNamed,
     generics: &syn::Generics,
+    attributes: &[ActionsAttribute],
     action: &ActionYML,
 ) -> Result<TokenStream, syn::Error> {
     let mut stream = TokenStream::new();
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let mut selfstream = TokenStream::new();
+    let (inputstream, selfstream) = generate_inputs(ident, fields, action, &quote! { Self })?;
+    let action_name = action.name.clone().unwrap_or_default();
+    let action_description = action.description.clone().unwrap_or_default();
 
-    for (action_name, input) in action.inputs.iter() {
-        let input_name = format!("INPUT_{}", input.action_name.to_uppercase());
-        let ident_input = syn::Ident::new(&input.field_name.clone(), ident.span());
+    // Explicit dotenv attributes are used even without the `dotenvy` feature (`load_dotenv`
+    // then fails for a file)
+    let dotenv = match attributes
+        .iter()
+        .find(|attr| attr.key == Some(ActionsAttributeKeys::Dotenv))
+        .and_then(|attr| attr.value.as_ref())
+    {
+        Some(ActionsAttributeValue::Bool(false)) => quote! {},
+        Some(ActionsAttributeValue::String(path)) => quote! {
+            ::ghactions::dotenv::load_dotenv(::ghactions::dotenv::DotenvSource::File(#path))?;
+        },
+        Some(ActionsAttributeValue::Path(path)) => {
+            let path = path.display().to_string();
+            quote! {
+                ::ghactions::dotenv::load_dotenv(::ghactions::dotenv::DotenvSource::File(#path))?;
+            }
+        }
+        Some(ActionsAttributeValue::Bool(true)) => quote! {
+            ::ghactions::dotenv::load_dotenv(::ghactions::dotenv::DotenvSource::Default)?;
+        },
+        _ if cfg!(feature = "dotenvy") => quote! {
+            ::ghactions::dotenv::load_dotenv(::ghactions::dotenv::DotenvSource::Default)?;
+        },
+        _ => quote! {},
+    };
+    let log = match cfg!(feature = "log") {
+        true => quote! {
+            ::ghactions::init_logger().try_init().ok();
+        },
+        false => quote! {},
+    };
 
-        let required = if input.required.unwrap_or(false) {
-            quote! { ? }
-        } else {
-            quote! { .unwrap_or_default() }
-        };
+    // Load the local inputs file (ignored in GitHub Actions)
+    let dev_inputs = attributes
+        .iter()
+        .find(|attr| attr.key == Some(ActionsAttributeKeys::DevInputs))
+        .and_then(|attr| match &attr.value {
+            Some(ActionsAttributeValue::String(value)) => Some(value.clone()),
+            Some(ActionsAttributeValue::Path(value)) => Some(value.display().to_string()),
+            _ => None,
+        });
+    let dev_inputs = match dev_inputs {
+        Some(path) => quote! { ::ghactions::dev_inputs::load_dev_inputs(Some(#path))?; },
+        None => quote! { ::ghactions::dev_inputs::load_dev_inputs(None)?; },
+    };
 
-        match input.r#type.as_str() {
-            "String" | "&str" => {
-                selfstream.extend(quote! {
-                    #ident_input: Self::get_input(#input_name)
-                        #required,
-                });
-            }
-            "bool" => {
-                selfstream.extend(quote! {
-                    #ident_input: Self::get_input_bool(#input_name)
-                        #required,
-                });
+    // Verify the Action file matches the code before parsing any inputs (in debug mode or
+    // CI only). The path is relative to the Action directory, not the workspace.
+    let verify = match attributes
+        .iter()
+        .find(|attr| attr.key == Some(ActionsAttributeKeys::VerifyOnInit))
+    {
+        Some(attr) => match action.path {
+            Some(ref path) => {
+                let path = path.display().to_string();
+                quote! {
+                    if ::ghactions::is_debug() || ::ghactions::is_ci() {
+                        let path = <Self as ::ghactions::ActionTrait>::action_path()?.join(#path);
+                        Self::verify_action_file(path)
+                            .map_err(::ghactions::ActionsError::ActionFileMismatch)?;
+                    }
+                }
             }
-            "i32" | "i64" | "u32" | "u64" => {
-                selfstream.extend(quote! {
-                    #ident_input: Self::get_input_int(#input_name)
-                        #required,
-                });
+            None => {
+                return Err(syn::Error::new(
+                    attr.span.span(),
+                    "`verify_on_init` requires the `path` attribute to be set",
+                ))
             }
-            // TODO: This hack is needed but should be fixed in the future
-            "Vec < String >" => {
-                let separator = input.separator.clone().unwrap_or_else(|| ",".to_string());
+        },
+        None => quote! {},
+    };
 
-                selfstream.extend(quote! {
-                    #ident_input: Self::get_input_vec(#input_name, #separator)
-                        #required,
-                });
+    // Check the workflow event is supported by the Action
+    let events = supported_events(attributes);
+    let event_guard = if events.is_empty() {
+        quote! {}
+    } else {
+        let skip = attributes.iter().any(|attr| {
+            attr.key == Some(ActionsAttributeKeys::EventsMode)
+                && matches!(&attr.value, Some(ActionsAttributeValue::String(mode)) if mode == "skip")
+        });
+        if skip {
+            let notice = match cfg!(feature = "log") {
+                true => quote! { ::ghactions::prelude::info!("::notice::{}", message); },
+                false => quote! { println!("::notice::{}", message); },
+            };
+            // Return before parsing the inputs (required inputs may not be set)
+            quote! {
+                if Self::should_skip() {
+                    let skipped = ::ghactions::ActionsError::SkippedEvent {
+                        got: ::ghactions::env::var("GITHUB_EVENT_NAME").unwrap_or_default(),
+                        supported: Self::supported_events()
+                            .iter()
+                            .map(|event| event.to_string())
+                            .collect(),
+                    };
+                    let message = skipped.to_string();
+                    #notice
+                    return Err(skipped);
+                }
             }
-            _ => {
+        } else {
+            quote! {
+                if Self::should_skip() {
+                    return Err(::ghactions::ActionsError::UnsupportedEvent {
+                        got: ::ghactions::env::var("GITHUB_EVENT_NAME").unwrap_or_default(),
+                        supported: Self::supported_events()
+                            .iter()
+                            .map(|event| event.to_string())
+                            .collect(),
+                    });
+                }
+            }
+        }
+    };
+
+    // Start the deadline before parsing the other inputs
+    let deadline = match attributes
+        .iter()
+        .find(|attr| attr.key == Some(ActionsAttributeKeys::DeadlineInput))
+    {
+        Some(ActionsAttribute {
+            value: Some(ActionsAttributeValue::String(name)),
+            value_span,
+            span,
+            ..
+        }) => {
+            if !action.inputs.contains_key(name) {
                 return Err(syn::Error::new(
-                    ident.span(),
-                    format!(
-                        "Unsupported type for input {} ({})",
-                        action_name, input.r#type
-                    ),
+                    value_span.unwrap_or(span.span()),
+                    format!("Deadline input `{}` is not an input of the Action", name),
                 ));
             }
+            quote! {
+                if let Some(value) = Self::get_input_raw(#name) {
+                    if let Err(err) = ::ghactions::deadline::start_from_input(#name, &value) {
+                        errors.push(err);
+                    }
+                }
+            }
         }
-    }
-    for (name, _output) in action.outputs.iter() {
-        let ident_output = syn::Ident::new(name, ident.span());
-        selfstream.extend(quote! {
-            #ident_output: String::new(),
-        });
-    }
-
-    let action_name = action.name.clone().unwrap_or_default();
-    let action_description = action.description.clone().unwrap_or_default();
-
-    let dotenv = match cfg!(feature = "dotenvy") {
-        true => quote! {
-            ::dotenvy::dotenv().ok();
-        },
-        false => quote! {},
+        _ => quote! {},
     };
-    let log = match cfg!(feature = "log") {
-        true => quote! {
-            ::ghactions::init_logger().init();
-        },
-        false => quote! {},
+
+    // Fail early on runners older than the Action supports
+    let runner_guard = match attributes
+        .iter()
+        .find(|attr| attr.key == Some(ActionsAttributeKeys::MinRunnerVersion))
+    {
+        Some(ActionsAttribute {
+            value: Some(ActionsAttributeValue::String(min_version)),
+            ..
+        }) => {
+            let notice = match cfg!(feature = "log") {
+                true => quote! { ::ghactions::prelude::info!("::notice::{}", message); },
+                false => quote! { println!("::notice::{}", message); },
+            };
+            quote! {
+                match ::ghactions::runner_version() {
+                    Some(version) => ::ghactions::check_runner_version(&version, #min_version)?,
+                    None => {
+                        let message = format!(
+                            "Unable to determine the runner version (requires `{}` or newer)",
+                            #min_version
+                        );
+                        #notice
+                    }
+                }
+            }
+        }
+        _ => quote! {},
     };
 
     stream.extend(quote! {
@@ -226,6 +882,21 @@ pub(crate) fn generate_traits(
             fn init() -> Result<Self, ::ghactions::ActionsError> {
                 #dotenv
                 #log
+                #dev_inputs
+                #verify
+                #runner_guard
+                #event_guard
+
+                let mut errors: Vec<::ghactions::ActionsError> = Vec::new();
+
+                #deadline
+                #inputstream
+
+                match errors.len() {
+                    0 => {}
+                    1 => return Err(errors.remove(0)),
+                    _ => return Err(::ghactions::ActionsError::MultipleErrors(errors)),
+                }
 
                 Ok(Self {
                     #selfstream
@@ -245,6 +916,399 @@ pub(crate) fn generate_traits(
     Ok(stream)
 }
 
+/// Generate the code parsing the inputs (`inputstream`) and the struct fields (`selfstream`)
+///
+/// The inputs are read using `reader` which implements `ActionTrait` (`Self` for Actions and
+/// the Action type parameter for input groups). Errors are collected in `errors`.
+pub(crate) fn generate_inputs(
+    ident: &syn::Ident,
+    fields: &syn::FieldsNamed,
+    action: &ActionYML,
+    reader: &TokenStream,
+) -> Result<(TokenStream, TokenStream), syn::Error> {
+    let mut inputstream = TokenStream::new();
+    let mut selfstream = TokenStream::new();
+
+    // Flattened inputs are parsed by the input group
+    let inputs = action
+        .inputs
+        .iter()
+        .filter(|(_, input)| input.group.is_none());
+    for (action_name, input) in inputs {
+        let name = &input.action_name;
+        // Errors point at the field (or its type) instead of the struct
+        let field = find_field(fields, &input.field_name)?;
+        let ident_input = field.ident.clone().unwrap();
+
+        // Inputs with aliases are read from the first name which is set
+        let input_name = if input.aliases.is_empty() {
+            quote! { #name }
+        } else {
+            let aliases = input.aliases.iter().flat_map(|alias| {
+                let mut names = vec![quote! { (#alias, #alias) }];
+                if alias.contains('-') {
+                    let alias_name = alias.replace('-', "_");
+                    names.push(quote! { (#alias, #alias_name) });
+                }
+                names
+            });
+            let warning = quote! {
+                "Input `{}` is deprecated, use `{}` instead",
+                alias,
+                #action_name
+            };
+            let warn = if cfg!(feature = "log") {
+                quote! { ::ghactions::prelude::warn!(#warning); }
+            } else {
+                quote! { println!("::warning::{}", format!(#warning)); }
+            };
+            inputstream.extend(quote! {
+                let (input_name, alias) = ::ghactions::input_source(#name, &[#(#aliases),*]);
+                if let Some(alias) = alias {
+                    #warn
+                }
+            });
+            quote! { input_name }
+        };
+
+        let required = input.required.unwrap_or(false);
+
+        let getter = match input.r#type.as_str() {
+            "String" | "&str" if input.trim => quote! { #reader::get_input(#input_name) },
+            "String" | "&str" => quote! { #reader::get_input_untrimmed(#input_name) },
+            "bool" if input.flag => quote! { #reader::get_input_flag(#input_name) },
+            "bool" if input.lenient => quote! { #reader::get_input_bool_lenient(#input_name) },
+            "bool" => quote! { #reader::get_input_bool(#input_name) },
+            "i32" => quote! { #reader::get_input_int(#input_name) },
+            "Duration" | "std :: time :: Duration" => {
+                quote! { #reader::get_input_duration(#input_name) }
+            }
+            // TODO: This hack is needed but should be fixed in the future
+            "Vec < String >" if input.multiline => {
+                if input.separator.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        &ident_input,
+                        format!(
+                            "Multiline and separator attributes can not be used together ({})",
+                            action_name
+                        ),
+                    ));
+                }
+                quote! { #reader::get_multiline_input(#input_name) }
+            }
+            "Vec < String >" => {
+                let separator = input.separator.clone().unwrap_or_else(|| ",".to_string());
+                if input.trim {
+                    quote! { #reader::get_input_vec(#input_name, #separator) }
+                } else {
+                    quote! {
+                        #reader::get_input_untrimmed(#input_name).map(|value| {
+                            value.split(#separator).map(|value| value.to_string()).collect()
+                        })
+                    }
+                }
+            }
+            // All other types need to implement `ghactions::FromInput`
+            _ => {
+                let ty = &field.ty;
+                quote_spanned! { ty.span() =>
+                    #reader::get_input_from::<#ty>(#input_name)
+                }
+            }
+        };
+
+        if input.multiline && input.r#type != "Vec < String >" {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                format!(
+                    "Multiline attribute is only supported for `Vec<String>` inputs ({})",
+                    action_name
+                ),
+            ));
+        }
+
+        // Missing inputs use the `default` attribute (if set), optional inputs fall back
+        // to the type default and missing required inputs are reported using the name and
+        // description workflow authors know the input by
+        let description = match input.description {
+            Some(ref description) => quote! { Some(#description.to_string()) },
+            None => quote! { None },
+        };
+        let missing = match runtime_default(fields, input, action_name, field.ty.span())? {
+            Some(default) => quote! {
+                Err(::ghactions::ActionsError::InputError(_)) => #default,
+            },
+            None => quote! {
+                Err(::ghactions::ActionsError::InputError(_)) if !#required => Default::default(),
+                Err(::ghactions::ActionsError::InputError(_)) => {
+                    errors.push(::ghactions::ActionsError::MissingInput {
+                        input: #action_name.to_string(),
+                        description: #description,
+                    });
+                    Default::default()
+                }
+            },
+        };
+        // Mask secrets before the value is parsed (errors can include the value)
+        if input.secret {
+            inputstream.extend(quote! {
+                if let Some(value) = #reader::get_input_raw(#input_name) {
+                    // Surrounding whitespace is part of the raw value but not the secret
+                    #reader::add_mask(value.trim());
+                }
+            });
+        }
+
+        inputstream.extend(quote! {
+            let #ident_input = match #getter {
+                Ok(value) => value,
+                #missing
+                Err(err) => {
+                    errors.push(::ghactions::with_input_name(err, #action_name));
+                    Default::default()
+                }
+            };
+        });
+
+        // Warn when a deprecated input is used
+        if let Some(ref message) = input.deprecation_message {
+            if cfg!(feature = "log") {
+                inputstream.extend(quote! {
+                    if #reader::get_input_raw(#input_name).is_some_and(|value| !value.is_empty()) {
+                        ::ghactions::prelude::warn!(
+                            "Input `{}` is deprecated: {}",
+                            #action_name,
+                            #message
+                        );
+                    }
+                });
+            }
+        }
+
+        // Warn when the workflow passed an expression that was not expanded
+        // (the value is not logged as it could be a secret)
+        if !input.allow_expressions {
+            let warning = quote! {
+                "Input `{}` contains an unexpanded `${{{{ ... }}}}` expression, check the workflow does not quote the expression and that any referenced secret exists",
+                #action_name
+            };
+            let warn = if cfg!(feature = "log") {
+                quote! { ::ghactions::prelude::warn!(#warning); }
+            } else {
+                quote! { println!("::warning::{}", format!(#warning)); }
+            };
+            inputstream.extend(quote! {
+                if #reader::get_input_raw(#input_name)
+                    .is_some_and(|value| ::ghactions::has_unexpanded_expression(&value))
+                {
+                    #warn
+                }
+            });
+        }
+
+        // Values to validate (only string inputs support validation)
+        let values = match input.r#type.as_str() {
+            "String" | "&str" => Some(quote! { ::std::iter::once(&#ident_input) }),
+            "Vec < String >" => Some(quote! { #ident_input.iter() }),
+            _ => None,
+        };
+        if values.is_none() && (input.pattern.is_some() || input.allowed_values.is_some()) {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                format!(
+                    "Pattern and allowed values attributes are only supported for string inputs ({})",
+                    action_name
+                ),
+            ));
+        }
+
+        // Validate the input value against the regex pattern
+        if let (Some(ref pattern), Some(ref values)) = (&input.pattern, &values) {
+            inputstream.extend(quote! {
+                {
+                    static PATTERN: ::std::sync::OnceLock<::ghactions::Regex> =
+                        ::std::sync::OnceLock::new();
+                    let regex = PATTERN.get_or_init(|| {
+                        ::ghactions::Regex::new(#pattern).expect("Pattern is validated at compile time")
+                    });
+                    for value in #values {
+                        if let Err(err) = ::ghactions::check_input_pattern(#action_name, value, regex) {
+                            errors.push(err);
+                        }
+                    }
+                }
+            });
+        }
+
+        // Validate the input value is one of the allowed values
+        if let (Some(ref allowed), Some(ref values)) = (&input.allowed_values, &values) {
+            let ignore_case = input.ignore_case.unwrap_or(false);
+            inputstream.extend(quote! {
+                for value in #values {
+                    if let Err(err) = ::ghactions::check_input_allowed(
+                        #action_name,
+                        value,
+                        &[#(#allowed),*],
+                        #ignore_case,
+                    ) {
+                        errors.push(err);
+                    }
+                }
+            });
+        }
+
+        selfstream.extend(quote! {
+            #ident_input,
+        });
+    }
+    let groups = flatten::flatten_fields(fields)?;
+    for field in groups.iter() {
+        let field_name = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let group = quote_spanned! { ty.span() => <#ty as ::ghactions::InputGroup> };
+        inputstream.extend(quote! {
+            let #field_name = #group::from_inputs::<#reader>(&mut errors);
+        });
+        selfstream.extend(quote! {
+            #field_name,
+        });
+    }
+    for output in action.outputs.values() {
+        let ident_output = syn::Ident::new(&output.field_name, ident.span());
+        selfstream.extend(quote! {
+            #ident_output: Default::default(),
+        });
+    }
+    // Other fields (e.g. `PhantomData`) need to implement `Default`
+    for field in fields.named.iter() {
+        let field_name = field.ident.as_ref().unwrap();
+        let is_action_field = action
+            .inputs
+            .values()
+            .any(|input| input.group.is_none() && field_name == &input.field_name)
+            || groups
+                .iter()
+                .any(|group| group.ident.as_ref() == Some(field_name))
+            || action
+                .outputs
+                .values()
+                .any(|output| field_name == &output.field_name);
+        if !is_action_field {
+            let ty = &field.ty;
+            selfstream.extend(quote_spanned! { ty.span() =>
+                #field_name: <#ty as ::core::default::Default>::default(),
+            });
+        }
+    }
+
+    Ok((inputstream, selfstream))
+}
+
+/// Get the Dockerfile to generate (and its path) if `generate_dockerfile` is set
+fn dockerfile_options(
+    attributes: &[ActionsAttribute],
+    action: &ActionYML,
+) -> Result<Option<(std::path::PathBuf, Dockerfile)>, syn::Error> {
+    let attr = match attributes.iter().find(|attr| {
+        attr.key == Some(ActionsAttributeKeys::GenerateDockerfile)
+            && !matches!(attr.value, Some(ActionsAttributeValue::Bool(false)))
+    }) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+    let path = match action.runs.image {
+        Some(ref image) => image.clone(),
+        None => {
+            return Err(syn::Error::new(
+                attr.span.span(),
+                "Generate Dockerfile attribute requires the `image` attribute (e.g. `image = \"./Dockerfile\"`)",
+            ))
+        }
+    };
+
+    let mut dockerfile = Dockerfile::default();
+    for attr in attributes.iter() {
+        match (&attr.key, &attr.value) {
+            (
+                Some(ActionsAttributeKeys::RustVersion),
+                Some(ActionsAttributeValue::String(version)),
+            ) => dockerfile.rust_version = version.clone(),
+            (Some(ActionsAttributeKeys::BaseImage), Some(ActionsAttributeValue::String(image))) => {
+                dockerfile.base_image = image.clone()
+            }
+            _ => {}
+        }
+    }
+    Ok(Some((path, dockerfile)))
+}
+
+/// Check the Action file instead of writing it
+///
+/// `GHACTIONS_GENERATE` (`write` or `check`) overrides the `generate` attribute and
+/// `GHACTIONS_CHECK=true` is kept for compatibility.
+#[cfg(feature = "generate")]
+fn check_mode(attributes: &[ActionsAttribute]) -> bool {
+    match std::env::var("GHACTIONS_GENERATE").as_deref() {
+        Ok("check") => return true,
+        Ok("write") => return false,
+        _ => {}
+    }
+    if std::env::var("GHACTIONS_CHECK").is_ok_and(|value| value == "true" || value == "1") {
+        return true;
+    }
+    attributes.iter().any(|attr| {
+        attr.key == Some(ActionsAttributeKeys::Generate)
+            && matches!(&attr.value, Some(ActionsAttributeValue::String(value)) if value == "check")
+    })
+}
+
+/// Get the span of the `name` attribute if set, otherwise the field name
+fn name_span(attributes: &[ActionsAttribute], field_name: &syn::Ident) -> proc_macro2::Span {
+    attributes
+        .iter()
+        .find(|attr| attr.key == Some(ActionsAttributeKeys::Name))
+        .and_then(|attr| attr.value_span)
+        .unwrap_or_else(|| field_name.span())
+}
+
+/// Validate an input / output name follows the GitHub Actions naming rules
+fn validate_name(kind: &str, name: &str, span: proc_macro2::Span) -> Result<(), syn::Error> {
+    if is_valid_name(name) {
+        Ok(())
+    } else {
+        Err(syn::Error::new(
+            span,
+            format!(
+                "Invalid {} name `{}`, names must start with a letter or `_` and only contain alphanumeric characters, `-` or `_` (suggestion: `{}`)",
+                kind,
+                name,
+                sanitize_name(name)
+            ),
+        ))
+    }
+}
+
+/// Get the list of supported events from the `events` attribute
+pub(crate) fn supported_events(attributes: &[ActionsAttribute]) -> Vec<String> {
+    attributes
+        .iter()
+        .find_map(|attr| match attr {
+            ActionsAttribute {
+                key: Some(ActionsAttributeKeys::Events),
+                value: Some(ActionsAttributeValue::String(events)),
+                ..
+            } => Some(
+                events
+                    .split(',')
+                    .map(|event| event.trim().to_string())
+                    .collect(),
+            ),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+#[allow(clippy::ptr_arg)]
 fn load_actionyaml(attributes: &Vec<ActionsAttribute>) -> Result<ActionYML, syn::Error> {
     let mut action = ActionYML::default();
 
@@ -265,6 +1329,10 @@ fn load_actionyaml(attributes: &Vec<ActionsAttribute>) -> Result<ActionYML, syn:
                     action.description = Some(value.clone());
                 }
             }
+            Some(ActionsAttributeKeys::FollowSymlinks) => {
+                action.follow_symlinks =
+                    !matches!(attr.value, Some(ActionsAttributeValue::Bool(false)));
+            }
             Some(ActionsAttributeKeys::Image) => {
                 if let Some(ActionsAttributeValue::Path(ref value)) = attr.value {
                     action.set_container_image(value.to_path_buf());
@@ -296,13 +1364,11 @@ fn load_actionyaml(attributes: &Vec<ActionsAttribute>) -> Result<ActionYML, syn:
                     let entrypoint = value.display().to_string().replace("./", "");
                     let run: String = format!("${{{{ github.action_path }}}}/{}", entrypoint);
 
-                    action.runs.steps =
-                        Some(vec![ghactions_core::actions::models::ActionRunStep {
-                            id: Some("entrypoint-script".to_string()),
-                            shell: Some(shell),
-                            run: Some(run),
-                            ..Default::default()
-                        }]);
+                    let mut step = ghactions_core::actions::models::ActionRunStep::default();
+                    step.id = Some("entrypoint-script".to_string());
+                    step.shell = Some(shell);
+                    step.run = Some(run);
+                    action.runs.steps = Some(vec![step]);
 
                     action.output_value_step_id = Some("entrypoint-script".to_string());
                 }
@@ -310,5 +1376,270 @@ fn load_actionyaml(attributes: &Vec<ActionsAttribute>) -> Result<ActionYML, syn:
             _ => {}
         }
     }
+
+    // Branding (GitHub requires both the icon and the colour)
+    let branding = |key: ActionsAttributeKeys| {
+        attributes
+            .iter()
+            .find(|attr| attr.key == Some(key.clone()))
+            .and_then(|attr| match &attr.value {
+                Some(ActionsAttributeValue::String(value)) => Some((attr, value.clone())),
+                _ => None,
+            })
+    };
+    match (
+        branding(ActionsAttributeKeys::BrandingIcon),
+        branding(ActionsAttributeKeys::BrandingColor),
+    ) {
+        (Some((_, icon)), Some((_, color))) => {
+            action.branding = Some(ActionBranding { color, icon });
+        }
+        (Some((attr, _)), None) | (None, Some((attr, _))) => {
+            return Err(syn::Error::new(
+                attr.span.span(),
+                "Branding requires both the `icon` and `color` attributes",
+            ));
+        }
+        (None, None) => {}
+    }
+
+    // User authored composite Action (the `runs` section is not generated)
+    if let Some(attr) = attributes.iter().find(|attr| {
+        attr.key == Some(ActionsAttributeKeys::Composite)
+            && !matches!(attr.value, Some(ActionsAttributeValue::Bool(false)))
+    }) {
+        if attributes.iter().any(|attr| {
+            matches!(
+                attr.key,
+                Some(ActionsAttributeKeys::Image) | Some(ActionsAttributeKeys::Entrypoint)
+            )
+        }) {
+            return Err(syn::Error::new(
+                attr.span.span(),
+                "Composite attribute can not be used with the `image` or `entrypoint` attributes",
+            ));
+        }
+        action.set_composite();
+    }
+
+    // Install the prebuilt binary instead of compiling the Action
+    if let Some(attr) = attributes.iter().find(|attr| {
+        attr.key == Some(ActionsAttributeKeys::Installer)
+            && !matches!(attr.value, Some(ActionsAttributeValue::Bool(false)))
+    }) {
+        if action.composite
+            || attributes.iter().any(|attr| {
+                matches!(
+                    attr.key,
+                    Some(ActionsAttributeKeys::Image) | Some(ActionsAttributeKeys::Entrypoint)
+                )
+            })
+        {
+            return Err(syn::Error::new(
+                attr.span.span(),
+                "Installer attribute can not be used with the `image`, `entrypoint` or `composite` attributes",
+            ));
+        }
+        let script = match attr.value {
+            Some(ActionsAttributeValue::Path(ref path)) => Some(path.clone()),
+            _ => None,
+        };
+        action.add_installer_step(script);
+    }
+
+    // JavaScript Action (e.g. a shim downloading the Action binary)
+    let node = |key: ActionsAttributeKeys| {
+        attributes
+            .iter()
+            .find(|attr| attr.key == Some(key.clone()))
+            .and_then(|attr| match &attr.value {
+                Some(ActionsAttributeValue::String(value)) => Some((attr, value.clone())),
+                Some(ActionsAttributeValue::Int(value)) => Some((attr, value.to_string())),
+                Some(ActionsAttributeValue::Path(value)) => {
+                    Some((attr, value.display().to_string()))
+                }
+                _ => None,
+            })
+    };
+    match (
+        node(ActionsAttributeKeys::Node),
+        node(ActionsAttributeKeys::Main),
+    ) {
+        (Some((attr, version)), Some((_, main))) => {
+            if action.composite
+                || attributes.iter().any(|attr| {
+                    matches!(
+                        attr.key,
+                        Some(ActionsAttributeKeys::Image)
+                            | Some(ActionsAttributeKeys::Entrypoint)
+                            | Some(ActionsAttributeKeys::Installer)
+                    )
+                })
+            {
+                return Err(syn::Error::new(
+                    attr.span.span(),
+                    "Node attribute can not be used with the `image`, `entrypoint`, `installer` or `composite` attributes",
+                ));
+            }
+            action.set_node(version, main);
+        }
+        (Some((attr, _)), None) | (None, Some((attr, _))) => {
+            return Err(syn::Error::new(
+                attr.span.span(),
+                "JavaScript Actions require both the `node` and `main` attributes",
+            ));
+        }
+        (None, None) => {}
+    }
+
+    // Container arguments and environment variables
+    for attr in attributes.iter() {
+        let value = match (&attr.key, &attr.value) {
+            (
+                Some(ActionsAttributeKeys::DockerArgs)
+                | Some(ActionsAttributeKeys::DockerEnv)
+                | Some(ActionsAttributeKeys::DockerPassInputs),
+                Some(ActionsAttributeValue::String(value)),
+            ) => value,
+            _ => continue,
+        };
+        if action.runs.using != ActionRunUsing::Docker {
+            return Err(syn::Error::new(
+                attr.span.span(),
+                format!(
+                    "`{}` is only supported for container Actions (`image` attribute)",
+                    attr.span
+                ),
+            ));
+        }
+        match attr.key {
+            Some(ActionsAttributeKeys::DockerArgs) => {
+                action
+                    .runs
+                    .args
+                    .get_or_insert_with(Default::default)
+                    .push(value.clone());
+            }
+            Some(ActionsAttributeKeys::DockerEnv) => {
+                let (name, env) = value.split_once('=').unwrap_or_default();
+                action
+                    .runs
+                    .env
+                    .get_or_insert_with(Default::default)
+                    .insert(name.trim().to_string(), env.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    // Customise the generated composite steps (after the Action type is known)
+    for attr in attributes.iter() {
+        let (key, value) = match (&attr.key, &attr.value) {
+            (Some(key), Some(ActionsAttributeValue::String(value))) => (key, value),
+            _ => continue,
+        };
+        let unsupported = || {
+            syn::Error::new(
+                attr.span.span(),
+                format!(
+                    "`{}` is only supported for the generated composite steps",
+                    attr.span
+                ),
+            )
+        };
+        match key {
+            ActionsAttributeKeys::RunStepEnv => {
+                let (name, env) = value.split_once('=').unwrap_or_default();
+                action
+                    .run_step_mut()
+                    .ok_or_else(unsupported)?
+                    .env
+                    .get_or_insert_with(Default::default)
+                    .insert(name.trim().to_string(), env.to_string());
+            }
+            ActionsAttributeKeys::RunStepName => {
+                action.run_step_mut().ok_or_else(unsupported)?.name = Some(value.clone());
+            }
+            ActionsAttributeKeys::CompileStepName => {
+                action.compile_step_mut().ok_or_else(unsupported)?.name = Some(value.clone());
+            }
+            ActionsAttributeKeys::OutputPrefix => {
+                // The run step selects the prefix used by the Action binary
+                action
+                    .run_step_mut()
+                    .ok_or_else(unsupported)?
+                    .env
+                    .get_or_insert_with(Default::default)
+                    .insert(OUTPUT_PREFIX_ENV.to_string(), value.clone());
+                action.output_prefix = Some(value.clone());
+            }
+            _ => {}
+        }
+    }
+
+    // Scripts run before / after the Action (`pre-entrypoint` / `post-entrypoint` for
+    // container Actions, otherwise the first / last composite step)
+    let script = |key: ActionsAttributeKeys| {
+        attributes
+            .iter()
+            .find(|attr| attr.key == Some(key.clone()))
+            .and_then(|attr| match &attr.value {
+                Some(ActionsAttributeValue::String(value)) => Some((attr, value.clone())),
+                Some(ActionsAttributeValue::Path(value)) => {
+                    Some((attr, value.display().to_string()))
+                }
+                _ => None,
+            })
+    };
+    for (entrypoint, condition) in [
+        (
+            ActionsAttributeKeys::PreEntrypoint,
+            ActionsAttributeKeys::PreIf,
+        ),
+        (
+            ActionsAttributeKeys::PostEntrypoint,
+            ActionsAttributeKeys::PostIf,
+        ),
+    ] {
+        let pre = entrypoint == ActionsAttributeKeys::PreEntrypoint;
+        match (script(entrypoint), script(condition)) {
+            (Some((attr, _)), _) if action.composite => {
+                return Err(syn::Error::new(
+                    attr.span.span(),
+                    "Pre / post entrypoint attributes can not be used with the `composite` attribute",
+                ));
+            }
+            (Some((_, script)), condition) => {
+                let condition = condition.map(|(_, condition)| condition);
+                match pre {
+                    true => action.set_pre_entrypoint(script, condition),
+                    false => action.set_post_entrypoint(script, condition),
+                }
+            }
+            (None, Some((attr, _))) => {
+                return Err(syn::Error::new(
+                    attr.span.span(),
+                    format!("`{}` requires the matching entrypoint attribute", attr.span),
+                ));
+            }
+            (None, None) => {}
+        }
+    }
+
+    // Outputs are read from a custom step (set last so the run step can still be customised)
+    if let Some(attr) = attributes
+        .iter()
+        .find(|attr| attr.key == Some(ActionsAttributeKeys::OutputStep))
+    {
+        if let Some(ActionsAttributeValue::String(ref step)) = attr.value {
+            if action.runs.steps.is_none() {
+                return Err(syn::Error::new(
+                    attr.span.span(),
+                    "Output step attribute is only supported for composite Actions",
+                ));
+            }
+            action.output_value_step_id = Some(step.clone());
+        }
+    }
     Ok(action)
 }
@@ -72,7 +72,33 @@ use syn::{parse_macro_input, DeriveInput};
 pub fn actions(input: TokenStream) -> TokenStream {
     let ast: DeriveInput = parse_macro_input!(input as DeriveInput);
 
-    match derives::derive_parser(&ast) {
+    match derives::derive_parser(&ast, &[]) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derive macro for a group of inputs shared between Actions
+///
+/// The group is embedded in an Action using `#[input(flatten)]`, the Action and the
+/// group have to be in the same crate.
+#[proc_macro_derive(ActionInputs, attributes(input))]
+pub fn action_inputs(input: TokenStream) -> TokenStream {
+    let ast: DeriveInput = parse_macro_input!(input as DeriveInput);
+
+    match derives::flatten::derive_input_group(&ast) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derive an Action once the flattened input groups are resolved (used by `Actions`)
+#[doc(hidden)]
+#[proc_macro]
+pub fn __derive_flattened(input: TokenStream) -> TokenStream {
+    let flattened = parse_macro_input!(input as derives::flatten::Flattened);
+
+    match derives::derive_parser(&flattened.action, &flattened.groups) {
         Ok(tokens) => tokens.into(),
         Err(err) => err.to_compile_error().into(),
     }
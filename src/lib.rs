@@ -3,26 +3,82 @@
 #![allow(unused_imports)]
 #![deny(missing_docs)]
 
-pub use ghactions_core::logging::init_logger;
+#[cfg(feature = "artifacts")]
+pub use ghactions_core::artifacts::{ArtifactClient, UploadedArtifact};
+pub use ghactions_core::commands::{
+    log_untrusted, stop_commands, Annotation, AnnotationLevel, StopCommandsGuard,
+};
+pub use ghactions_core::context::RefType;
+pub use ghactions_core::deadline;
+pub use ghactions_core::dev_inputs;
+pub use ghactions_core::dotenv;
+pub use ghactions_core::env;
+pub use ghactions_core::env_files::{parse_env_file, to_json_output, write_outputs, OutputWriter};
+pub use ghactions_core::exit;
+pub use ghactions_core::exit::{fail, fail_with_error, run, run_blocking};
+#[cfg(feature = "octocrab")]
+pub use ghactions_core::graphql;
+pub use ghactions_core::inputs::{input_env_name, input_key};
+#[cfg(feature = "log")]
+pub use ghactions_core::logging::{init_logger, log_group, GroupGuard};
+#[cfg(feature = "reports")]
+pub use ghactions_core::reports::Annotations;
+#[cfg(feature = "octocrab")]
+pub use ghactions_core::retry;
+pub use ghactions_core::runner::{
+    check_runner_version, is_ci, is_debug, runner_supports_output_files, runner_version,
+};
+pub use ghactions_core::safety::{mark_untrusted, SafetyPolicy};
+#[cfg(feature = "testing")]
+pub use ghactions_core::testing;
 pub use ghactions_core::ActionTrait;
 pub use ghactions_core::ActionsError;
+pub use ghactions_core::FromInput;
+pub use ghactions_core::InputGroup;
+#[cfg(feature = "log")]
+pub use ghactions_core::{
+    errorf, group, group_scope, groupend, mask, notice, setenv, setoutput, warningf,
+};
+pub use ghactions_core::{
+    ActionYML, AlertKind, GitHubContext, GitHubUrls, SchemaMismatch, Severity, Summary,
+    ValidationIssue,
+};
+pub use ghactions_derive::{ActionInputs, Actions};
+
+// Used by the code generated by the derive macros and by tests (not part of the public API)
+#[doc(hidden)]
 #[cfg(feature = "log")]
-pub use ghactions_core::{errorf, group, groupend, setoutput};
-pub use ghactions_derive::Actions;
+pub use ghactions_core::__annotation;
+#[doc(hidden)]
+pub use ghactions_core::env_files::reset_paths_for_testing;
+#[doc(hidden)]
+pub use ghactions_core::inputs::{
+    check_input_allowed, check_input_pattern, has_unexpanded_expression, input_source,
+    with_input_name,
+};
+#[doc(hidden)]
+pub use ghactions_derive::__derive_flattened;
+#[doc(hidden)]
+pub use regex::Regex;
 
 /// Prelude module to re-export the most commonly used types
 pub mod prelude {
     // Derive Macros
-    pub use ghactions_derive::Actions;
+    pub use ghactions_derive::{ActionInputs, Actions};
 
     // Traits
-    pub use ghactions_core::ActionTrait;
+    pub use ghactions_core::{ActionTrait, FromInput};
 
     // Structs / Functions
     pub use ghactions_core::errors::ActionsError;
+    pub use ghactions_core::summary::{AlertKind, Summary};
 
     #[cfg(feature = "log")]
-    pub use ghactions_core::{errorf, group, groupend, setoutput};
+    pub use ghactions_core::logging::log_group;
+    #[cfg(feature = "log")]
+    pub use ghactions_core::{
+        errorf, group, group_scope, groupend, mask, notice, setenv, setoutput, warningf,
+    };
     #[cfg(feature = "log")]
     pub use log::{debug, error, info, trace, warn};
 }
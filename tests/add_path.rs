@@ -0,0 +1,47 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Add Path Action")]
+struct AddPathAction {}
+
+#[test]
+fn test_add_path() {
+    let root = std::env::temp_dir().join(format!("ghactions-add-path-{}", std::process::id()));
+    let file = root.with_extension("env");
+    let original = std::env::var_os("PATH").unwrap_or_default();
+
+    std::env::set_var("GITHUB_PATH", &file);
+    ghactions::reset_paths_for_testing();
+    AddPathAction::add_path(root.join("bin")).unwrap();
+    // Relative paths are made absolute
+    AddPathAction::add_path("tools").unwrap();
+
+    let content = std::fs::read_to_string(&file).unwrap();
+    std::fs::remove_file(&file).unwrap();
+    let cwd = std::env::current_dir().unwrap();
+    assert_eq!(
+        content,
+        format!(
+            "{}\n{}\n",
+            root.join("bin").display(),
+            cwd.join("tools").display()
+        )
+    );
+
+    // Added directories are first in the process PATH
+    let paths: Vec<_> = std::env::split_paths(&std::env::var_os("PATH").unwrap()).collect();
+    assert_eq!(paths[0], cwd.join("tools"));
+    assert_eq!(paths[1], root.join("bin"));
+
+    // Without `GITHUB_PATH` only the process PATH is updated
+    std::env::remove_var("GITHUB_PATH");
+    ghactions::reset_paths_for_testing();
+    AddPathAction::add_path(root.join("local")).unwrap();
+    assert!(!file.exists());
+    let paths: Vec<_> = std::env::split_paths(&std::env::var_os("PATH").unwrap()).collect();
+    assert_eq!(paths[0], root.join("local"));
+
+    std::env::set_var("PATH", original);
+}
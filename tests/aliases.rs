@@ -0,0 +1,65 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Alias Action", path = "./target/ghactions-tests/aliases.yml")]
+struct AliasAction {
+    #[input(
+        name = "alias-token",
+        description = "GitHub Token",
+        alias = "alias-gh-token"
+    )]
+    token: String,
+
+    #[input(
+        name = "alias-mode",
+        description = "Mode",
+        alias = "alias-old-mode",
+        alias = "alias-legacy-mode",
+        deprecate_aliases
+    )]
+    mode: String,
+}
+
+#[test]
+fn test_alias_precedence() {
+    std::env::set_var("INPUT_ALIAS-TOKEN", "new");
+    std::env::set_var("INPUT_ALIAS-GH-TOKEN", "old");
+    std::env::set_var("INPUT_ALIAS-LEGACY-MODE", "legacy");
+
+    let action = AliasAction::init().unwrap();
+    assert_eq!(action.token, "new");
+    assert_eq!(action.mode, "legacy");
+
+    // The alias is used when the input is missing (or empty)
+    std::env::set_var("INPUT_ALIAS-TOKEN", "");
+    std::env::set_var("INPUT_ALIAS_OLD_MODE", "old");
+    let action = AliasAction::init().unwrap();
+    assert_eq!(action.token, "old");
+    // Aliases are checked in order
+    assert_eq!(action.mode, "old");
+}
+
+#[test]
+fn test_alias_yaml() {
+    let content = std::fs::read_to_string("./target/ghactions-tests/aliases.yml").unwrap();
+    let inputs = &content[content.find("inputs:").unwrap()..content.find("outputs:").unwrap()];
+
+    assert_eq!(
+        inputs,
+        r#"inputs:
+  alias-token:
+    description: GitHub Token
+  alias-mode:
+    description: Mode
+  alias-old-mode:
+    description: Mode
+    deprecationMessage: Use `alias-mode` instead
+  alias-legacy-mode:
+    description: Mode
+    deprecationMessage: Use `alias-mode` instead
+"#
+    );
+    assert!(AliasAction::verify_action_file("./target/ghactions-tests/aliases.yml").is_ok());
+}
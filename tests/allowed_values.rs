@@ -0,0 +1,65 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Allowed Values Action",
+    path = "./target/ghactions-tests/allowed-values.yml"
+)]
+struct AllowedValuesAction {
+    #[input(description = "Log level", allowed_values = "debug,info,warn,error")]
+    allowed_level: String,
+
+    #[input(allowed_values = "fast, slow", ignore_case = true)]
+    allowed_mode: String,
+}
+
+#[test]
+fn test_allowed_values() {
+    std::env::set_var("INPUT_ALLOWED_LEVEL", "warn");
+    std::env::set_var("INPUT_ALLOWED_MODE", "FAST");
+    let action = AllowedValuesAction::init().unwrap();
+    assert_eq!(action.allowed_level, "warn");
+    assert_eq!(action.allowed_mode, "FAST");
+
+    // Case sensitive by default
+    std::env::set_var("INPUT_ALLOWED_LEVEL", "WARN");
+    let err = AllowedValuesAction::init().unwrap_err();
+    assert_eq!(
+        err,
+        ActionsError::InputAllowedError(
+            "allowed_level".to_string(),
+            "WARN".to_string(),
+            vec![
+                "debug".to_string(),
+                "info".to_string(),
+                "warn".to_string(),
+                "error".to_string()
+            ]
+        )
+    );
+
+    std::env::set_var("INPUT_ALLOWED_LEVEL", "info");
+    std::env::set_var("INPUT_ALLOWED_MODE", "medium");
+    assert!(matches!(
+        AllowedValuesAction::init(),
+        Err(ActionsError::InputAllowedError(name, value, _)) if name == "allowed_mode" && value == "medium"
+    ));
+}
+
+#[test]
+fn test_allowed_values_description() {
+    let action = ghactions::ActionYML::load_action(
+        "./target/ghactions-tests/allowed-values.yml".to_string(),
+    )
+    .unwrap();
+    assert_eq!(
+        action.inputs["allowed_level"].description,
+        Some("Log level (allowed values: debug, info, warn, error)".to_string())
+    );
+    assert_eq!(
+        action.inputs["allowed_mode"].description,
+        Some("(allowed values: fast, slow)".to_string())
+    );
+}
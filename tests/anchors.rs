@@ -0,0 +1,66 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+use ghactions::ActionYML;
+
+#[derive(Actions, Debug)]
+#[action(name = "Anchors Action")]
+struct AnchorsAction {
+    #[input(description = "GitHub Token", required = true)]
+    token: String,
+
+    #[input(description = "Mode", default = "fast")]
+    mode: String,
+
+    #[input(name = "legacy-mode", description = "Mode", default = "fast")]
+    legacy_mode: String,
+
+    #[output(description = "Output Version")]
+    version: String,
+}
+
+fn load(path: &str) -> ActionYML {
+    let mut action = ActionYML::load_action(path.to_string()).unwrap();
+    action.path = None;
+    action
+}
+
+#[test]
+fn test_load_anchors() {
+    let action = load("tests/fixtures/anchors.yml");
+    assert_eq!(
+        action.inputs["legacy-mode"].default,
+        Some("fast".to_string())
+    );
+
+    let steps = action.runs.steps.as_ref().unwrap();
+    assert_eq!(steps.len(), 3);
+    let env = steps[1].env.as_ref().unwrap();
+    assert_eq!(env["RUST_LOG"], "info");
+    assert_eq!(env["TOOL_MODE"], "fast");
+
+    // Merge keys are expanded and unknown step fields are preserved
+    assert_eq!(steps[2].name, Some("Checkout again".to_string()));
+    assert_eq!(steps[2].extra["uses"], "actions/checkout@v4");
+    assert_eq!(steps[2].extra["if"], "always()");
+    assert_eq!(steps[2].extra["with"]["fetch-depth"], 1);
+}
+
+#[test]
+fn test_anchors_round_trip() {
+    let action = load("tests/fixtures/anchors.yml");
+    let reloaded = ActionYML::from_yaml(&action.to_yaml().unwrap()).unwrap();
+    assert_eq!(action, reloaded);
+}
+
+#[test]
+fn test_anchors_equivalent() {
+    assert_eq!(
+        load("tests/fixtures/anchors.yml"),
+        load("tests/fixtures/anchors-expanded.yml")
+    );
+    assert_eq!(
+        AnchorsAction::verify_action_file("tests/fixtures/anchors.yml"),
+        Ok(())
+    );
+}
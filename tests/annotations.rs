@@ -0,0 +1,88 @@
+#![allow(dead_code)]
+
+use std::sync::Mutex;
+
+use ghactions::prelude::*;
+use ghactions::{warningf, Annotation, AnnotationLevel};
+
+/// Logger capturing the level and message of the records
+struct CaptureLogger;
+
+static RECORDS: Mutex<Vec<(log::Level, String)>> = Mutex::new(Vec::new());
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        RECORDS
+            .lock()
+            .unwrap()
+            .push((record.level(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+#[test]
+fn test_annotation_macros() {
+    log::set_logger(&CaptureLogger).unwrap();
+    log::set_max_level(log::LevelFilter::Info);
+    std::env::set_var("GHACTIONS_LOCAL_MODE", "false");
+
+    errorf!(file: "src/main.rs", line: 1, column: 2, "Error checking file");
+    errorf!(
+        file: "src/config.rs",
+        line: 10,
+        end_line: 12,
+        column: 4,
+        end_column: 8,
+        title: "Invalid config: name, version",
+        "Missing `{}` key\n100% required",
+        "name"
+    );
+    errorf!("Plain {}", "error");
+    // User controlled values can not start new workflow commands
+    errorf!("Branch `{}`", "main\n::set-env name=PATH::/tmp");
+    warningf!(title: "a\r\n::add-mask::x", "100%");
+    warningf!(file: "src/lib.rs", line: 3, column: 5, title: "Unused", "Unused import");
+    notice!("Cache restored");
+    Annotation::new(AnnotationLevel::Warning, "Built")
+        .file("Cargo.toml")
+        .emit();
+
+    let records = RECORDS.lock().unwrap();
+    assert_eq!(
+        *records,
+        vec![
+            (
+                log::Level::Error,
+                "::error file=src/main.rs,line=1,col=2::Error checking file".to_string()
+            ),
+            (
+                log::Level::Error,
+                "::error file=src/config.rs,line=10,endLine=12,col=4,endColumn=8,title=Invalid config%3A name%2C version::Missing `name` key%0A100%25 required".to_string()
+            ),
+            (log::Level::Error, "::error::Plain error".to_string()),
+            (
+                log::Level::Error,
+                "::error::Branch `main%0A::set-env name=PATH::/tmp`".to_string()
+            ),
+            (
+                log::Level::Warn,
+                "::warning title=a%0D%0A%3A%3Aadd-mask%3A%3Ax::100%25".to_string()
+            ),
+            (
+                log::Level::Warn,
+                "::warning file=src/lib.rs,line=3,col=5,title=Unused::Unused import".to_string()
+            ),
+            (log::Level::Info, "::notice::Cache restored".to_string()),
+            (
+                log::Level::Warn,
+                "::warning file=Cargo.toml::Built".to_string()
+            ),
+        ]
+    );
+    std::env::remove_var("GHACTIONS_LOCAL_MODE");
+}
@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Booleans Action")]
+struct BooleansAction {
+    #[input(description = "Dry run")]
+    bool_dry_run: bool,
+
+    #[input(description = "Verbose output", lenient, default = "no")]
+    bool_verbose: bool,
+}
+
+/// Both inputs are read from the environment, so the cases run in one test
+#[test]
+fn test_boolean_inputs() {
+    std::env::remove_var("INPUT_BOOL_VERBOSE");
+    for (value, expected) in [("true", true), (" True ", true), ("FALSE", false)] {
+        std::env::set_var("INPUT_BOOL_DRY_RUN", value);
+        let action = BooleansAction::init().unwrap();
+        assert_eq!(action.bool_dry_run, expected, "`{}`", value);
+        assert!(!action.bool_verbose);
+    }
+
+    // Lenient spellings are only accepted by lenient inputs
+    std::env::set_var("INPUT_BOOL_DRY_RUN", "yes");
+    assert_eq!(
+        BooleansAction::init().unwrap_err(),
+        ActionsError::InputTypeError(
+            "bool_dry_run".to_string(),
+            "bool: true, True, TRUE, false, False or FALSE".to_string()
+        )
+    );
+
+    std::env::set_var("INPUT_BOOL_DRY_RUN", "false");
+    for (value, expected) in [("1", true), ("On", true), ("off", false), ("TRUE", true)] {
+        std::env::set_var("INPUT_BOOL_VERBOSE", value);
+        assert_eq!(
+            BooleansAction::init().unwrap().bool_verbose,
+            expected,
+            "`{}`",
+            value
+        );
+    }
+
+    std::env::set_var("INPUT_BOOL_VERBOSE", "maybe");
+    assert_eq!(
+        BooleansAction::init().unwrap_err(),
+        ActionsError::InputTypeError(
+            "bool_verbose".to_string(),
+            "bool: true, True, TRUE, false, False, FALSE, 1, 0, yes, no, on or off".to_string()
+        )
+    );
+    std::env::remove_var("INPUT_BOOL_DRY_RUN");
+    std::env::remove_var("INPUT_BOOL_VERBOSE");
+}
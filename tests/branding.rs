@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Branding Action",
+    description = "Branding Action Description",
+    path = "./target/ghactions-tests/branding.yml",
+    icon = "git-branch",
+    colour = "purple"
+)]
+struct BrandingAction {}
+
+#[test]
+fn test_branding_yaml() {
+    let action =
+        ghactions::ActionYML::load_action("./target/ghactions-tests/branding.yml".to_string())
+            .unwrap();
+    let branding = action.branding.as_ref().unwrap();
+    assert_eq!(branding.icon, "git-branch");
+    assert_eq!(branding.color, "purple");
+    assert!(action.validate().is_empty());
+
+    let content = std::fs::read_to_string("./target/ghactions-tests/branding.yml").unwrap();
+    assert!(content.contains("branding:\n  color: purple\n  icon: git-branch\n"));
+}
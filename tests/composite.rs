@@ -0,0 +1,90 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+use ghactions::ActionYML;
+use ghactions_core::ActionInput;
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Composite Action",
+    description = "User authored composite Action",
+    path = "./target/ghactions-tests/composite.yml",
+    composite = true
+)]
+struct CompositeAction {
+    #[input(description = "Mode", default = "fast")]
+    mode: String,
+
+    #[output(description = "Tool Version", step = "tool")]
+    version: String,
+}
+
+const CUSTOM: &str = r#"name: Old Name
+description: Old Description
+author: GeekMasher
+inputs:
+  removed:
+    description: Removed input
+runs:
+  using: composite
+  steps:
+  - id: tool
+    uses: actions/setup-node@v4
+    with:
+      node-version: 20
+  - shell: bash
+    run: echo "custom"
+"#;
+
+#[test]
+fn test_composite_yaml() {
+    // Written at compile time (no `runs` section existed so no steps are generated)
+    let action =
+        ActionYML::load_action("./target/ghactions-tests/composite.yml".to_string()).unwrap();
+    assert!(action.inputs.contains_key("mode"));
+    assert_eq!(
+        action.outputs["version"].value.as_deref(),
+        Some("${{ steps.tool.outputs.version }}")
+    );
+    assert!(CompositeAction::verify_action_file("./target/ghactions-tests/composite.yml").is_ok());
+}
+
+#[test]
+fn test_composite_keeps_steps() {
+    let path = std::path::PathBuf::from("./target/ghactions-tests/composite-custom.yml");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, CUSTOM).unwrap();
+
+    let mut action = ActionYML {
+        path: Some(path.clone()),
+        name: Some("New Name".to_string()),
+        ..Default::default()
+    };
+    action.set_composite();
+    action.inputs.insert(
+        "mode".to_string(),
+        ActionInput {
+            description: Some("Mode".to_string()),
+            ..Default::default()
+        },
+    );
+    action.write().unwrap();
+    // Regenerating again does not change the file
+    let first = std::fs::read_to_string(&path).unwrap();
+    action.write().unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), first);
+
+    let written = ActionYML::load_action(path.display().to_string()).unwrap();
+    assert_eq!(written.name.as_deref(), Some("New Name"));
+    // Sections which are not generated are removed / kept
+    assert_eq!(written.description, None);
+    assert_eq!(written.author.as_deref(), Some("GeekMasher"));
+    assert_eq!(written.inputs.keys().collect::<Vec<_>>(), vec!["mode"]);
+
+    let steps = written.runs.steps.unwrap();
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0].id.as_deref(), Some("tool"));
+    assert_eq!(steps[0].extra["uses"], "actions/setup-node@v4");
+    assert_eq!(steps[0].extra["with"]["node-version"], 20);
+    assert_eq!(steps[1].run.as_deref(), Some("echo \"custom\""));
+}
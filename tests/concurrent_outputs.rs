@@ -0,0 +1,74 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Concurrent Outputs Action")]
+struct ConcurrentOutputsAction {}
+
+const THREADS: usize = 8;
+const OUTPUTS: usize = 1000;
+
+/// Parse an output file (`key=value` and `key<<delimiter` records)
+fn parse_outputs(content: &str) -> HashMap<String, String> {
+    let mut outputs = HashMap::new();
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        if let Some((key, delimiter)) = line.split_once("<<") {
+            let mut value = Vec::new();
+            loop {
+                let line = lines.next().expect("unterminated multi-line output");
+                if line == delimiter {
+                    break;
+                }
+                value.push(line);
+            }
+            assert!(outputs.insert(key.to_string(), value.join("\n")).is_none());
+        } else {
+            let (key, value) = line
+                .split_once('=')
+                .unwrap_or_else(|| panic!("invalid output line: `{}`", line));
+            assert!(outputs.insert(key.to_string(), value.to_string()).is_none());
+        }
+    }
+    outputs
+}
+
+#[test]
+fn test_concurrent_outputs() {
+    let path = std::env::temp_dir().join(format!(
+        "ghactions-concurrent-outputs-{}.env",
+        std::process::id()
+    ));
+    std::env::set_var("GITHUB_OUTPUT", &path);
+    ghactions::reset_paths_for_testing();
+
+    let threads: Vec<_> = (0..THREADS)
+        .map(|thread| {
+            std::thread::spawn(move || {
+                for index in 0..OUTPUTS {
+                    let key = format!("output_{}_{}", thread, index);
+                    // Every other output uses the multi-line (heredoc) format
+                    let value = match index % 2 {
+                        0 => format!("{}-{}", thread, index),
+                        _ => format!("{}\n{}", thread, index),
+                    };
+                    ConcurrentOutputsAction::set_output(key.as_str(), value.as_str()).unwrap();
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let outputs = parse_outputs(&content);
+    assert_eq!(outputs.len(), THREADS * OUTPUTS);
+    assert_eq!(outputs["output_3_10"], "3-10");
+    assert_eq!(outputs["output_7_999"], "7\n999");
+}
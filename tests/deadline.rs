@@ -0,0 +1,63 @@
+#![allow(dead_code)]
+
+use std::process::Command;
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Deadline Action", deadline_input = "timeout")]
+struct DeadlineAction {
+    #[input(description = "Total time the Action can run for", default = "10m")]
+    timeout: String,
+}
+
+/// Run by [test_deadline_exceeded] in a child process so the exit code can be checked
+#[test]
+fn deadline_child() {
+    if std::env::var("DEADLINE_CHILD").is_err() {
+        return;
+    }
+    let _action = DeadlineAction::init().unwrap();
+
+    ghactions::deadline::add_cleanup_hook(|| println!("DEADLINE_CLEANUP"));
+    group!("Upload Results");
+    std::thread::sleep(std::time::Duration::from_secs(30));
+}
+
+#[test]
+fn test_deadline_exceeded() {
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args([
+            "deadline_child",
+            "--exact",
+            "--nocapture",
+            "--test-threads=1",
+        ])
+        .env("DEADLINE_CHILD", "1")
+        .env("INPUT_TIMEOUT", "500ms")
+        .output()
+        .unwrap();
+    assert_eq!(
+        output.status.code(),
+        Some(ghactions::deadline::DEADLINE_EXIT_CODE)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // Cleanup hooks run before the error is reported
+    let cleanup = stdout.find("DEADLINE_CLEANUP").unwrap();
+    let error = stdout
+        .find("::error::Action exceeded its 500ms timeout during phase `Upload Results`")
+        .unwrap();
+    assert!(cleanup < error);
+}
+
+#[test]
+fn test_deadline_invalid() {
+    std::env::set_var("INPUT_TIMEOUT", "soon");
+    let err = DeadlineAction::init().unwrap_err();
+    std::env::remove_var("INPUT_TIMEOUT");
+    assert_eq!(
+        err,
+        ActionsError::InputTypeError("timeout".to_string(), "duration".to_string())
+    );
+}
@@ -0,0 +1,62 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Defaults Action")]
+struct DefaultsAction {
+    #[input(description = "Branch", default = "main")]
+    defaults_branch: String,
+
+    #[input(description = "Enabled", default = "true")]
+    defaults_enabled: bool,
+
+    #[input(description = "Retries", default = "3")]
+    defaults_retries: i32,
+
+    #[input(description = "Labels", default = "bug, docs", split = ",", trim)]
+    defaults_labels: Vec<String>,
+
+    #[input(description = "Directory", default = "./src")]
+    defaults_directory: String,
+
+    #[input(description = "Token", default = "${{ github.token }}")]
+    defaults_token: String,
+}
+
+#[test]
+fn test_runtime_defaults() {
+    for name in [
+        "INPUT_DEFAULTS_BRANCH",
+        "INPUT_DEFAULTS_ENABLED",
+        "INPUT_DEFAULTS_RETRIES",
+        "INPUT_DEFAULTS_LABELS",
+        "INPUT_DEFAULTS_DIRECTORY",
+        "INPUT_DEFAULTS_TOKEN",
+    ] {
+        std::env::remove_var(name);
+    }
+
+    let action = DefaultsAction::init().unwrap();
+    assert_eq!(action.defaults_branch, "main");
+    assert!(action.defaults_enabled);
+    assert_eq!(action.defaults_retries, 3);
+    assert_eq!(action.defaults_labels, vec!["bug", "docs"]);
+    assert_eq!(action.defaults_directory, "./src");
+    // Expressions are only evaluated by GitHub
+    assert_eq!(action.defaults_token, "");
+
+    // Provided inputs take precedence over the defaults
+    std::env::set_var("INPUT_DEFAULTS_BRANCH", "develop");
+    std::env::set_var("INPUT_DEFAULTS_ENABLED", "false");
+    std::env::set_var("INPUT_DEFAULTS_RETRIES", "5");
+
+    let action = DefaultsAction::init().unwrap();
+    assert_eq!(action.defaults_branch, "develop");
+    assert!(!action.defaults_enabled);
+    assert_eq!(action.defaults_retries, 5);
+
+    std::env::remove_var("INPUT_DEFAULTS_BRANCH");
+    std::env::remove_var("INPUT_DEFAULTS_ENABLED");
+    std::env::remove_var("INPUT_DEFAULTS_RETRIES");
+}
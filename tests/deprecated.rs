@@ -0,0 +1,38 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Deprecated Action",
+    path = "./target/ghactions-tests/deprecated.yml"
+)]
+struct DeprecatedAction {
+    #[input(description = "GitHub Token")]
+    token: String,
+
+    #[input(description = "GitHub Token (old)", deprecated = "Use 'token' instead")]
+    gh_token: String,
+}
+
+#[test]
+fn test_deprecated_yaml() {
+    let content = std::fs::read_to_string("./target/ghactions-tests/deprecated.yml").unwrap();
+    assert!(content.contains("deprecationMessage: Use 'token' instead"));
+
+    let action =
+        ghactions::ActionYML::load_action("./target/ghactions-tests/deprecated.yml".to_string())
+            .unwrap();
+    assert_eq!(action.inputs["token"].deprecation_message, None);
+    assert_eq!(
+        action.inputs["gh_token"].deprecation_message,
+        Some("Use 'token' instead".to_string())
+    );
+}
+
+#[test]
+fn test_deprecated_init() {
+    std::env::set_var("INPUT_GH_TOKEN", "secret");
+    let action = DeprecatedAction::init().unwrap();
+    assert_eq!(action.gh_token, "secret");
+}
@@ -0,0 +1,117 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+use ghactions_core::testing::TestEnv;
+
+/// Inputs file of [DevInputsAttributeAction] (written by the test)
+const DEV_INPUTS_PATH: &str = "./target/ghactions-tests/dev-inputs.local.yml";
+
+#[derive(Actions, Debug)]
+#[action(name = "Dev Inputs Action")]
+struct DevInputsAction {
+    #[input(description = "Repository", required = true)]
+    dev_repository: String,
+
+    #[input(description = "Retries", default = "1")]
+    dev_retries: i32,
+
+    #[input(description = "Dry run")]
+    dev_dry_run: bool,
+}
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Dev Inputs Attribute Action",
+    dev_inputs = "./target/ghactions-tests/dev-inputs.local.yml"
+)]
+struct DevInputsAttributeAction {
+    #[input(description = "Repository", required = true)]
+    dev_repository: String,
+}
+
+fn inputs_file(env: &TestEnv, content: &str) -> String {
+    let path = env.dir().join("inputs.local.yml");
+    std::fs::write(&path, content).unwrap();
+    path.display().to_string()
+}
+
+#[test]
+fn test_dev_inputs_precedence() {
+    let env = TestEnv::new().remove("GITHUB_ACTIONS");
+    let path = inputs_file(
+        &env,
+        "dev_repository: local/repo\ndev_retries: 5\ndev_dry_run: true\n",
+    );
+    let _env = env
+        .github_env("GHACTIONS_INPUTS_FILE", &path)
+        .input("dev_retries", "7");
+
+    let action = DevInputsAction::init().unwrap();
+    assert_eq!(action.dev_repository, "local/repo");
+    // The environment variables take precedence over the file
+    assert_eq!(action.dev_retries, 7);
+    assert!(action.dev_dry_run);
+}
+
+#[test]
+fn test_dev_inputs_type_errors() {
+    let env = TestEnv::new().remove("GITHUB_ACTIONS");
+    let path = inputs_file(&env, "dev_repository: local/repo\ndev_retries: many\n");
+    let _env = env.github_env("GHACTIONS_INPUTS_FILE", &path);
+
+    // Values are parsed like the environment variables
+    assert_eq!(
+        DevInputsAction::init().unwrap_err(),
+        ActionsError::InputTypeError("dev_retries".to_string(), "int".to_string())
+    );
+}
+
+#[test]
+fn test_dev_inputs_in_github_actions() {
+    let env = TestEnv::new();
+    let path = inputs_file(&env, "dev_repository: local/repo\n");
+    let _env = env
+        .github_env("GHACTIONS_INPUTS_FILE", &path)
+        .github_env("GITHUB_ACTIONS", "true");
+
+    // The inputs file is never used in GitHub Actions
+    assert!(matches!(
+        DevInputsAction::init().unwrap_err(),
+        ActionsError::MissingInput { .. }
+    ));
+}
+
+#[test]
+fn test_dev_inputs_malformed() {
+    let env = TestEnv::new().remove("GITHUB_ACTIONS");
+    let path = inputs_file(&env, "dev_repository: [local/repo\n");
+    let _env = env.github_env("GHACTIONS_INPUTS_FILE", &path);
+
+    match DevInputsAction::init().unwrap_err() {
+        ActionsError::FileError {
+            path: error_path,
+            kind,
+            ..
+        } => {
+            assert_eq!(error_path.display().to_string(), path);
+            assert_eq!(kind, std::io::ErrorKind::InvalidData);
+        }
+        err => panic!("unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn test_dev_inputs_attribute() {
+    let _env = TestEnv::new()
+        .remove("GITHUB_ACTIONS")
+        .remove("GHACTIONS_INPUTS_FILE");
+    std::fs::create_dir_all("./target/ghactions-tests").unwrap();
+    std::fs::write(DEV_INPUTS_PATH, "dev_repository: attribute/repo\n").unwrap();
+
+    let action = DevInputsAttributeAction::init();
+    std::fs::remove_file(DEV_INPUTS_PATH).unwrap();
+    assert_eq!(action.unwrap().dev_repository, "attribute/repo");
+
+    // Missing files are ignored
+    assert!(DevInputsAttributeAction::init().is_err());
+}
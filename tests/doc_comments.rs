@@ -0,0 +1,48 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+use ghactions::ActionYML;
+
+/// Doc Action
+///
+/// Reads the descriptions
+/// from the doc comments.
+#[derive(Actions, Debug)]
+#[action(
+    name = "Doc Action",
+    path = "./target/ghactions-tests/doc-comments.yml"
+)]
+struct DocAction {
+    /// GitHub Token
+    /// used for the API
+    #[input(required = true)]
+    token: String,
+
+    /// Ignored as the attribute wins
+    #[input(description = "Mode", default = "fast")]
+    mode: String,
+
+    ///   Output Version   
+    #[output]
+    version: String,
+}
+
+#[test]
+fn test_doc_comments() {
+    // Written at compile time
+    let action =
+        ActionYML::load_action("./target/ghactions-tests/doc-comments.yml".to_string()).unwrap();
+    assert_eq!(
+        action.description.as_deref(),
+        Some("Doc Action\nReads the descriptions from the doc comments.")
+    );
+    assert_eq!(
+        action.inputs["token"].description.as_deref(),
+        Some("GitHub Token used for the API")
+    );
+    assert_eq!(action.inputs["mode"].description.as_deref(), Some("Mode"));
+    assert_eq!(
+        action.outputs["version"].description.as_deref(),
+        Some("Output Version")
+    );
+}
@@ -0,0 +1,40 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Docker Action",
+    path = "./target/ghactions-tests/docker.yml",
+    image = "./examples/advanced/Dockerfile",
+    docker_args = "--verbose",
+    docker_pass_inputs = "args",
+    docker_env = "GITHUB_TOKEN=${{ github.token }}"
+)]
+struct DockerAction {
+    #[input(description = "Mode", default = "fast")]
+    mode: String,
+
+    #[input(name = "dry-run", description = "Dry Run")]
+    dry_run: bool,
+}
+
+#[test]
+fn test_docker_yaml() {
+    let content = std::fs::read_to_string("./target/ghactions-tests/docker.yml").unwrap();
+    let runs = &content[content.find("runs:").unwrap()..];
+
+    assert_eq!(
+        runs,
+        r#"runs:
+  using: docker
+  image: ./examples/advanced/Dockerfile
+  args:
+  - --verbose
+  - ${{ inputs.mode }}
+  - ${{ inputs.dry-run }}
+  env:
+    GITHUB_TOKEN: ${{ github.token }}
+"#
+    );
+}
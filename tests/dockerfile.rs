@@ -0,0 +1,26 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Dockerfile Action",
+    path = "./target/ghactions-tests/dockerfile.yml",
+    image = "./target/ghactions-tests/Dockerfile",
+    generate_dockerfile = true,
+    rust_version = "1.78",
+    base_image = "debian:bookworm-slim"
+)]
+struct DockerfileAction {}
+
+#[test]
+fn test_generated_dockerfile() {
+    let binary = std::env::var("CARGO_BIN_NAME").unwrap_or_else(|_| "action".to_string());
+    let content = std::fs::read_to_string("./target/ghactions-tests/Dockerfile").unwrap();
+
+    assert!(content.starts_with("# This file is generated by ghactions\n"));
+    assert!(content.contains("FROM rust:1.78 AS builder\n"));
+    assert!(content.contains(&format!("RUN cargo build --release --bin {}\n", binary)));
+    assert!(content.contains("FROM debian:bookworm-slim\n"));
+    assert!(content.contains(&format!("ENTRYPOINT [\"/usr/local/bin/{}\"]\n", binary)));
+}
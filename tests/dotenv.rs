@@ -0,0 +1,99 @@
+#![allow(dead_code)]
+
+use ghactions::dotenv::{load_dotenv, DotenvSource, DOTENV_ENV};
+use ghactions::prelude::*;
+use ghactions_core::testing::TestEnv;
+
+/// Dotenv file of [DotenvFileAction] (written by the test)
+const DOTENV_PATH: &str = "./target/ghactions-tests/.env.dotenv-test";
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Dotenv File Action",
+    dotenv = "./target/ghactions-tests/.env.dotenv-test"
+)]
+struct DotenvFileAction {
+    #[input(description = "Environment", required = true)]
+    dotenv_environment: String,
+}
+
+#[derive(Actions, Debug)]
+#[action(name = "Dotenv Action", dotenv = true)]
+struct DotenvAction {
+    #[input(description = "Environment", required = true)]
+    dotenv_environment: String,
+}
+
+#[derive(Actions, Debug)]
+#[action(name = "Dotenv Disabled Action", dotenv = false)]
+struct DotenvDisabledAction {
+    #[input(description = "Environment", required = true)]
+    dotenv_environment: String,
+}
+
+fn dotenv_file(env: &TestEnv, name: &str, content: &str) -> String {
+    let path = env.dir().join(name);
+    std::fs::write(&path, content).unwrap();
+    path.display().to_string()
+}
+
+#[test]
+fn test_dotenv_attribute() {
+    let env = TestEnv::new().remove("INPUT_DOTENV_ENVIRONMENT");
+    // The attribute takes precedence over `GHACTIONS_DOTENV`
+    let other = dotenv_file(&env, ".env.other", "INPUT_DOTENV_ENVIRONMENT=other\n");
+    let _env = env.github_env(DOTENV_ENV, other);
+
+    std::fs::create_dir_all("./target/ghactions-tests").unwrap();
+    std::fs::write(DOTENV_PATH, "INPUT_DOTENV_ENVIRONMENT=attribute\n").unwrap();
+    let action = DotenvFileAction::init();
+    std::fs::remove_file(DOTENV_PATH).unwrap();
+    assert_eq!(action.unwrap().dotenv_environment, "attribute");
+
+    std::env::remove_var("INPUT_DOTENV_ENVIRONMENT");
+    // Failing to read an explicit file is an error
+    assert!(matches!(
+        DotenvFileAction::init().unwrap_err(),
+        ActionsError::FailedLoading(message) if message.contains(".env.dotenv-test")
+    ));
+}
+
+#[test]
+fn test_dotenv_env_var() {
+    let env = TestEnv::new().remove("INPUT_DOTENV_ENVIRONMENT");
+    let path = dotenv_file(&env, ".env.staging", "INPUT_DOTENV_ENVIRONMENT=staging\n");
+    let env = env.github_env(DOTENV_ENV, &path);
+
+    let action = DotenvAction::init().unwrap();
+    assert_eq!(action.dotenv_environment, "staging");
+
+    // Variables already set are not overridden
+    std::env::set_var("INPUT_DOTENV_ENVIRONMENT", "production");
+    assert_eq!(
+        load_dotenv(DotenvSource::Default).unwrap(),
+        Some(path.into())
+    );
+    assert_eq!(
+        std::env::var("INPUT_DOTENV_ENVIRONMENT").unwrap(),
+        "production"
+    );
+
+    let _env = env.github_env(DOTENV_ENV, "./missing/.env.staging");
+    assert!(matches!(
+        load_dotenv(DotenvSource::Default).unwrap_err(),
+        ActionsError::FailedLoading(_)
+    ));
+}
+
+#[test]
+fn test_dotenv_disabled() {
+    let env = TestEnv::new().remove("INPUT_DOTENV_ENVIRONMENT");
+    let path = dotenv_file(&env, ".env.disabled", "INPUT_DOTENV_ENVIRONMENT=disabled\n");
+    let _env = env.github_env(DOTENV_ENV, path);
+
+    assert_eq!(load_dotenv(DotenvSource::Disabled).unwrap(), None);
+    assert!(matches!(
+        DotenvDisabledAction::init().unwrap_err(),
+        ActionsError::MissingInput { .. }
+    ));
+}
@@ -0,0 +1,56 @@
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Duration Action")]
+struct DurationAction {
+    #[input(description = "Timeout")]
+    duration_timeout: Duration,
+
+    #[input(description = "Interval", default = "1h30m")]
+    duration_interval: std::time::Duration,
+}
+
+#[test]
+fn test_duration_inputs() {
+    std::env::set_var("INPUT_DURATION_TIMEOUT", "90");
+    std::env::remove_var("INPUT_DURATION_INTERVAL");
+
+    let action = DurationAction::init().unwrap();
+    assert_eq!(action.duration_timeout, Duration::from_secs(90));
+    assert_eq!(action.duration_interval, Duration::from_secs(5400));
+
+    std::env::set_var("INPUT_DURATION_TIMEOUT", "90s");
+    std::env::set_var("INPUT_DURATION_INTERVAL", "5m");
+    let action = DurationAction::init().unwrap();
+    assert_eq!(action.duration_timeout, Duration::from_secs(90));
+    assert_eq!(action.duration_interval, Duration::from_secs(300));
+
+    std::env::set_var("INPUT_DURATION_TIMEOUT", "soon");
+    let err = DurationAction::init().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Input Type Error: `duration_timeout` (Expected: `duration, found \"soon\"`)"
+    );
+}
+
+#[test]
+fn test_get_input_duration() {
+    std::env::set_var("INPUT_GET_DURATION", "1h30m");
+    assert_eq!(
+        DurationAction::get_input_duration("get_duration"),
+        Ok(Duration::from_secs(5400))
+    );
+
+    std::env::set_var("INPUT_GET_DURATION", "ten minutes");
+    assert_eq!(
+        DurationAction::get_input_duration("get_duration"),
+        Err(ActionsError::InputTypeError(
+            "get_duration".to_string(),
+            "duration, found \"ten minutes\"".to_string()
+        ))
+    );
+}
@@ -0,0 +1,76 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Errors Action")]
+struct ErrorsAction {
+    #[input(description = "Token", required = true)]
+    errors_token: String,
+
+    #[input(description = "Repository", required = true)]
+    errors_repository: String,
+
+    #[input(description = "Count")]
+    errors_count: i32,
+}
+
+#[test]
+fn test_multiple_missing_inputs() {
+    std::env::remove_var("INPUT_ERRORS_TOKEN");
+    std::env::remove_var("INPUT_ERRORS_REPOSITORY");
+    std::env::set_var("INPUT_ERRORS_COUNT", "not-a-number");
+
+    let err = ErrorsAction::init().unwrap_err();
+    assert_eq!(
+        err,
+        ActionsError::MultipleErrors(vec![
+            ActionsError::MissingInput {
+                input: "errors_token".to_string(),
+                description: Some("Token".to_string()),
+            },
+            ActionsError::MissingInput {
+                input: "errors_repository".to_string(),
+                description: Some("Repository".to_string()),
+            },
+            ActionsError::InputTypeError("errors_count".to_string(), "int".to_string()),
+        ])
+    );
+
+    let message = err.to_string();
+    assert!(message.contains(
+        "\n - Missing required input 'errors_token' (Token) \u{2014} set it under `with:` in your workflow"
+    ));
+    assert!(message.contains("\n - Missing required input 'errors_repository' (Repository)"));
+}
+
+#[derive(Actions, Debug)]
+#[action(name = "Missing Input Action")]
+struct MissingInputAction {
+    #[input(name = "token", description = "GitHub Token", required = true)]
+    missing_token: String,
+}
+
+#[derive(Actions, Debug)]
+#[action(name = "Missing Input Action")]
+struct MissingUndocumentedInputAction {
+    #[input(name = "missing-token", required = true)]
+    missing_token: String,
+}
+
+#[test]
+fn test_missing_required_input() {
+    std::env::remove_var("INPUT_TOKEN");
+    assert_eq!(
+        MissingInputAction::init().unwrap_err().to_string(),
+        "Missing required input 'token' (GitHub Token) \u{2014} set it under `with:` in your workflow"
+    );
+
+    std::env::remove_var("INPUT_MISSING-TOKEN");
+    assert_eq!(
+        MissingUndocumentedInputAction::init()
+            .unwrap_err()
+            .to_string(),
+        "Missing required input 'missing-token' \u{2014} set it under `with:` in your workflow"
+    );
+}
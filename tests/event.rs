@@ -0,0 +1,59 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+use serde::Deserialize;
+
+#[derive(Actions, Debug)]
+#[action(name = "Event Action")]
+struct EventAction {}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestEvent {
+    action: String,
+    pull_request: PullRequest,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    number: u64,
+    title: String,
+}
+
+/// `GITHUB_EVENT_PATH` is read from the environment, so the cases run in one test
+#[test]
+fn test_event_payload() {
+    let action = EventAction {};
+
+    std::env::remove_var("GITHUB_EVENT_PATH");
+    assert_eq!(
+        action.get_event_payload(),
+        Err(ActionsError::EventPathMissing)
+    );
+
+    // Pull request payload
+    std::env::set_var(
+        "GITHUB_EVENT_PATH",
+        "tests/fixtures/event-pull-request.json",
+    );
+    let payload = action.get_event_payload().unwrap();
+    assert_eq!(payload["pull_request"]["number"], 42);
+
+    let event: PullRequestEvent = action.get_event().unwrap();
+    assert_eq!(event.action, "opened");
+    assert_eq!(event.pull_request.number, 42);
+    assert_eq!(event.pull_request.title, "Add event payload helpers");
+
+    // Missing file
+    std::env::set_var("GITHUB_EVENT_PATH", "tests/fixtures/missing-event.json");
+    let err = action.get_event_payload().unwrap_err();
+    assert!(err.is_not_found());
+
+    // Malformed JSON
+    std::env::set_var("GITHUB_EVENT_PATH", "tests/fixtures/junit.xml");
+    assert!(matches!(
+        action.get_event_payload(),
+        Err(ActionsError::EventPayloadError { .. })
+    ));
+
+    std::env::remove_var("GITHUB_EVENT_PATH");
+}
@@ -0,0 +1,98 @@
+#![allow(dead_code)]
+
+use std::sync::Mutex;
+
+use ghactions::prelude::*;
+
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Actions, Debug)]
+#[action(name = "Events Action", events = "pull_request, push")]
+struct EventsAction {}
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Events Skip Action",
+    events = "pull_request, push",
+    events_mode = "skip"
+)]
+struct EventsSkipAction {}
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Events Skip Required Action",
+    events = "push",
+    events_mode = "skip"
+)]
+struct EventsSkipRequiredAction {
+    #[input(description = "Token", required = true)]
+    token: String,
+}
+
+#[test]
+fn test_events_supported() {
+    let _lock = ENV_LOCK.lock().unwrap();
+    std::env::set_var("GITHUB_EVENT_NAME", "push");
+
+    let action = EventsAction::init().unwrap();
+    assert!(!EventsAction::should_skip());
+    assert_eq!(
+        action.description(),
+        "(Supported events: pull_request, push)"
+    );
+    assert_eq!(EventsAction::supported_events(), &["pull_request", "push"]);
+}
+
+#[test]
+fn test_events_unsupported_error() {
+    let _lock = ENV_LOCK.lock().unwrap();
+    std::env::set_var("GITHUB_EVENT_NAME", "schedule");
+
+    let err = EventsAction::init().unwrap_err();
+    assert_eq!(
+        err,
+        ActionsError::UnsupportedEvent {
+            got: "schedule".to_string(),
+            supported: vec!["pull_request".to_string(), "push".to_string()],
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Unsupported event: `schedule` (supported: pull_request, push)"
+    );
+}
+
+#[test]
+fn test_events_unsupported_skip() {
+    let _lock = ENV_LOCK.lock().unwrap();
+    std::env::set_var("GITHUB_EVENT_NAME", "schedule");
+
+    let err = EventsSkipAction::init().unwrap_err();
+    assert_eq!(
+        err,
+        ActionsError::SkippedEvent {
+            got: "schedule".to_string(),
+            supported: vec!["pull_request".to_string(), "push".to_string()],
+        }
+    );
+    assert!(EventsSkipAction::should_skip());
+}
+
+#[test]
+fn test_events_unsupported_skip_required_input() {
+    let _lock = ENV_LOCK.lock().unwrap();
+    std::env::set_var("GITHUB_EVENT_NAME", "schedule");
+    std::env::remove_var("INPUT_TOKEN");
+
+    // Skipped before the (missing) required input is parsed
+    assert!(matches!(
+        EventsSkipRequiredAction::init(),
+        Err(ActionsError::SkippedEvent { .. })
+    ));
+
+    std::env::set_var("GITHUB_EVENT_NAME", "push");
+    assert!(!matches!(
+        EventsSkipRequiredAction::init(),
+        Err(ActionsError::SkippedEvent { .. })
+    ));
+}
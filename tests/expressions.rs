@@ -0,0 +1,56 @@
+#![allow(dead_code)]
+
+use std::sync::Mutex;
+
+use ghactions::prelude::*;
+
+/// Logger capturing warnings emitted by the Action
+struct CaptureLogger;
+
+static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        if record.level() == log::Level::Warn {
+            WARNINGS.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[derive(Actions, Debug)]
+#[action(name = "Expressions Action")]
+struct ExpressionsAction {
+    #[input(description = "Token")]
+    expressions_token: String,
+
+    #[input(description = "Template", allow_expressions)]
+    expressions_template: String,
+
+    #[input(description = "Name")]
+    expressions_name: String,
+}
+
+#[test]
+fn test_unexpanded_expressions() {
+    log::set_logger(&CaptureLogger).unwrap();
+    log::set_max_level(log::LevelFilter::Warn);
+
+    std::env::set_var("INPUT_EXPRESSIONS_TOKEN", "${{ secrets.MY_TOKEN }}");
+    std::env::set_var("INPUT_EXPRESSIONS_TEMPLATE", "${{ github.sha }}");
+    std::env::set_var("INPUT_EXPRESSIONS_NAME", "geekmasher");
+
+    let action = ExpressionsAction::init().unwrap();
+    assert_eq!(action.expressions_token, "${{ secrets.MY_TOKEN }}");
+
+    let warnings = WARNINGS.lock().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].starts_with("Input `expressions_token` contains an unexpanded"));
+    // The value is never logged
+    assert!(!warnings[0].contains("secrets.MY_TOKEN"));
+}
@@ -0,0 +1,104 @@
+#![allow(dead_code)]
+
+use std::process::{Command, Output};
+
+use anyhow::Context;
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Fail Action")]
+struct FailAction {
+    #[input(description = "Target to deploy", required = true)]
+    fail_target: String,
+}
+
+/// Run by the tests in a child process so the exit code can be checked
+#[test]
+fn fail_child() {
+    let Ok(case) = std::env::var("FAIL_CHILD") else {
+        return;
+    };
+    ghactions::deadline::add_cleanup_hook(|| println!("FAIL_CLEANUP"));
+
+    match case.as_str() {
+        "fail" => ghactions::fail("Deploy failed: 100%\nretry later"),
+        "blocking" => {
+            let target = ghactions::run_blocking(|action: FailAction| {
+                Ok::<String, ActionsError>(action.fail_target)
+            });
+            println!("TARGET={}", target);
+        }
+        "async" => {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(ghactions::run(|action: FailAction| async move {
+                std::fs::read_to_string("./missing/deploy.toml")
+                    .with_context(|| format!("Failed to deploy `{}`", action.fail_target))
+            }));
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn run_child(case: &str, target: Option<&str>) -> Output {
+    let mut command = Command::new(std::env::current_exe().unwrap());
+    command
+        .args(["fail_child", "--exact", "--nocapture", "--test-threads=1"])
+        .env("FAIL_CHILD", case)
+        .env("GHACTIONS_LOCAL_MODE", "false")
+        .env_remove("INPUT_FAIL_TARGET");
+    if let Some(target) = target {
+        command.env("INPUT_FAIL_TARGET", target);
+    }
+    command.output().unwrap()
+}
+
+#[test]
+fn test_fail() {
+    let output = run_child("fail", None);
+    assert_eq!(
+        output.status.code(),
+        Some(ghactions::exit::FAILURE_EXIT_CODE)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // Cleanup hooks run before the error is reported
+    let cleanup = stdout.find("FAIL_CLEANUP").unwrap();
+    let error = stdout
+        .find("::error::Deploy failed: 100%25%0Aretry later")
+        .unwrap();
+    assert!(cleanup < error);
+}
+
+#[test]
+fn test_run_blocking() {
+    let output = run_child("blocking", Some("production"));
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("TARGET=production"));
+
+    // Parsing errors fail the Action
+    let output = run_child("blocking", None);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(
+            "::error title=Invalid input::Missing required input 'fail_target' (Target to deploy)"
+        ),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("TARGET="));
+}
+
+#[test]
+fn test_run_async() {
+    let output = run_child("async", Some("staging"));
+    assert_eq!(output.status.code(), Some(1));
+
+    // The sources of the error are included in the message
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("::error::Failed to deploy `staging`: No such file or directory"),
+        "{}",
+        stdout
+    );
+}
@@ -0,0 +1,47 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Flags Action", path = "./target/ghactions-tests/flags.yml")]
+struct FlagsAction {
+    #[input(description = "Verbose output", flag)]
+    flag_verbose: bool,
+}
+
+#[test]
+fn test_flag_values() {
+    std::env::remove_var("INPUT_FLAG_VERBOSE");
+    assert!(!FlagsAction::init().unwrap().flag_verbose);
+
+    std::env::set_var("INPUT_FLAG_VERBOSE", "");
+    assert!(FlagsAction::init().unwrap().flag_verbose);
+
+    std::env::set_var("INPUT_FLAG_VERBOSE", "true");
+    assert!(FlagsAction::init().unwrap().flag_verbose);
+
+    std::env::set_var("INPUT_FLAG_VERBOSE", "false");
+    assert!(!FlagsAction::init().unwrap().flag_verbose);
+
+    std::env::set_var("INPUT_FLAG_VERBOSE", "junk");
+    assert_eq!(
+        FlagsAction::init().unwrap_err(),
+        ActionsError::InputTypeError(
+            "flag_verbose".to_string(),
+            "bool: true, True, TRUE, false, False or FALSE".to_string()
+        )
+    );
+}
+
+#[test]
+fn test_flag_yaml() {
+    let action =
+        ghactions::ActionYML::load_action("./target/ghactions-tests/flags.yml".to_string())
+            .unwrap();
+    let input = &action.inputs["flag_verbose"];
+    assert_eq!(input.default, Some("false".to_string()));
+    assert_eq!(
+        input.description,
+        Some("Verbose output (flag: empty or `true` to enable)".to_string())
+    );
+}
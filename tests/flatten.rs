@@ -0,0 +1,74 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+mod shared {
+    use ghactions::prelude::*;
+
+    /// Inputs shared by all the Actions in the repository
+    #[derive(ActionInputs, Debug)]
+    pub struct CommonInputs {
+        #[input(description = "GitHub Token", required = true)]
+        pub token: String,
+
+        #[input(description = "Repository", default = "42ByteLabs/ghactions")]
+        pub repository: String,
+
+        #[input(name = "dry-run", description = "Dry run", default = "false")]
+        pub dry_run: bool,
+    }
+}
+
+#[derive(Actions, Debug)]
+#[action(name = "Flatten Action")]
+struct FlattenAction {
+    #[input(description = "Mode", default = "fast")]
+    mode: String,
+
+    #[input(flatten)]
+    common: shared::CommonInputs,
+
+    #[output(description = "Output Version")]
+    version: String,
+}
+
+#[derive(Actions, Debug)]
+#[action(name = "Other Action")]
+struct OtherAction {
+    #[input(flatten)]
+    common: shared::CommonInputs,
+}
+
+#[test]
+fn test_flatten_init() {
+    std::env::set_var("INPUT_TOKEN", "abc123");
+    std::env::set_var("INPUT_DRY-RUN", "true");
+
+    let action = FlattenAction::init().unwrap();
+    assert_eq!(action.mode, "fast");
+    assert_eq!(action.common.token, "abc123");
+    assert_eq!(action.common.repository, "42ByteLabs/ghactions");
+    assert!(action.common.dry_run);
+
+    let other = OtherAction::init().unwrap();
+    assert_eq!(other.common.token, "abc123");
+
+    // Errors of flattened inputs are reported by the Action
+    std::env::remove_var("INPUT_TOKEN");
+    assert_eq!(
+        FlattenAction::init().unwrap_err(),
+        ActionsError::MissingInput {
+            input: "token".to_string(),
+            description: Some("GitHub Token".to_string()),
+        }
+    );
+    std::env::remove_var("INPUT_DRY-RUN");
+}
+
+#[test]
+fn test_flatten_action_file() {
+    assert_eq!(
+        FlattenAction::verify_action_file("tests/fixtures/flatten.yml"),
+        Ok(())
+    );
+}
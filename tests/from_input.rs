@@ -0,0 +1,66 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Debug, Default, PartialEq)]
+struct CommitSha(String);
+
+impl FromInput for CommitSha {
+    fn from_input(raw: &str) -> Result<Self, String> {
+        if raw.len() == 40 && raw.chars().all(|c| c.is_ascii_hexdigit()) {
+            Ok(CommitSha(raw.to_string()))
+        } else {
+            Err(format!("`{}` is not a commit SHA", raw))
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Port(u16);
+
+impl FromInput for Port {
+    fn from_input(raw: &str) -> Result<Self, String> {
+        Ok(Port(u16::from_input(raw)?))
+    }
+}
+
+#[derive(Actions, Debug)]
+#[action(name = "FromInput Action")]
+struct FromInputAction {
+    #[input(description = "Commit SHA")]
+    from_input_sha: CommitSha,
+
+    #[input(description = "Port", default = "8080")]
+    from_input_port: Port,
+
+    #[input(description = "Size")]
+    from_input_size: u64,
+}
+
+#[test]
+fn test_from_input() {
+    std::env::set_var(
+        "INPUT_FROM_INPUT_SHA",
+        "0123456789abcdef0123456789abcdef01234567",
+    );
+    std::env::remove_var("INPUT_FROM_INPUT_PORT");
+    std::env::set_var("INPUT_FROM_INPUT_SIZE", "5000000000");
+
+    let action = FromInputAction::init().unwrap();
+    assert_eq!(
+        action.from_input_sha,
+        CommitSha("0123456789abcdef0123456789abcdef01234567".to_string())
+    );
+    assert_eq!(action.from_input_port, Port(8080));
+    assert_eq!(action.from_input_size, 5_000_000_000);
+
+    // Validation fails at runtime
+    std::env::set_var("INPUT_FROM_INPUT_SHA", "main");
+    assert_eq!(
+        FromInputAction::init().unwrap_err(),
+        ActionsError::InputParseError(
+            "from_input_sha".to_string(),
+            "`main` is not a commit SHA".to_string()
+        )
+    );
+}
@@ -0,0 +1,25 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+/// The build fails if `tests/fixtures/generate-check.yml` is out of date
+#[derive(Actions, Debug)]
+#[action(
+    name = "Generate Check Action",
+    description = "Generate Check Action Description",
+    path = "./tests/fixtures/generate-check.yml",
+    generate = "check"
+)]
+struct GenerateCheckAction {
+    #[input(description = "Mode", default = "fast")]
+    mode: String,
+}
+
+#[test]
+fn test_generate_check() {
+    let action =
+        ghactions::ActionYML::load_action("./tests/fixtures/generate-check.yml".to_string())
+            .unwrap();
+    assert_eq!(action.name.as_deref(), Some("Generate Check Action"));
+    assert_eq!(action.inputs["mode"].default.as_deref(), Some("fast"));
+}
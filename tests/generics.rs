@@ -0,0 +1,48 @@
+#![allow(dead_code)]
+
+use std::marker::PhantomData;
+
+use ghactions::prelude::*;
+
+trait Config: std::fmt::Debug {
+    const NAME: &'static str;
+}
+
+#[derive(Debug)]
+struct Production;
+
+impl Config for Production {
+    const NAME: &'static str = "production";
+}
+
+#[derive(Actions, Debug)]
+#[action(name = "Generic Action")]
+struct GenericAction<T>
+where
+    T: Config,
+{
+    #[input(description = "Mode", default = "fast")]
+    mode: String,
+
+    #[output(description = "Version")]
+    version: String,
+
+    config: PhantomData<T>,
+}
+
+impl<T: Config> GenericAction<T> {
+    fn environment(&self) -> &'static str {
+        T::NAME
+    }
+}
+
+#[test]
+fn test_generic_action() {
+    let mut action = GenericAction::<Production>::init().unwrap();
+    assert_eq!(action.mode, "fast");
+    assert_eq!(action.environment(), "production");
+    assert_eq!(action.name(), "Generic Action");
+
+    action.set_version("1.0.0").unwrap();
+    assert_eq!(action.version, "1.0.0");
+}
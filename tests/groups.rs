@@ -0,0 +1,80 @@
+#![allow(dead_code)]
+
+use std::sync::Mutex;
+
+use ghactions::prelude::*;
+
+/// Logger capturing the messages of the records
+struct CaptureLogger;
+
+static RECORDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        RECORDS.lock().unwrap().push(record.args().to_string());
+    }
+
+    fn flush(&self) {}
+}
+
+fn step(fail: bool) -> Result<u32, String> {
+    let _group = log_group("Step");
+    let value = group_scope!("Inner", {
+        if fail {
+            Err::<(), _>("failed".to_string())?;
+        }
+        info!("working");
+        42
+    });
+    Ok(value)
+}
+
+/// The logger is global, so the cases run in one test
+#[test]
+fn test_group_guards() {
+    log::set_logger(&CaptureLogger).unwrap();
+    log::set_max_level(log::LevelFilter::Info);
+
+    // Early return from the inner scope closes both groups
+    assert_eq!(step(true), Err("failed".to_string()));
+    assert_eq!(
+        std::mem::take(&mut *RECORDS.lock().unwrap()),
+        vec![
+            "::group::Step",
+            "::group::Inner",
+            "::endgroup::",
+            "::endgroup::"
+        ]
+    );
+
+    assert_eq!(step(false), Ok(42));
+    assert_eq!(
+        std::mem::take(&mut *RECORDS.lock().unwrap()),
+        vec![
+            "::group::Step",
+            "::group::Inner",
+            "working",
+            "::endgroup::",
+            "::endgroup::"
+        ]
+    );
+
+    // Dropping the outer guard first also closes the inner group (once)
+    let outer = log_group("Outer");
+    let inner = log_group("Nested");
+    drop(outer);
+    drop(inner);
+    assert_eq!(
+        std::mem::take(&mut *RECORDS.lock().unwrap()),
+        vec![
+            "::group::Outer",
+            "::group::Nested",
+            "::endgroup::",
+            "::endgroup::"
+        ]
+    );
+}
@@ -0,0 +1,87 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Init From Action", events = "push")]
+struct InitFromAction {
+    #[input(description = "Repository", required = true)]
+    repository: String,
+
+    #[input(description = "Retries", default = "3")]
+    retries: i32,
+
+    #[input(description = "Labels", split = ",", trim)]
+    labels: Vec<String>,
+}
+
+fn env(vars: &[(&str, &str)]) -> HashMap<String, String> {
+    vars.iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_init_from() {
+    let action = InitFromAction::init_from(&env(&[
+        ("INPUT_REPOSITORY", "42ByteLabs/ghactions"),
+        ("INPUT_LABELS", "bug, docs"),
+        ("GITHUB_EVENT_NAME", "push"),
+    ]))
+    .unwrap();
+    assert_eq!(action.repository, "42ByteLabs/ghactions");
+    assert_eq!(action.retries, 3);
+    assert_eq!(action.labels, vec!["bug", "docs"]);
+
+    // The process environment is not used
+    std::env::set_var("INPUT_REPOSITORY", "process/env");
+    assert_eq!(
+        InitFromAction::init_from(&env(&[("GITHUB_EVENT_NAME", "push")])).unwrap_err(),
+        ActionsError::MissingInput {
+            input: "repository".to_string(),
+            description: Some("Repository".to_string()),
+        }
+    );
+    std::env::remove_var("INPUT_REPOSITORY");
+
+    // The event guard uses the provided `GITHUB_EVENT_NAME`
+    let err = InitFromAction::init_from(&env(&[
+        ("INPUT_REPOSITORY", "42ByteLabs/ghactions"),
+        ("GITHUB_EVENT_NAME", "pull_request"),
+    ]))
+    .unwrap_err();
+    assert!(matches!(err, ActionsError::UnsupportedEvent { .. }));
+}
+
+#[test]
+fn test_init_from_parallel() {
+    let threads: Vec<_> = (0..8)
+        .map(|index| {
+            std::thread::spawn(move || {
+                let env = env(&[
+                    ("INPUT_REPOSITORY", &format!("owner/repo-{}", index)),
+                    ("INPUT_RETRIES", &index.to_string()),
+                    ("GITHUB_EVENT_NAME", "push"),
+                ]);
+                for _ in 0..50 {
+                    let action = InitFromAction::init_from(&env).unwrap();
+                    assert_eq!(action.repository, format!("owner/repo-{}", index));
+                    assert_eq!(action.retries, index);
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+}
+
+#[test]
+fn test_getters_with_env() {
+    let value = ghactions::env::with_env(env(&[("INPUT_RETRIES", " 5 ")]), || {
+        InitFromAction::get_input_int("retries")
+    });
+    assert_eq!(value, Ok(5));
+}
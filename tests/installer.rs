@@ -0,0 +1,94 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Installer Action",
+    path = "./target/ghactions-tests/installer.yml",
+    installer
+)]
+struct InstallerAction {}
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Custom Installer Action",
+    path = "./target/ghactions-tests/installer-custom.yml",
+    installer = "./tests/fixtures/installer.sh",
+    compile_step_name = "Download the tool"
+)]
+struct CustomInstallerAction {}
+
+fn runs(path: &str) -> String {
+    let content = std::fs::read_to_string(path).unwrap();
+    content[content.find("runs:").unwrap()..].to_string()
+}
+
+#[test]
+fn test_installer_yaml() {
+    let binary = std::env::var("CARGO_BIN_NAME").unwrap_or_else(|_| "action".to_string());
+    let script: String = ghactions_core::actions::models::INSTALLER_SCRIPT
+        .trim_end()
+        .lines()
+        .map(|line| match line.is_empty() {
+            true => "\n".to_string(),
+            false => format!("      {}\n", line),
+        })
+        .collect();
+
+    assert_eq!(
+        runs("./target/ghactions-tests/installer.yml"),
+        format!(
+            r#"runs:
+  using: composite
+  steps:
+  - id: installer
+    name: Install the Action binary
+    shell: bash
+    run: |
+{}    env:
+      ACTION_BINARY: {}
+      ACTION_REPOSITORY: ${{{{ github.action_repository }}}}
+      ACTION_REF: ${{{{ github.action_ref }}}}
+      GH_TOKEN: ${{{{ github.token }}}}
+  - id: cargo-run
+    name: Run the Action
+    shell: bash
+    run: |-
+      set -e
+      {}
+"#,
+            script, binary, binary
+        )
+    );
+}
+
+#[test]
+fn test_custom_installer_yaml() {
+    let binary = std::env::var("CARGO_BIN_NAME").unwrap_or_else(|_| "action".to_string());
+    assert_eq!(
+        runs("./target/ghactions-tests/installer-custom.yml"),
+        format!(
+            r#"runs:
+  using: composite
+  steps:
+  - id: installer
+    name: Download the tool
+    shell: bash
+    run: ${{{{ github.action_path }}}}/tests/fixtures/installer.sh
+    env:
+      ACTION_BINARY: {}
+      ACTION_REPOSITORY: ${{{{ github.action_repository }}}}
+      ACTION_REF: ${{{{ github.action_ref }}}}
+      GH_TOKEN: ${{{{ github.token }}}}
+  - id: cargo-run
+    name: Run the Action
+    shell: bash
+    run: |-
+      set -e
+      {}
+"#,
+            binary, binary
+        )
+    );
+}
@@ -0,0 +1,62 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use ghactions::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+struct Report {
+    passed: u32,
+    failed: Vec<String>,
+}
+
+#[derive(Actions, Debug)]
+#[action(name = "JSON Outputs Action")]
+struct JsonOutputsAction {
+    #[output(description = "Test report", json = true)]
+    report: Report,
+
+    #[output(description = "Version")]
+    version: String,
+}
+
+#[test]
+fn test_json_outputs() {
+    let path =
+        std::env::temp_dir().join(format!("ghactions-json-outputs-{}.env", std::process::id()));
+    std::env::set_var("GITHUB_OUTPUT", &path);
+    ghactions::reset_paths_for_testing();
+
+    let report = Report {
+        passed: 41,
+        failed: vec!["tests::a\nb".to_string()],
+    };
+    JsonOutputsAction::set_output_json("summary", &report).unwrap();
+
+    let mut action = JsonOutputsAction::init().unwrap();
+    action.set_report(report.clone()).unwrap();
+    assert_eq!(action.report, report);
+    action.write_outputs().unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let outputs = ghactions::parse_env_file(&content);
+    let names: Vec<&str> = outputs.iter().map(|(name, _)| name.as_str()).collect();
+    // Empty outputs (`version`) are not written
+    assert_eq!(names, vec!["summary", "report", "report"]);
+    // JSON outputs are written using the heredoc format
+    assert!(content.starts_with("summary<<ghadelimiter_"));
+    for (name, value) in outputs.iter() {
+        let parsed: Report = serde_json::from_str(value).unwrap();
+        assert_eq!(parsed, report, "output `{}`", name);
+    }
+
+    // Values which can not be serialized
+    let invalid: HashMap<(u8, u8), u8> = HashMap::from([((1, 2), 3)]);
+    assert!(matches!(
+        JsonOutputsAction::set_output_json("invalid", &invalid),
+        Err(ActionsError::SerializationError(_))
+    ));
+}
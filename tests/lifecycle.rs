@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Lifecycle Docker Action",
+    path = "./target/ghactions-tests/lifecycle-docker.yml",
+    image = "./examples/advanced/Dockerfile",
+    pre_entrypoint = "/setup.sh",
+    post_entrypoint = "/cleanup.sh",
+    post_if = "always()"
+)]
+struct LifecycleDockerAction {}
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Lifecycle Composite Action",
+    path = "./target/ghactions-tests/lifecycle-composite.yml",
+    post_entrypoint = "./cleanup.sh",
+    post_if = "always()"
+)]
+struct LifecycleCompositeAction {}
+
+#[test]
+fn test_lifecycle_docker_yaml() {
+    let content = std::fs::read_to_string("./target/ghactions-tests/lifecycle-docker.yml").unwrap();
+    let runs = &content[content.find("runs:").unwrap()..];
+
+    assert_eq!(
+        runs,
+        r#"runs:
+  using: docker
+  image: ./examples/advanced/Dockerfile
+  pre-entrypoint: /setup.sh
+  post-entrypoint: /cleanup.sh
+  post-if: always()
+"#
+    );
+}
+
+#[test]
+fn test_lifecycle_composite_yaml() {
+    let content =
+        std::fs::read_to_string("./target/ghactions-tests/lifecycle-composite.yml").unwrap();
+    let post = &content[content.find("  - id: post-entrypoint").unwrap()..];
+
+    assert_eq!(
+        post,
+        r#"  - id: post-entrypoint
+    name: Post entrypoint
+    shell: bash
+    run: ${{ github.action_path }}/cleanup.sh
+    if: always()
+"#
+    );
+}
@@ -0,0 +1,76 @@
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// Run the `ghactions-lint` example (built by `cargo test` with the other examples)
+fn lint(args: &[&str], github_actions: bool) -> Output {
+    let binary: PathBuf = std::env::current_exe()
+        .unwrap()
+        .parent()
+        .and_then(|deps| deps.parent())
+        .unwrap()
+        .join("examples")
+        .join("ghactions-lint");
+    assert!(
+        binary.exists(),
+        "run `cargo build --example ghactions-lint` first"
+    );
+    let mut command = Command::new(binary);
+    command.args(args).env_remove("GITHUB_ACTIONS");
+    if github_actions {
+        command.env("GITHUB_ACTIONS", "true");
+    }
+    command.output().unwrap()
+}
+
+#[test]
+fn test_lint_good() {
+    let output = lint(&["tests/fixtures/lint-good.yml"], false);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_lint_bad() {
+    let output = lint(
+        &[
+            "tests/fixtures/lint-good.yml",
+            "tests/fixtures/lint-bad.yml",
+        ],
+        false,
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        r#"tests/fixtures/lint-bad.yml:13: warning: `icon` is not a supported key
+tests/fixtures/lint-bad.yml: error: `description` is required
+tests/fixtures/lint-bad.yml:3: warning: `inputs.mode` has no description
+tests/fixtures/lint-bad.yml:6: error: `outputs.version` needs a value for composite Actions
+tests/fixtures/lint-bad.yml:10: error: `runs.steps[0]` needs a shell to run a command
+"#
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "3 error(s) found\n"
+    );
+}
+
+#[test]
+fn test_lint_annotations() {
+    let output = lint(&["tests/fixtures/lint-bad.yml"], true);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(
+        "::error file=tests/fixtures/lint-bad.yml,line=6::error: `outputs.version` needs a value for composite Actions\n"
+    ));
+    assert!(stdout.contains(
+        "::warning file=tests/fixtures/lint-bad.yml,line=3::warning: `inputs.mode` has no description\n"
+    ));
+}
+
+#[test]
+fn test_lint_missing_file() {
+    let output = lint(&["tests/fixtures/does-not-exist.yml"], false);
+    assert_eq!(output.status.code(), Some(1));
+}
@@ -0,0 +1,68 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+use ghactions::Summary;
+
+/// Set when the test runs in a child process (the logger can only be initialised once)
+const CHILD_ENV: &str = "GHACTIONS_LOCAL_MODE_TEST_CHILD";
+const SUMMARY_PATH: &str = "./target/ghactions-tests/local-mode-summary.md";
+
+/// Run the test in a child process and return its stdout and stderr
+fn run_child(github_actions: bool) -> (String, String) {
+    let mut command = std::process::Command::new(std::env::current_exe().unwrap());
+    command
+        .args(["test_local_mode_sinks", "--exact", "--nocapture"])
+        .env(CHILD_ENV, "1")
+        .env("GHACTIONS_SUMMARY_PATH", SUMMARY_PATH)
+        .env_remove("GHACTIONS_LOCAL_MODE")
+        .env_remove("GITHUB_STEP_SUMMARY")
+        .env_remove("GITHUB_ACTIONS");
+    if github_actions {
+        command.env("GITHUB_ACTIONS", "true");
+    }
+    let output = command.output().unwrap();
+    assert!(output.status.success());
+    (
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn test_local_mode_sinks() {
+    if std::env::var_os(CHILD_ENV).is_some() {
+        ghactions::init_logger().init();
+        errorf!(file: "src/main.rs", line: 10, "Invalid config");
+        // Fails in GitHub Actions as `GITHUB_STEP_SUMMARY` is not set
+        let _ = Summary::new().text("Local summary").write();
+        return;
+    }
+    std::fs::create_dir_all("./target/ghactions-tests").unwrap();
+    let _ = std::fs::remove_file(SUMMARY_PATH);
+
+    // Locally, annotations are written to stderr and the summary to the local file
+    let (stdout, stderr) = run_child(false);
+    assert!(!stdout.contains("::error"), "{}", stdout);
+    assert!(stderr.contains("src/main.rs:10:"), "{}", stderr);
+    assert!(stderr.contains("Invalid config"), "{}", stderr);
+    assert!(
+        stderr.contains(&format!("job summary is written to `{}`", SUMMARY_PATH)),
+        "{}",
+        stderr
+    );
+    assert_eq!(
+        std::fs::read_to_string(SUMMARY_PATH).unwrap(),
+        "Local summary\n\n"
+    );
+    std::fs::remove_file(SUMMARY_PATH).unwrap();
+
+    // In GitHub Actions, annotations are workflow commands and no local summary is written
+    let (stdout, stderr) = run_child(true);
+    assert!(
+        stdout.contains("::error file=src/main.rs,line=10::Invalid config"),
+        "{}",
+        stdout
+    );
+    assert!(!stderr.contains("Invalid config"), "{}", stderr);
+    assert!(!std::path::Path::new(SUMMARY_PATH).exists());
+}
@@ -0,0 +1,56 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Multiline Action")]
+struct MultilineAction {
+    #[input(description = "Files", multiline = true)]
+    multiline_files: Vec<String>,
+}
+
+#[test]
+fn test_multiline_crlf() {
+    std::env::set_var(
+        "INPUT_MULTILINE_FILES",
+        "src/main.rs\r\nsrc/lib.rs, with comma\r\n",
+    );
+    let action = MultilineAction::init().unwrap();
+    assert_eq!(
+        action.multiline_files,
+        vec!["src/main.rs", "src/lib.rs, with comma"]
+    );
+}
+
+#[test]
+fn test_multiline_blank_lines() {
+    assert_eq!(
+        MultilineAction::get_multiline_input("multiline_blank").ok(),
+        None
+    );
+
+    std::env::set_var("INPUT_MULTILINE_BLANK", "  first  \n\n   \nsecond\n");
+    assert_eq!(
+        MultilineAction::get_multiline_input("multiline_blank").unwrap(),
+        vec!["first", "second"]
+    );
+}
+
+#[test]
+fn test_get_multiline_input() {
+    // YAML block scalar (`files: |`)
+    std::env::set_var(
+        "INPUT_MULTILINE_BLOCK",
+        "Cargo.toml\n  src/**/*.rs\n\nREADME.md\n",
+    );
+    assert_eq!(
+        MultilineAction::get_multiline_input("multiline_block").unwrap(),
+        vec!["Cargo.toml", "src/**/*.rs", "README.md"]
+    );
+
+    std::env::set_var("INPUT_MULTILINE_SINGLE", " Cargo.toml ");
+    assert_eq!(
+        MultilineAction::get_multiline_input("multiline_single").unwrap(),
+        vec!["Cargo.toml"]
+    );
+}
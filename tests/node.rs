@@ -0,0 +1,52 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+use ghactions::ActionYML;
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Node Action",
+    description = "JavaScript shim downloading the Action binary",
+    path = "./target/ghactions-tests/node.yml",
+    node = "20",
+    main = "./dist/index.js",
+    post_entrypoint = "./dist/cleanup.js",
+    post_if = "always()"
+)]
+struct NodeAction {
+    #[input(description = "Version of the Action binary", default = "latest")]
+    version: String,
+
+    #[output(description = "Path of the Action binary")]
+    binary: String,
+}
+
+#[test]
+fn test_node_yaml() {
+    let content = std::fs::read_to_string("./target/ghactions-tests/node.yml").unwrap();
+    let runs = &content[content.find("runs:").unwrap()..];
+
+    assert_eq!(
+        runs,
+        r#"runs:
+  using: node20
+  main: ./dist/index.js
+  post: ./dist/cleanup.js
+  post-if: always()
+"#
+    );
+    // Outputs are set by the Action (no composite step values)
+    assert!(!content.contains("value:"));
+}
+
+#[test]
+fn test_node_round_trip() {
+    let action = ActionYML::load_action("./target/ghactions-tests/node.yml".to_string()).unwrap();
+    assert!(action.validate().is_empty());
+    assert_eq!(
+        ActionYML::from_yaml(&action.to_yaml().unwrap())
+            .unwrap()
+            .runs,
+        action.runs
+    );
+}
@@ -0,0 +1,66 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+use ghactions::OutputWriter;
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Output Prefix Action",
+    path = "./target/ghactions-tests/output-prefix.yml",
+    output_prefix = "plan-"
+)]
+struct OutputPrefixAction {
+    #[output(description = "Version")]
+    version: String,
+}
+
+#[test]
+fn test_output_prefix_yaml() {
+    let action =
+        ghactions::ActionYML::load_action("./target/ghactions-tests/output-prefix.yml".to_string())
+            .unwrap();
+    assert_eq!(
+        action.outputs["version"].value.as_deref(),
+        Some("${{ steps.cargo-run.outputs.plan-version }}")
+    );
+    let steps = action.runs.steps.as_ref().unwrap();
+    let run_step = steps
+        .iter()
+        .find(|step| step.id.as_deref() == Some("cargo-run"))
+        .unwrap();
+    let env = run_step.env.as_ref().unwrap();
+    assert_eq!(env["GHACTIONS_OUTPUT_PREFIX"], "plan-");
+}
+
+#[test]
+fn test_output_prefix_shared_file() {
+    let path = std::env::temp_dir().join(format!(
+        "ghactions-output-prefix-{}.env",
+        std::process::id()
+    ));
+    std::env::set_var("GITHUB_OUTPUT", &path);
+    ghactions::reset_paths_for_testing();
+
+    // First invocation (plan) uses the prefix from the environment
+    std::env::set_var("GHACTIONS_OUTPUT_PREFIX", "plan-");
+    let mut action = OutputPrefixAction::init().unwrap();
+    action.set_version("1.0.0").unwrap();
+    OutputPrefixAction::set_output("version", "1.0.1").unwrap();
+    action.write_outputs().unwrap();
+
+    // Second invocation (apply) without a prefix
+    std::env::remove_var("GHACTIONS_OUTPUT_PREFIX");
+    let mut action = OutputPrefixAction::init().unwrap();
+    action.set_version("2.0.0").unwrap();
+
+    OutputWriter::with_prefix("apply-")
+        .set("version", "3.0.0")
+        .unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        content,
+        "plan-version=1.0.0\nplan-version=1.0.1\nplan-version=1.0.0\nversion=2.0.0\napply-version=3.0.0\n"
+    );
+}
@@ -0,0 +1,44 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Output Rename Action",
+    path = "./target/ghactions-tests/output-rename.yml"
+)]
+struct OutputRenameAction {
+    #[output(name = "html-url", description = "HTML URL")]
+    html_url: String,
+}
+
+#[test]
+fn test_output_rename() {
+    let action =
+        ghactions::ActionYML::load_action("./target/ghactions-tests/output-rename.yml".to_string())
+            .unwrap();
+    assert!(!action.outputs.contains_key("html_url"));
+    assert_eq!(
+        action.outputs["html-url"].value,
+        Some("${{ steps.cargo-run.outputs.html-url }}".to_string())
+    );
+
+    let path = std::env::temp_dir().join(format!(
+        "ghactions-output-rename-{}.env",
+        std::process::id()
+    ));
+    std::env::set_var("GITHUB_OUTPUT", &path);
+    ghactions::reset_paths_for_testing();
+
+    let mut action = OutputRenameAction::init().unwrap();
+    action
+        .set_html_url("https://github.com/42ByteLabs/ghactions")
+        .unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        content,
+        "html-url=https://github.com/42ByteLabs/ghactions\n"
+    );
+}
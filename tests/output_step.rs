@@ -0,0 +1,35 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Output Step Action",
+    path = "./target/ghactions-tests/output-step.yml",
+    output_step = "run-tool"
+)]
+struct OutputStepAction {
+    #[output(description = "Version")]
+    version: String,
+
+    #[output(description = "Report", step = "other-step")]
+    report: String,
+}
+
+#[test]
+fn test_output_step_yaml() {
+    let content = std::fs::read_to_string("./target/ghactions-tests/output-step.yml").unwrap();
+    let outputs = &content[content.find("outputs:").unwrap()..content.find("runs:").unwrap()];
+
+    assert_eq!(
+        outputs,
+        r#"outputs:
+  version:
+    description: Version
+    value: ${{ steps.run-tool.outputs.version }}
+  report:
+    description: Report
+    value: ${{ steps.other-step.outputs.report }}
+"#
+    );
+}
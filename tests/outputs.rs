@@ -0,0 +1,127 @@
+#![allow(dead_code)]
+
+use std::process::Command;
+
+use ghactions::prelude::*;
+use ghactions_core::testing::TestEnv;
+
+#[derive(Actions, Debug)]
+#[action(name = "Outputs Action")]
+struct OutputsAction {
+    #[output(description = "Version")]
+    version: String,
+}
+
+#[test]
+fn test_set_output_invalid_name() {
+    assert_eq!(
+        OutputsAction::set_output("my output", "value"),
+        Err(ActionsError::OutputNameError("my output".to_string()))
+    );
+    assert_eq!(
+        OutputsAction::set_output("1output", "value"),
+        Err(ActionsError::OutputNameError("1output".to_string()))
+    );
+}
+
+#[derive(Actions, Debug)]
+#[action(name = "Typed Outputs Action")]
+struct TypedOutputsAction {
+    #[output(description = "Count")]
+    count: u64,
+
+    #[output(description = "Passed")]
+    passed: bool,
+
+    #[output(description = "Notes")]
+    notes: String,
+}
+
+#[test]
+fn test_typed_outputs() {
+    let env = TestEnv::new();
+
+    let mut action = TypedOutputsAction::init().unwrap();
+    assert_eq!(action.count, 0);
+    assert!(!action.passed);
+
+    action.set_count(42).unwrap();
+    action.set_passed(true).unwrap();
+    action.set_notes("first\nsecond").unwrap();
+    assert_eq!(action.count, 42);
+
+    assert_eq!(
+        env.outputs().into_iter().collect::<Vec<_>>(),
+        vec![
+            ("count".to_string(), "42".to_string()),
+            ("passed".to_string(), "true".to_string()),
+            ("notes".to_string(), "first\nsecond".to_string()),
+        ]
+    );
+
+    // Multi-line values use the heredoc format
+    let content = std::fs::read_to_string(env.dir().join("output.env")).unwrap();
+    let lines: Vec<&str> = content.lines().collect();
+    assert!(lines[2].starts_with("notes<<ghadelimiter_"));
+    assert_eq!(lines[5], lines[2].trim_start_matches("notes<<"));
+}
+
+#[test]
+fn test_set_output_file_error() {
+    let env = TestEnv::new();
+    // The output file is a directory
+    let dir = env.dir().display().to_string();
+    let _env = env.github_env("GITHUB_OUTPUT", dir);
+
+    let mut action = TypedOutputsAction::init().unwrap();
+    assert!(matches!(
+        OutputsAction::set_output("version", "1.0.0"),
+        Err(ActionsError::FileError { .. })
+    ));
+    assert!(matches!(
+        action.set_count(42),
+        Err(ActionsError::FileError { .. })
+    ));
+}
+
+/// Run by [test_set_output_command_escaped] in a child process so stdout can be captured
+#[test]
+fn set_output_child() {
+    if std::env::var("SET_OUTPUT_CHILD").is_err() {
+        return;
+    }
+    OutputsAction::set_output("version", "1.0\n::set-env name=PATH::/tmp").unwrap();
+    setoutput!("version", "100%\r\ndone");
+}
+
+#[test]
+fn test_set_output_command_escaped() {
+    // Runners older than 2.297.0 do not support the output file
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args([
+            "set_output_child",
+            "--exact",
+            "--nocapture",
+            "--test-threads=1",
+        ])
+        .env("SET_OUTPUT_CHILD", "1")
+        .env("RUNNER_VERSION", "2.296.0")
+        .env_remove("GITHUB_OUTPUT")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // The test harness prints the test name on the same line
+    let commands: Vec<&str> = stdout
+        .lines()
+        .filter_map(|line| line.find("::").map(|index| &line[index..]))
+        .collect();
+    assert_eq!(
+        commands,
+        vec![
+            "::set-output name=version::1.0%0A::set-env name=PATH::/tmp",
+            "::set-output name=version::100%25%0D%0Adone",
+        ]
+    );
+}
@@ -0,0 +1,29 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Pattern Action")]
+struct PatternAction {
+    #[input(description = "Version", pattern = "^v[0-9]+\\.[0-9]+\\.[0-9]+$")]
+    pattern_version: String,
+}
+
+#[test]
+fn test_pattern_valid() {
+    std::env::set_var("INPUT_PATTERN_VERSION", "v1.2.3");
+    let action = PatternAction::init().unwrap();
+    assert_eq!(action.pattern_version, "v1.2.3");
+
+    std::env::set_var("INPUT_PATTERN_VERSION", "1.2");
+    let err = PatternAction::init().unwrap_err();
+    assert_eq!(
+        err,
+        ActionsError::InputPatternError(
+            "pattern_version".to_string(),
+            "1.2".to_string(),
+            "^v[0-9]+\\.[0-9]+\\.[0-9]+$".to_string()
+        )
+    );
+    assert!(err.to_string().contains("`1.2`"));
+}
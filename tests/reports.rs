@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+use ghactions_core::reports::{Annotation, AnnotationLevel, Annotations};
+
+fn fixture(name: &str) -> std::io::BufReader<std::fs::File> {
+    std::io::BufReader::new(std::fs::File::open(format!("./tests/fixtures/{}", name)).unwrap())
+}
+
+#[test]
+fn test_reports() {
+    std::env::set_var("GITHUB_WORKSPACE", "/home/runner/work/app/app");
+
+    // cargo --message-format=json
+    let annotations = Annotations::from_cargo_json(fixture("cargo-diagnostics.json")).unwrap();
+    assert_eq!(annotations.len(), 4);
+    assert!(annotations.has_errors());
+    assert_eq!(
+        annotations.annotations[0],
+        Annotation {
+            level: AnnotationLevel::Warning,
+            title: Some("unused_variables".to_string()),
+            file: Some(PathBuf::from("src/main.rs")),
+            line: Some(2),
+            end_line: None,
+            column: Some(9),
+            end_column: Some(10),
+            message: "unused variable: `x`\nnote: `#[warn(unused_variables)]` on by default\nhelp: if this is intentional, prefix it with an underscore".to_string(),
+        }
+    );
+    assert_eq!(
+        annotations.lines(),
+        vec![
+            "::warning file=src/main.rs,line=2,col=9,endColumn=10,title=unused_variables::unused variable: `x`%0Anote: `#[warn(unused_variables)]` on by default%0Ahelp: if this is intentional, prefix it with an underscore",
+            // Only the primary span is used
+            "::error file=src/lib.rs,line=6,col=13,endColumn=19,title=E0499::cannot borrow `v` as mutable more than once at a time",
+            // Multiple primary spans (multi-line spans have no end column)
+            "::error file=src/lib.rs,line=10,endLine=12,col=5,title=E0308::mismatched types",
+            "::error file=src/lib.rs,line=14,col=5,endColumn=15,title=E0308::mismatched types",
+        ]
+    );
+
+    // JUnit XML
+    let annotations = Annotations::from_junit(fixture("junit.xml")).unwrap();
+    assert_eq!(
+        annotations.lines(),
+        vec![
+            "::error file=src/lib.rs,line=42,title=tests%3A%3Atest_add::assertion `left == right` failed%0A%0Athread 'tests::test_add' panicked at src/lib.rs:42:9:%0A  left: 3%0A right: 4",
+            "::error title=tests%3A%3Atest_io::No such file & directory",
+            "::error title=tests%3A%3Atest_cdata::expected <a> got <b>",
+        ]
+    );
+
+    std::env::remove_var("GITHUB_WORKSPACE");
+}
@@ -0,0 +1,59 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Resources Action")]
+struct ResourcesAction {}
+
+#[test]
+fn test_action_path() {
+    let action_dir =
+        std::env::temp_dir().join(format!("ghactions-resources-{}", std::process::id()));
+    std::fs::create_dir_all(&action_dir).unwrap();
+    std::fs::write(action_dir.join("matcher.json"), "{}").unwrap();
+
+    // 1. GITHUB_ACTION_PATH
+    std::env::set_var("GITHUB_ACTION_PATH", action_dir.as_path());
+    assert_eq!(
+        ResourcesAction::action_path().unwrap(),
+        action_dir.as_path()
+    );
+    assert_eq!(
+        ResourcesAction::action_resource("matcher.json").unwrap(),
+        action_dir.as_path().join("matcher.json")
+    );
+    assert!(ResourcesAction::action_resource("../matcher.json").is_err());
+    assert!(ResourcesAction::action_resource("missing.json").is_err());
+
+    // Missing directories are ignored
+    std::env::set_var("GITHUB_ACTION_PATH", action_dir.as_path().join("missing"));
+    assert_ne!(
+        ResourcesAction::action_path().unwrap(),
+        action_dir.as_path()
+    );
+
+    // 2. Next to the Action binary
+    std::env::remove_var("GITHUB_ACTION_PATH");
+    let exe_dir = std::env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let action_file = exe_dir.join("action.yml");
+    std::fs::write(&action_file, "name: test").unwrap();
+    let result = ResourcesAction::action_path();
+    std::fs::remove_file(&action_file).unwrap();
+    assert_eq!(result.unwrap(), exe_dir);
+
+    // 3. Current working directory
+    let cwd = std::env::current_dir().unwrap();
+    std::env::set_current_dir(action_dir.as_path()).unwrap();
+    let result = ResourcesAction::action_path();
+    std::env::set_current_dir(cwd).unwrap();
+    assert_eq!(
+        result.unwrap().canonicalize().unwrap(),
+        action_dir.canonicalize().unwrap()
+    );
+    std::fs::remove_dir_all(&action_dir).unwrap();
+}
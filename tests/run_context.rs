@@ -0,0 +1,44 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Run Context Action")]
+struct RunContextAction {}
+
+#[test]
+fn test_run_context() {
+    let action = RunContextAction {};
+
+    std::env::set_var("GITHUB_SERVER_URL", "https://github.example.com/");
+    std::env::set_var("GITHUB_REPOSITORY", "42ByteLabs/ghactions");
+    std::env::set_var("GITHUB_RUN_ID", "9876543210");
+    std::env::set_var("GITHUB_RUN_NUMBER", "12");
+    std::env::set_var("GITHUB_RUN_ATTEMPT", "2");
+    std::env::set_var("GITHUB_JOB", "build");
+    std::env::set_var("GITHUB_WORKFLOW", "CI");
+    std::env::set_var("GITHUB_ACTION", "__run_2");
+
+    assert_eq!(action.get_run_id().unwrap(), 9876543210);
+    assert_eq!(action.get_run_number().unwrap(), 12);
+    assert_eq!(action.get_run_attempt().unwrap(), 2);
+    assert_eq!(action.get_job().unwrap(), "build");
+    assert_eq!(action.get_workflow().unwrap(), "CI");
+    assert_eq!(action.get_action().unwrap(), "__run_2");
+    assert_eq!(
+        action.get_run_url().unwrap(),
+        "https://github.example.com/42ByteLabs/ghactions/actions/runs/9876543210"
+    );
+
+    std::env::set_var("GITHUB_RUN_ATTEMPT", "first");
+    assert_eq!(
+        action.get_run_attempt(),
+        Err(ActionsError::InputTypeError(
+            "GITHUB_RUN_ATTEMPT".to_string(),
+            "u32".to_string()
+        ))
+    );
+
+    std::env::remove_var("GITHUB_RUN_ID");
+    assert!(action.get_run_url().is_err());
+}
@@ -0,0 +1,28 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Runner Action", min_runner_version = "2.300.0")]
+struct RunnerAction {}
+
+#[test]
+fn test_min_runner_version() {
+    // Older runner
+    std::env::set_var("RUNNER_VERSION", "2.299.1");
+    assert_eq!(
+        RunnerAction::init().unwrap_err(),
+        ActionsError::RunnerVersionError {
+            found: "2.299.1".to_string(),
+            required: "2.300.0".to_string(),
+        }
+    );
+
+    // Newer runner
+    std::env::set_var("RUNNER_VERSION", "2.311.0");
+    assert!(RunnerAction::init().is_ok());
+
+    // Unknown version (only a notice is logged)
+    std::env::remove_var("RUNNER_VERSION");
+    assert!(RunnerAction::init().is_ok());
+}
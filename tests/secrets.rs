@@ -0,0 +1,93 @@
+#![allow(dead_code)]
+
+use std::process::Command;
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Secrets Action")]
+struct SecretsAction {
+    #[input(description = "API Token", secret = true)]
+    secrets_token: String,
+
+    #[input(description = "Optional API Key", secret)]
+    secrets_key: String,
+
+    #[input(description = "Name")]
+    secrets_name: String,
+}
+
+/// Run by [test_secret_inputs_masked] in a child process so stdout can be captured
+#[test]
+fn secrets_child() {
+    if std::env::var("SECRETS_CHILD").is_err() {
+        return;
+    }
+    let action = SecretsAction::init().unwrap();
+    assert_eq!(action.secrets_token, "ghp_abc123");
+}
+
+#[test]
+fn test_secret_inputs_masked() {
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args([
+            "secrets_child",
+            "--exact",
+            "--nocapture",
+            "--test-threads=1",
+        ])
+        .env("SECRETS_CHILD", "1")
+        .env("INPUT_SECRETS_TOKEN", "ghp_abc123")
+        .env("INPUT_SECRETS_KEY", "")
+        .env("INPUT_SECRETS_NAME", "geekmasher")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // The test harness prints the test name on the same line
+    let masks: Vec<&str> = stdout
+        .lines()
+        .filter_map(|line| line.find("::add-mask::").map(|index| &line[index..]))
+        .collect();
+    // Empty secrets and non-secret inputs are not masked
+    assert_eq!(masks, vec!["::add-mask::ghp_abc123"]);
+}
+
+/// Run by [test_add_mask_commands] in a child process so stdout can be captured
+#[test]
+fn mask_child() {
+    if std::env::var("MASK_CHILD").is_err() {
+        return;
+    }
+    SecretsAction::add_mask("ghs_token");
+    SecretsAction::add_mask("");
+    SecretsAction::add_mask("first line\r\n100%\n\nlast\rline");
+    mask!(String::from("macro_token"));
+}
+
+#[test]
+fn test_add_mask_commands() {
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args(["mask_child", "--exact", "--nocapture", "--test-threads=1"])
+        .env("MASK_CHILD", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let masks: Vec<&str> = stdout
+        .lines()
+        .filter_map(|line| line.find("::add-mask::").map(|index| &line[index..]))
+        .collect();
+    assert_eq!(
+        masks,
+        vec![
+            "::add-mask::ghs_token",
+            "::add-mask::first line",
+            "::add-mask::100%25",
+            "::add-mask::last%0Dline",
+            "::add-mask::macro_token",
+        ]
+    );
+}
@@ -0,0 +1,48 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+use ghactions_core::testing::TestEnv;
+
+#[derive(Actions, Debug)]
+#[action(name = "Set Env Action")]
+struct SetEnvAction {}
+
+#[test]
+fn test_set_env() {
+    let env = TestEnv::new();
+
+    SetEnvAction::set_env("TOOL_VERSION", "1.2.3").unwrap();
+    SetEnvAction::set_env("TOOL_NOTES", "first line\nsecond line").unwrap();
+    setenv!("TOOL_COUNT", 42).unwrap();
+
+    assert_eq!(
+        env.exported_env().into_iter().collect::<Vec<_>>(),
+        vec![
+            ("TOOL_VERSION".to_string(), "1.2.3".to_string()),
+            (
+                "TOOL_NOTES".to_string(),
+                "first line\nsecond line".to_string()
+            ),
+            ("TOOL_COUNT".to_string(), "42".to_string()),
+        ]
+    );
+
+    // Also set for the current process (until the test environment is dropped)
+    assert_eq!(std::env::var("TOOL_VERSION").unwrap(), "1.2.3");
+
+    assert_eq!(
+        SetEnvAction::set_env("TOOL=VERSION", "1.2.3"),
+        Err(ActionsError::EnvNameError("TOOL=VERSION".to_string()))
+    );
+    assert_eq!(
+        setenv!("A=B", "x"),
+        Err(ActionsError::EnvNameError("A=B".to_string()))
+    );
+    assert_eq!(
+        setenv!("A\nB", "x"),
+        Err(ActionsError::EnvNameError("A\nB".to_string()))
+    );
+
+    drop(env);
+    assert!(std::env::var("TOOL_VERSION").is_err());
+}
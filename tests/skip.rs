@@ -0,0 +1,127 @@
+#![allow(dead_code)]
+
+use std::time::{Duration, Instant};
+
+use ghactions::prelude::*;
+
+/// State computed by the Action (not an input or output)
+#[derive(Debug, PartialEq)]
+struct Stats {
+    processed: usize,
+    started_at: Instant,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self {
+            processed: 0,
+            started_at: Instant::now(),
+        }
+    }
+}
+
+#[derive(Actions, Debug)]
+#[action(name = "Skip Action")]
+struct SkipAction {
+    #[input(description = "Mode", default = "fast")]
+    mode: String,
+
+    #[action(skip)]
+    stats: Stats,
+
+    client: Option<String>,
+
+    retries: Vec<Duration>,
+
+    #[output(description = "Output Version")]
+    version: String,
+}
+
+#[test]
+fn test_skipped_fields_default() {
+    let action = SkipAction::init().unwrap();
+    assert_eq!(action.mode, "fast");
+    assert_eq!(action.stats.processed, 0);
+    assert!(action.stats.started_at.elapsed() < Duration::from_secs(60));
+    assert_eq!(action.client, None);
+    assert!(action.retries.is_empty());
+}
+
+#[test]
+fn test_skipped_fields_action_file() {
+    assert_eq!(
+        SkipAction::verify_action_file("tests/fixtures/skip.yml"),
+        Ok(())
+    );
+}
+
+/// Set when the test runs in a child process (the `main` of the Action, `checked` or `unchecked`)
+const CHILD_ENV: &str = "GHACTIONS_SKIP_TEST_CHILD";
+
+#[derive(Actions, Debug)]
+#[action(name = "Skip Event Action", events = "push", events_mode = "skip")]
+struct SkipEventAction {
+    #[input(description = "Token", required = true)]
+    token: String,
+}
+
+/// `main` of an Action checking if the event is skipped before `init()`
+fn checked_main() -> Result<(), ActionsError> {
+    if SkipEventAction::should_skip() {
+        return Ok(());
+    }
+    let action = SkipEventAction::init()?;
+    println!("token: {}", action.token);
+    Ok(())
+}
+
+/// `main` of an Action only using `init()`
+fn unchecked_main() -> Result<(), ActionsError> {
+    let action = SkipEventAction::init()?;
+    println!("token: {}", action.token);
+    Ok(())
+}
+
+/// Run the `main` in a child process and return its exit status and stdout
+fn run_child(main: &str) -> (Option<i32>, String) {
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .args(["test_skip_main", "--exact", "--nocapture"])
+        .env(CHILD_ENV, main)
+        .env("GITHUB_EVENT_NAME", "schedule")
+        // The notice is a workflow command (not written to stderr as in local mode)
+        .env("GITHUB_ACTIONS", "true")
+        .env_remove("GHACTIONS_LOCAL_MODE")
+        .env_remove("INPUT_TOKEN")
+        .output()
+        .unwrap();
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn test_skip_main() {
+    if let Ok(main) = std::env::var(CHILD_ENV) {
+        // Same exit code as a `fn main() -> Result<(), ActionsError>`
+        let result = match main.as_str() {
+            "checked" => checked_main(),
+            _ => unchecked_main(),
+        };
+        std::process::exit(if result.is_ok() { 0 } else { 1 });
+    }
+
+    // The Action exits successfully without parsing the (missing) required input
+    let (code, stdout) = run_child("checked");
+    assert_eq!(code, Some(0), "{}", stdout);
+    assert!(!stdout.contains("token:"), "{}", stdout);
+
+    // `init()?` fails with the skipped event after printing the notice
+    let (code, stdout) = run_child("unchecked");
+    assert_eq!(code, Some(1), "{}", stdout);
+    assert!(
+        stdout.contains("::notice::Skipping Action, event `schedule` is not supported"),
+        "{}",
+        stdout
+    );
+}
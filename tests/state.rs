@@ -0,0 +1,38 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "State Action")]
+struct StateAction {}
+
+#[test]
+fn test_state_round_trip() {
+    let path = std::env::temp_dir().join(format!("ghactions-state-{}.env", std::process::id()));
+    std::env::set_var("GITHUB_STATE", &path);
+    ghactions::reset_paths_for_testing();
+
+    // Main step
+    StateAction::save_state("cache-key", "linux-1234").unwrap();
+    StateAction::save_state("files", "Cargo.lock\ntarget/").unwrap();
+
+    // The runner passes the state to the post step as `STATE_<key>`
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    for (key, value) in ghactions::parse_env_file(&content) {
+        std::env::set_var(format!("STATE_{}", key), value);
+    }
+
+    // Post step
+    assert_eq!(
+        StateAction::get_state("cache-key"),
+        Some("linux-1234".to_string())
+    );
+    assert_eq!(
+        StateAction::get_state("files"),
+        Some("Cargo.lock\ntarget/".to_string())
+    );
+    assert_eq!(StateAction::get_state("missing"), None);
+
+    assert!(StateAction::save_state("key=value", "value").is_err());
+}
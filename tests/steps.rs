@@ -0,0 +1,46 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Steps Action",
+    path = "./target/ghactions-tests/steps.yml",
+    compile_step_name = "Build the tool",
+    run_step_name = "Run the tool",
+    run_step_env = "RUST_LOG=debug",
+    run_step_env = "TOOL_MODE=fast"
+)]
+struct StepsAction {}
+
+#[test]
+fn test_steps_yaml() {
+    let content = std::fs::read_to_string("./target/ghactions-tests/steps.yml").unwrap();
+    let runs = &content[content.find("runs:").unwrap()..];
+    let binary = std::env::var("CARGO_BIN_NAME").unwrap_or_else(|_| "action".to_string());
+
+    assert_eq!(
+        runs,
+        format!(
+            r#"runs:
+  using: composite
+  steps:
+  - name: Build the tool
+    shell: bash
+    run: |-
+      set -e
+      cargo install --path "${{{{ github.action_path }}}}"
+  - id: cargo-run
+    name: Run the tool
+    shell: bash
+    run: |-
+      set -e
+      {}
+    env:
+      RUST_LOG: debug
+      TOOL_MODE: fast
+"#,
+            binary
+        )
+    );
+}
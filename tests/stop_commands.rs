@@ -0,0 +1,62 @@
+use std::process::Command;
+
+use ghactions::{log_untrusted, stop_commands};
+
+/// Run by [test_stop_commands] in a child process so stdout can be captured
+#[test]
+fn stop_commands_child() {
+    if std::env::var("STOP_COMMANDS_CHILD").is_err() {
+        return;
+    }
+    log_untrusted("Fix ::error::pwned\n::add-mask::token");
+    {
+        let _outer = stop_commands();
+        {
+            let _inner = stop_commands();
+            println!("::set-env name=PATH::/tmp");
+        }
+        log_untrusted("nested");
+    }
+    println!("::debug::resumed");
+}
+
+#[test]
+fn test_stop_commands() {
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args([
+            "stop_commands_child",
+            "--exact",
+            "--nocapture",
+            "--test-threads=1",
+        ])
+        .env("STOP_COMMANDS_CHILD", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // The test harness prints the test name on the same line
+    let lines: Vec<&str> = stdout
+        .lines()
+        .filter_map(|line| match line.find("::") {
+            Some(index) => Some(&line[index..]),
+            None if line == "nested" => Some(line),
+            None => None,
+        })
+        .collect();
+    assert_eq!(lines.len(), 9, "{:#?}", lines);
+
+    let first = lines[0].strip_prefix("::stop-commands::").unwrap();
+    assert_eq!(first.len(), 32);
+    assert_eq!(lines[1], "::error::pwned");
+    assert_eq!(lines[2], "::add-mask::token");
+    assert_eq!(lines[3], format!("::{}::", first));
+
+    // Nested guards resume using the token of the outer guard
+    let second = lines[4].strip_prefix("::stop-commands::").unwrap();
+    assert_ne!(first, second);
+    assert_eq!(lines[5], "::set-env name=PATH::/tmp");
+    assert_eq!(lines[6], "nested");
+    assert_eq!(lines[7], format!("::{}::", second));
+    assert_eq!(lines[8], "::debug::resumed");
+}
@@ -0,0 +1,44 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+use ghactions_core::testing::TestEnv;
+
+#[derive(Actions, Debug)]
+#[action(name = "Summary Action")]
+struct SummaryAction {}
+
+#[test]
+fn test_summary_write() {
+    let action = SummaryAction {};
+
+    // Missing `GITHUB_STEP_SUMMARY` when running in GitHub Actions
+    {
+        let _env = TestEnv::new()
+            .remove("GITHUB_STEP_SUMMARY")
+            .github_env("GHACTIONS_LOCAL_MODE", "false");
+        let mut summary = action.summary();
+        summary.text("Lost");
+        assert_eq!(summary.write(), Err(ActionsError::SummaryUnavailable));
+        assert_eq!(summary.as_str(), "Lost\n\n");
+    }
+
+    let env = TestEnv::new();
+    let mut summary = action.summary();
+    summary.heading("Tests", 2).table(
+        &["Suite", "Passed"],
+        &[vec!["unit", "10"], vec!["e2e", "2"]],
+    );
+    summary.write().unwrap();
+    assert!(summary.is_empty());
+
+    summary.text("Done");
+    summary.write().unwrap();
+    assert_eq!(
+        env.summary(),
+        "## Tests\n\n| Suite | Passed |\n| --- | --- |\n| unit | 10 |\n| e2e | 2 |\n\nDone\n\n"
+    );
+
+    summary.text("Replaced");
+    summary.overwrite().unwrap();
+    assert_eq!(env.summary(), "Replaced\n\n");
+}
@@ -0,0 +1,69 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Token Action")]
+struct TokenAction {
+    #[input(description = "GitHub Token", default = "")]
+    token: String,
+}
+
+fn token(vars: &[(&str, &str)]) -> Result<String, ActionsError> {
+    let env: HashMap<String, String> = vars
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    let action = TokenAction::init_from(&env).unwrap();
+    ghactions::env::with_env(env, || action.get_token())
+}
+
+#[test]
+fn test_token_sources() {
+    assert_eq!(token(&[("GITHUB_TOKEN", "env")]).unwrap(), "env");
+    assert_eq!(
+        token(&[("ACTIONS_RUNTIME_TOKEN", "runtime")]).unwrap(),
+        "runtime"
+    );
+    assert_eq!(token(&[("INPUT_TOKEN", "input")]).unwrap(), "input");
+    assert_eq!(
+        token(&[("INPUT_GITHUB_TOKEN", "github_token")]).unwrap(),
+        "github_token"
+    );
+    assert_eq!(
+        token(&[("INPUT_GITHUB-TOKEN", "github-token")]).unwrap(),
+        "github-token"
+    );
+    assert_eq!(
+        token(&[]).unwrap_err(),
+        ActionsError::InputError("GITHUB_TOKEN".to_string())
+    );
+}
+
+#[test]
+fn test_token_precedence() {
+    let all = [
+        ("GITHUB_TOKEN", "env"),
+        ("ACTIONS_RUNTIME_TOKEN", "runtime"),
+        ("INPUT_TOKEN", "input"),
+        ("INPUT_GITHUB_TOKEN", "github_token"),
+        ("INPUT_GITHUB-TOKEN", "github-token"),
+    ];
+    assert_eq!(token(&all).unwrap(), "env");
+    assert_eq!(token(&all[1..]).unwrap(), "runtime");
+    assert_eq!(token(&all[2..]).unwrap(), "input");
+    assert_eq!(token(&all[3..]).unwrap(), "github_token");
+
+    // Empty values (e.g. an input without a value) are skipped
+    assert_eq!(
+        token(&[
+            ("GITHUB_TOKEN", ""),
+            ("INPUT_TOKEN", ""),
+            ("INPUT_GITHUB_TOKEN", "x")
+        ])
+        .unwrap(),
+        "x"
+    );
+}
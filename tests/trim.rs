@@ -0,0 +1,71 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Trim Action")]
+struct TrimAction {
+    #[input(description = "Name", trim = true)]
+    trim_name: String,
+
+    #[input(description = "Default name")]
+    trim_default: String,
+
+    #[input(description = "Raw name", trim = false)]
+    trim_raw: String,
+
+    #[input(description = "Count")]
+    trim_count: i32,
+
+    #[input(description = "Enabled")]
+    trim_enabled: bool,
+
+    #[input(description = "List", split = ",", trim)]
+    trim_list: Vec<String>,
+
+    #[input(description = "Raw list", split = ",", trim = false)]
+    trim_raw_list: Vec<String>,
+}
+
+#[test]
+fn test_trim_inputs() {
+    std::env::set_var("INPUT_TRIM_NAME", " \tgeekmasher\n");
+    std::env::set_var("INPUT_TRIM_DEFAULT", " default\r\n");
+    std::env::set_var("INPUT_TRIM_RAW", " raw\n");
+    std::env::set_var("INPUT_TRIM_COUNT", "\t42 \n");
+    std::env::set_var("INPUT_TRIM_ENABLED", "true\n");
+    std::env::set_var("INPUT_TRIM_LIST", " a ,\tb\n, c");
+    std::env::set_var("INPUT_TRIM_RAW_LIST", " a , b\n");
+
+    let action = TrimAction::init().unwrap();
+    assert_eq!(action.trim_name, "geekmasher");
+    assert_eq!(action.trim_default, "default");
+    assert_eq!(action.trim_raw, " raw\n");
+    assert_eq!(action.trim_count, 42);
+    assert!(action.trim_enabled);
+    assert_eq!(action.trim_list, vec!["a", "b", "c"]);
+    assert_eq!(action.trim_raw_list, vec![" a ", " b\n"]);
+}
+
+#[test]
+fn test_trim_getters() {
+    std::env::set_var("INPUT_GETTER_BOOL", " true \n");
+    std::env::set_var("INPUT_GETTER_INT", "42\r\n");
+    std::env::set_var("INPUT_GETTER_PATH", "./action.yml\n");
+    std::env::set_var("INPUT_GETTER_LIST", "a, b ,c\n");
+
+    assert_eq!(TrimAction::get_input_bool("getter_bool"), Ok(true));
+    assert_eq!(TrimAction::get_input_int("getter_int"), Ok(42));
+    assert_eq!(
+        TrimAction::get_input("getter_path"),
+        Ok("./action.yml".to_string())
+    );
+    assert_eq!(
+        TrimAction::get_input_untrimmed("getter_path"),
+        Ok("./action.yml\n".to_string())
+    );
+    assert_eq!(
+        TrimAction::get_input_vec("getter_list", ","),
+        Ok(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+    );
+}
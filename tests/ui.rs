@@ -0,0 +1,10 @@
+#[test]
+fn test_ui() {
+    let tests = trybuild::TestCases::new();
+    tests.pass("tests/ui/generic-action.rs");
+    tests.compile_fail("tests/ui/flatten-collision.rs");
+    tests.compile_fail("tests/ui/skip-input.rs");
+    tests.compile_fail("tests/ui/multiline-type.rs");
+    tests.compile_fail("tests/ui/flag-type.rs");
+    tests.compile_fail("tests/ui/pattern-type.rs");
+}
@@ -0,0 +1,10 @@
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Flag Type Action")]
+struct FlagTypeAction {
+    #[input(description = "Verbose", flag)]
+    verbose: String,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use ghactions::prelude::*;
+
+#[derive(ActionInputs, Debug)]
+struct CommonInputs {
+    #[input(description = "GitHub Token")]
+    token: String,
+}
+
+#[derive(Actions, Debug)]
+#[action(name = "Collision Action")]
+struct CollisionAction {
+    #[input(description = "Token")]
+    token: String,
+
+    #[input(flatten)]
+    common: CommonInputs,
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use std::marker::PhantomData;
+
+use ghactions::prelude::*;
+
+trait Config {}
+
+struct Production;
+
+impl Config for Production {}
+
+#[derive(Actions)]
+#[action(name = "Generic Action")]
+struct GenericAction<T: Config> {
+    #[input(description = "Mode", default = "fast")]
+    mode: String,
+
+    #[output(description = "Version")]
+    version: String,
+
+    config: PhantomData<T>,
+}
+
+fn main() {
+    let mut action = GenericAction::<Production>::init().unwrap();
+    action.set_version("1.0.0").unwrap();
+}
@@ -0,0 +1,13 @@
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Multiline Type Action")]
+struct MultilineTypeAction {
+    #[input(description = "Mode")]
+    mode: String,
+
+    #[input(description = "Paths", multiline)]
+    paths: String,
+}
+
+fn main() {}
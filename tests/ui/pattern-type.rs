@@ -0,0 +1,10 @@
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Pattern Type Action")]
+struct PatternTypeAction {
+    #[input(description = "Count", pattern = "^[0-9]+$")]
+    count: i32,
+}
+
+fn main() {}
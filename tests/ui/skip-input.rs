@@ -0,0 +1,11 @@
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Skip Input Action")]
+struct SkipInputAction {
+    #[action(skip)]
+    #[input(description = "Mode")]
+    mode: String,
+}
+
+fn main() {}
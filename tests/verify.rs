@@ -0,0 +1,102 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+use ghactions::SchemaMismatch;
+
+#[derive(Actions, Debug)]
+#[action(name = "Verify Action")]
+struct VerifyAction {
+    #[input(description = "GitHub Token", required = true)]
+    token: String,
+
+    #[input(description = "Mode", default = "fast")]
+    mode: String,
+
+    #[output(description = "Output Version")]
+    version: String,
+}
+
+#[test]
+fn test_verify_matching() {
+    assert_eq!(
+        VerifyAction::verify_action_file("tests/fixtures/verify-matching.yml"),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_verify_missing_input() {
+    assert_eq!(
+        VerifyAction::verify_action_file("tests/fixtures/verify-missing-input.yml"),
+        Err(vec![SchemaMismatch::InputRemoved("token".to_string())])
+    );
+}
+
+#[test]
+fn test_verify_changed_default() {
+    assert_eq!(
+        VerifyAction::verify_action_file("tests/fixtures/verify-changed-default.yml"),
+        Err(vec![SchemaMismatch::InputDefaultChanged(
+            "mode".to_string(),
+            Some("fast".to_string()),
+            Some("slow".to_string())
+        )])
+    );
+}
+
+#[test]
+fn test_verify_missing_file() {
+    let result = VerifyAction::verify_action_file("tests/fixtures/does-not-exist.yml");
+    assert!(matches!(
+        result.unwrap_err().as_slice(),
+        [SchemaMismatch::LoadError(_)]
+    ));
+}
+
+#[derive(Actions, Debug)]
+#[action(
+    name = "Verify On Init Action",
+    path = "./target/ghactions-tests/verify-on-init.yml",
+    verify_on_init
+)]
+struct VerifyOnInitAction {
+    #[input(description = "Mode", default = "fast")]
+    mode: String,
+}
+
+fn init_with(vars: &[(&str, &str)]) -> Result<VerifyOnInitAction, ActionsError> {
+    let env: std::collections::HashMap<String, String> = vars
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    ghactions::env::with_env(env, VerifyOnInitAction::init)
+}
+
+#[test]
+fn test_verify_on_init() {
+    // Action directory with a hand edited copy of the Action file
+    let action_dir = std::path::Path::new("./target/ghactions-tests/verify-on-init-action");
+    let edited = action_dir.join("target/ghactions-tests/verify-on-init.yml");
+    std::fs::create_dir_all(edited.parent().unwrap()).unwrap();
+    std::fs::write(
+        &edited,
+        "name: Verify On Init Action\ninputs:\n  mode:\n    description: Mode\n    default: slow\n",
+    )
+    .unwrap();
+    std::env::set_var("GITHUB_ACTION_PATH", action_dir);
+
+    // Only checked in debug mode or CI
+    assert!(init_with(&[]).is_ok());
+    assert!(matches!(
+        init_with(&[("CI", "true")]),
+        Err(ActionsError::ActionFileMismatch(_))
+    ));
+    assert!(matches!(
+        init_with(&[("RUNNER_DEBUG", "1")]),
+        Err(ActionsError::ActionFileMismatch(_))
+    ));
+
+    // The generated file in the current directory matches
+    std::env::remove_var("GITHUB_ACTION_PATH");
+    assert!(init_with(&[("CI", "true")]).is_ok());
+}
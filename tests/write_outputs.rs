@@ -0,0 +1,38 @@
+#![allow(dead_code)]
+
+use ghactions::prelude::*;
+
+#[derive(Actions, Debug)]
+#[action(name = "Write Outputs Action")]
+struct WriteOutputsAction {
+    #[output(description = "Version")]
+    version: String,
+
+    #[output(description = "Count")]
+    count: u64,
+
+    #[output(description = "Skipped")]
+    skipped: String,
+
+    #[output(description = "Report", always = true)]
+    report: String,
+}
+
+#[test]
+fn test_write_outputs() {
+    let path = std::env::temp_dir().join(format!(
+        "ghactions-write-outputs-{}.env",
+        std::process::id()
+    ));
+    std::env::set_var("GITHUB_OUTPUT", &path);
+    ghactions::reset_paths_for_testing();
+
+    let mut action = WriteOutputsAction::init().unwrap();
+    action.version = "1.2.3".to_string();
+    action.count = 42;
+    action.write_outputs().unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(content, "version=1.2.3\ncount=42\nreport=\n");
+}